@@ -0,0 +1,109 @@
+use crate::grid::GridError;
+use std::{error::Error, fmt::Display, str::FromStr};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AocError {
+    Invalid { what: &'static str, token: String },
+    Missing(&'static str),
+    Grid(GridError),
+    Line { line: usize, error: Box<AocError> },
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid { what, token } => write!(f, "invalid {} `{}`", what, token),
+            Self::Missing(what) => write!(f, "missing {}", what),
+            Self::Grid(err) => write!(f, "{}", err),
+            Self::Line { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl Error for AocError {}
+
+impl From<GridError> for AocError {
+    fn from(err: GridError) -> Self {
+        Self::Grid(err)
+    }
+}
+
+impl AocError {
+    pub fn invalid(what: &'static str, token: &str) -> Self {
+        Self::Invalid {
+            what,
+            token: token.to_string(),
+        }
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            Self::Line { .. } => self,
+            error => Self::Line {
+                line,
+                error: Box::new(error),
+            },
+        }
+    }
+}
+
+pub fn parse_number<T: FromStr>(what: &'static str, token: &str) -> Result<T, AocError> {
+    token
+        .trim()
+        .parse()
+        .map_err(|_| AocError::invalid(what, token))
+}
+
+pub fn split_once<'a>(
+    s: &'a str,
+    delimiter: &str,
+    what: &'static str,
+) -> Result<(&'a str, &'a str), AocError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| AocError::invalid(what, s))
+}
+
+// Parses every non-blank line, tagging errors with their 1-based line number.
+pub fn parse_lines<'a, T>(
+    s: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, AocError>,
+) -> Result<Vec<T>, AocError> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| parse(line).map_err(|err| err.at_line(idx + 1)))
+        .collect()
+}
+
+pub trait OrExit<T> {
+    fn or_exit(self, context: &str) -> T;
+}
+
+impl<T, E: Display> OrExit<T> for Result<T, E> {
+    fn or_exit(self, context: &str) -> T {
+        self.unwrap_or_else(|err| {
+            eprintln!("{}: {}", context, err);
+            std::process::exit(1);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::*;
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(
+            parse_lines("1\n\n2", |line| parse_number::<u8>("number", line)),
+            Ok(vec![1, 2])
+        );
+        let err = parse_lines("1\n\nx", |line| parse_number::<u8>("number", line)).unwrap_err();
+        assert_eq!(err.to_string(), "line 3: invalid number `x`");
+        assert_eq!(err.clone().at_line(7), err);
+        assert_eq!(
+            split_once("a-b", ":", "pair").unwrap_err().to_string(),
+            "invalid pair `a-b`"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use crate::AocError;
+use std::{
+    fmt::Display,
+    ops::{Add, Sub},
+};
+
+// Screen orientation, like Grid: y grows downwards, so Up is a negative step.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turn_left().opposite()
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Self::Up => (0, -1),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+            Self::Right => (1, 0),
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Down => 'v',
+            Self::Left => '<',
+            Self::Right => '>',
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Up => "U",
+            Self::Down => "D",
+            Self::Left => "L",
+            Self::Right => "R",
+        })
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'U' => Ok(Self::Up),
+            'D' => Ok(Self::Down),
+            'L' => Ok(Self::Left),
+            'R' => Ok(Self::Right),
+            _ => Err(AocError::invalid("direction", &value.to_string())),
+        }
+    }
+}
+
+// One step on a width x height grid, or None when it would leave the grid.
+pub fn step(
+    (x, y): (usize, usize),
+    direction: Direction,
+    width: usize,
+    height: usize,
+) -> Option<(usize, usize)> {
+    let (dx, dy) = direction.offset();
+    let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+    (x < width && y < height).then_some((x, y))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn step(self, direction: Direction, distance: i64) -> Self {
+        let (dx, dy) = direction.offset();
+        Self {
+            x: self.x + dx as i64 * distance,
+            y: self.y + dy as i64 * distance,
+        }
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::*;
+
+    #[test]
+    fn test_direction() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.opposite().opposite(), direction);
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(
+                Direction::try_from(direction.to_string().chars().next().unwrap()),
+                Ok(direction)
+            );
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert!(Direction::Down.is_vertical() && !Direction::Left.is_vertical());
+        assert_eq!(Direction::Left.arrow(), '<');
+        assert_eq!(
+            Direction::try_from('X').unwrap_err().to_string(),
+            "invalid direction `X`"
+        );
+    }
+
+    #[test]
+    fn test_step() {
+        assert_eq!(step((0, 0), Direction::Right, 2, 2), Some((1, 0)));
+        assert_eq!(step((0, 0), Direction::Up, 2, 2), None);
+        assert_eq!(step((1, 1), Direction::Down, 2, 2), None);
+        let point = Point::ORIGIN
+            .step(Direction::Up, 3)
+            .step(Direction::Right, 4);
+        assert_eq!(point, Point::new(4, -3));
+        assert_eq!(point.manhattan(Point::ORIGIN), 7);
+        assert_eq!(point - Point::new(1, 1) + Point::from((1, 1)), point);
+    }
+}
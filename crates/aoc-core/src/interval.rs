@@ -0,0 +1,219 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, Sub},
+};
+
+pub trait Bound: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! bound {
+    ($($t:ty),*) => {
+        $(impl Bound for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+bound!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+// Half-open, so `start..end` holds `end - start` values and is empty once
+// `end <= start`. Empty intervals are valid values, which keeps intersection
+// total.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn inclusive(start: T, last: T) -> Self {
+        Self::new(start, last + T::ONE)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn len(&self) -> T {
+        match self.is_empty() {
+            true => T::ZERO,
+            false => self.end - self.start,
+        }
+    }
+
+    // The largest value in the interval, which must not be empty.
+    pub fn last(&self) -> T {
+        assert!(!self.is_empty(), "empty interval has no last value");
+        self.end - T::ONE
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let start = self.start.max(other.start);
+        Self::new(start, self.end.min(other.end).max(start))
+    }
+
+    // The interval covering both, if they share a value. Intervals that only
+    // touch, like 1..3 and 3..5, are left apart.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other)
+            .then(|| Self::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    // What is left of `self` below and above `other`, skipping empty pieces.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        if !self.overlaps(other) {
+            return match self.is_empty() {
+                true => vec![],
+                false => vec![*self],
+            };
+        }
+        [
+            Self::new(self.start, other.start),
+            Self::new(other.end, self.end),
+        ]
+        .into_iter()
+        .filter(|piece| !piece.is_empty())
+        .collect()
+    }
+
+    // Splits into the values below `at` and the rest.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let at = at.max(self.start).min(self.end.max(self.start));
+        (Self::new(self.start, at), Self::new(at, self.end))
+    }
+
+    pub fn shift(&self, by: T) -> Self {
+        Self::new(self.start + by, self.end + by)
+    }
+}
+
+// Sorted, with overlapping intervals merged and empty ones dropped.
+pub fn coalesce<T: Bound>(intervals: impl IntoIterator<Item = Interval<T>>) -> Vec<Interval<T>> {
+    let mut intervals: Vec<Interval<T>> = intervals
+        .into_iter()
+        .filter(|interval| !interval.is_empty())
+        .collect();
+    intervals.sort();
+    let mut result: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match result
+            .last_mut()
+            .and_then(|prev| Some((prev.union(&interval)?, prev)))
+        {
+            Some((merged, prev)) => *prev = merged,
+            None => result.push(interval),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interval::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(1, 5);
+        assert_eq!((a.len(), a.last()), (4, 4));
+        assert_eq!(a, Interval::inclusive(1, 4));
+        assert!(a.contains(1) && !a.contains(5));
+        assert_eq!(a.intersection(&Interval::new(3, 9)), Interval::new(3, 5));
+        assert!(a.intersection(&Interval::new(7, 9)).is_empty());
+        assert_eq!(a.union(&Interval::new(3, 9)), Some(Interval::new(1, 9)));
+        assert_eq!(a.union(&Interval::new(5, 9)), None);
+        assert_eq!(
+            a.subtract(&Interval::new(2, 3)),
+            vec![Interval::new(1, 2), Interval::new(3, 5)]
+        );
+        assert_eq!(a.subtract(&Interval::new(0, 9)), vec![]);
+        assert_eq!(a.split_at(3), (Interval::new(1, 3), Interval::new(3, 5)));
+        assert_eq!(a.shift(-1), Interval::new(0, 4));
+        assert_eq!(Interval::new(5u16, 2).len(), 0);
+        assert_eq!(
+            coalesce([
+                Interval::new(6, 8),
+                Interval::new(1, 3),
+                Interval::new(2, 4),
+                Interval::new(4, 5),
+                Interval::new(9, 9),
+            ]),
+            vec![
+                Interval::new(1, 4),
+                Interval::new(4, 5),
+                Interval::new(6, 8)
+            ]
+        );
+    }
+
+    fn interval() -> impl Strategy<Value = Interval<i32>> {
+        (-20..20, 0..10).prop_map(|(start, len)| Interval::new(start, start + len))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_intersection(a in interval(), b in interval(), x in -30..30) {
+            let both = a.intersection(&b);
+            prop_assert_eq!(both.contains(x), a.contains(x) && b.contains(x));
+            prop_assert_eq!(both, b.intersection(&a));
+            prop_assert_eq!(a.overlaps(&b), !both.is_empty());
+            prop_assert_eq!(a.overlaps(&b), b.overlaps(&a));
+        }
+
+        #[test]
+        fn prop_union(a in interval(), b in interval(), x in -30..30) {
+            prop_assert_eq!(a.union(&b), b.union(&a));
+            if let Some(hull) = a.union(&b) {
+                prop_assert_eq!(hull.contains(x), a.contains(x) || b.contains(x));
+            }
+        }
+
+        #[test]
+        fn prop_subtract(a in interval(), b in interval(), x in -30..30) {
+            let pieces = a.subtract(&b);
+            let inside = pieces.iter().filter(|piece| piece.contains(x)).count();
+            prop_assert_eq!(inside == 1, a.contains(x) && !b.contains(x));
+            prop_assert!(inside <= 1);
+            prop_assert!(pieces.iter().all(|piece| !piece.is_empty()));
+            let total: i32 = pieces.iter().map(Interval::len).sum();
+            prop_assert_eq!(total, a.len() - a.intersection(&b).len());
+        }
+
+        #[test]
+        fn prop_split_at(a in interval(), at in -30..30, x in -30..30) {
+            let (low, high) = a.split_at(at);
+            prop_assert_eq!(low.len() + high.len(), a.len());
+            prop_assert_eq!(low.contains(x) || high.contains(x), a.contains(x));
+            prop_assert!(!low.contains(x) || x < at);
+            prop_assert!(!high.contains(x) || x >= at);
+        }
+
+        #[test]
+        fn prop_coalesce(intervals in prop::collection::vec(interval(), 0..8), x in -30..30) {
+            let merged = coalesce(intervals.clone());
+            prop_assert_eq!(
+                merged.iter().any(|interval| interval.contains(x)),
+                intervals.iter().any(|interval| interval.contains(x))
+            );
+            for pair in merged.windows(2) {
+                prop_assert!(pair[0].end <= pair[1].start);
+            }
+            prop_assert_eq!(coalesce(merged.clone()), merged);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod interval;
+pub mod matcher;
+pub mod math;
+pub mod parallel;
+pub mod progress;
+pub mod solver;
+pub mod timing;
+
+pub use error::AocError;
+pub use geometry::{Direction, Point};
+pub use grid::Grid;
+pub use interval::Interval;
+pub use matcher::Matcher;
+pub use solver::{Answer, Registration, Solver};
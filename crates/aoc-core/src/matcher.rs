@@ -0,0 +1,153 @@
+use std::collections::VecDeque;
+
+const ALPHABET: usize = 256;
+const NONE: u32 = u32::MAX;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Match {
+    // Index of the pattern in the list the matcher was built from.
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+// Aho-Corasick over bytes: every occurrence of every pattern, overlapping
+// ones included, in one pass over the haystack whatever the number of
+// patterns.
+#[derive(Clone, Debug)]
+pub struct Matcher {
+    // `next[state * 256 + byte]`, with the failure links folded in so each
+    // byte is a single lookup.
+    next: Vec<u32>,
+    // The patterns that end at each state, its suffixes' included.
+    outputs: Vec<Vec<usize>>,
+    lens: Vec<usize>,
+}
+
+impl Matcher {
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> Self {
+        let mut next = vec![NONE; ALPHABET];
+        let mut outputs = vec![Vec::new()];
+        for (index, pattern) in patterns.iter().enumerate() {
+            assert!(!pattern.as_ref().is_empty(), "empty pattern {}", index);
+            let mut state = 0;
+            for &byte in pattern.as_ref() {
+                let slot = state * ALPHABET + byte as usize;
+                if next[slot] == NONE {
+                    next[slot] = outputs.len() as u32;
+                    next.extend([NONE; ALPHABET]);
+                    outputs.push(Vec::new());
+                }
+                state = next[slot] as usize;
+            }
+            outputs[state].push(index);
+        }
+
+        // Breadth first, so a state's failure link is always finished first.
+        let mut fail = vec![0; outputs.len()];
+        let mut queue = VecDeque::new();
+        for slot in next.iter_mut().take(ALPHABET) {
+            match *slot {
+                NONE => *slot = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+            for byte in 0..ALPHABET {
+                let fallback = next[fail[state] * ALPHABET + byte];
+                let slot = state * ALPHABET + byte;
+                match next[slot] {
+                    NONE => next[slot] = fallback,
+                    child => {
+                        fail[child as usize] = fallback as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+        Self {
+            next,
+            outputs,
+            lens: patterns
+                .iter()
+                .map(|pattern| pattern.as_ref().len())
+                .collect(),
+        }
+    }
+
+    // Matches in the order they end.
+    pub fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> impl Iterator<Item = Match> + 'a {
+        haystack
+            .iter()
+            .scan(0, move |state, &byte| {
+                *state = self.next[*state * ALPHABET + byte as usize] as usize;
+                Some(*state)
+            })
+            .enumerate()
+            .flat_map(move |(i, state)| {
+                self.outputs[state].iter().map(move |&pattern| Match {
+                    pattern,
+                    start: i + 1 - self.lens[pattern],
+                    end: i + 1,
+                })
+            })
+    }
+
+    // The matches starting first and last, found in the same pass. Of two
+    // starting together the shorter comes first and the longer last.
+    pub fn first_last(&self, haystack: &[u8]) -> Option<(Match, Match)> {
+        self.find_iter(haystack).fold(None, |found, m| match found {
+            None => Some((m, m)),
+            Some((first, last)) => Some((
+                if m.start < first.start { m } else { first },
+                if m.start >= last.start { m } else { last },
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_find_iter() {
+        let matcher = Matcher::new(&["one", "eight", "ne", "e"]);
+        let found: Vec<(usize, usize)> = matcher
+            .find_iter(b"oneight")
+            .map(|m| (m.pattern, m.start))
+            .collect();
+        assert_eq!(found, [(0, 0), (2, 1), (3, 2), (1, 2)]);
+        let (first, last) = matcher.first_last(b"xoneightx").unwrap();
+        assert_eq!((first.pattern, first.start, first.end), (0, 1, 4));
+        assert_eq!((last.pattern, last.start, last.end), (1, 3, 8));
+        assert_eq!(matcher.first_last(b"xyz"), None);
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_naive(
+            patterns in prop::collection::vec("[ab]{1,3}", 1..5),
+            haystack in "[abc]{0,20}",
+        ) {
+            let matcher = Matcher::new(&patterns);
+            let mut found: Vec<(usize, usize)> = matcher
+                .find_iter(haystack.as_bytes())
+                .map(|m| (m.start, m.pattern))
+                .collect();
+            found.sort();
+            let mut naive = Vec::new();
+            for start in 0..haystack.len() {
+                for (pattern, p) in patterns.iter().enumerate() {
+                    if haystack[start..].starts_with(p.as_str()) {
+                        naive.push((start, pattern));
+                    }
+                }
+            }
+            prop_assert_eq!(found, naive);
+        }
+    }
+}
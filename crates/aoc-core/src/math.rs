@@ -0,0 +1,69 @@
+pub fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn lcm(a: usize, b: usize) -> usize {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+// `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+// Chinese remainder theorem over `(residue, modulus)` pairs, moduli not
+// necessarily coprime. Gives the smallest solution and the combined
+// modulus, or None when the congruences contradict each other.
+pub fn crt(congruences: &[(usize, usize)]) -> Option<(usize, usize)> {
+    let mut residue: i128 = 0;
+    let mut modulus: i128 = 1;
+    for &(r, m) in congruences {
+        let m = m as i128;
+        let r = r as i128 % m;
+        let (g, p, _) = extended_gcd(modulus, m);
+        if (r - residue) % g != 0 {
+            return None;
+        }
+        let step = m / g;
+        let k = ((r - residue) / g % step * (p % step)).rem_euclid(step);
+        residue += modulus * k;
+        modulus *= step;
+        residue = residue.rem_euclid(modulus);
+    }
+    Some((residue as usize, modulus as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!([2, 3, 4].into_iter().fold(1, lcm), 12);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(3, 4), (1, 2)]), Some((3, 4)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(0, 4), (1, 2)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
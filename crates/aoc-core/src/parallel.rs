@@ -0,0 +1,64 @@
+use crate::AocError;
+
+// Iterators that fan out over rayon's thread pool with the `parallel` feature
+// and stay on the calling thread without it. Days glob-import this module so
+// the rayon traits come along when they're needed, and stick to the adaptors
+// both kinds of iterator have.
+#[cfg(feature = "parallel")]
+pub use rayon::prelude::*;
+
+#[cfg(feature = "parallel")]
+pub fn par_iter<I: IntoParallelIterator>(items: I) -> I::Iter {
+    items.into_par_iter()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn par_iter<I: IntoIterator>(items: I) -> I::IntoIter {
+    items.into_iter()
+}
+
+#[cfg(feature = "parallel")]
+pub fn par_lines(s: &str) -> rayon::str::Lines<'_> {
+    s.par_lines()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn par_lines(s: &str) -> std::str::Lines<'_> {
+    s.lines()
+}
+
+// `error::parse_lines` with the lines parsed in parallel. When several lines
+// are invalid, which one gets reported depends on the scheduling.
+pub fn par_parse_lines<'a, T: Send>(
+    s: &'a str,
+    parse: impl Fn(&'a str) -> Result<T, AocError> + Send + Sync,
+) -> Result<Vec<T>, AocError> {
+    let lines: Vec<(usize, &str)> = s
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+    par_iter(lines)
+        .map(|(idx, line)| parse(line).map_err(|err| err.at_line(idx + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::parse_number, parallel::*};
+
+    #[test]
+    fn test_par_parse_lines() {
+        let parse = |line| parse_number::<u32>("number", line);
+        assert_eq!(par_parse_lines("1\n\n2\n3", parse), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            par_parse_lines("1\n\nx", parse),
+            Err(AocError::invalid("number", "x").at_line(3))
+        );
+        assert_eq!(
+            par_lines("1\n2\n3").map(|line| line.len()).sum::<usize>(),
+            3
+        );
+        assert_eq!(par_iter(1..=4u64).map(|n| n * n).max(), Some(16));
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+// Receives how far along a part is, in whole percent. Set per thread by
+// whoever is solving, so parts report progress without knowing if anyone is
+// listening.
+pub type Sink = Arc<dyn Fn(u8) + Send + Sync>;
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+struct Restore(Option<Sink>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        SINK.with(|sink| *sink.borrow_mut() = self.0.take());
+    }
+}
+
+// Runs `f` with its progress going to `sink`.
+pub fn with_sink<R>(sink: Sink, f: impl FnOnce() -> R) -> R {
+    let _restore = Restore(SINK.with(|current| current.replace(Some(sink))));
+    f()
+}
+
+// Counts finished steps out of `total`, reporting each new whole percent.
+// Made on the solving thread, it can be ticked from any.
+pub struct Progress {
+    total: usize,
+    done: AtomicUsize,
+    sink: Option<Sink>,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Self {
+        Self {
+            total: total.max(1),
+            done: AtomicUsize::new(0),
+            sink: SINK.with(|sink| sink.borrow().clone()),
+        }
+    }
+
+    pub fn tick(&self) {
+        let Some(sink) = &self.sink else {
+            return;
+        };
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        let percent = |done: usize| (done.min(self.total) * 100 / self.total) as u8;
+        if percent(done) != percent(done - 1) {
+            sink(percent(done));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::progress::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_progress() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        with_sink(
+            Arc::new(move |percent| sink.lock().unwrap().push(percent)),
+            || {
+                let progress = Progress::new(8);
+                for _ in 0..9 {
+                    progress.tick();
+                }
+            },
+        );
+        assert_eq!(*reports.lock().unwrap(), [12, 25, 37, 50, 62, 75, 87, 100]);
+        Progress::new(2).tick();
+        assert_eq!(reports.lock().unwrap().len(), 8);
+    }
+}
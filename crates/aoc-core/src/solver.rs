@@ -0,0 +1,105 @@
+use std::fmt::Display;
+
+pub trait Answer {
+    fn answer(self) -> Result<String, String>;
+}
+
+macro_rules! display_answer {
+    ($($ty:ty),*) => {
+        $(impl Answer for $ty {
+            fn answer(self) -> Result<String, String> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+display_answer!(i64, u64, usize, u128, &str);
+
+impl<T: Answer, E: Display> Answer for Result<T, E> {
+    fn answer(self) -> Result<String, String> {
+        self.map_err(|err| err.to_string())?.answer()
+    }
+}
+
+impl<T: Answer> Answer for Option<T> {
+    fn answer(self) -> Result<String, String> {
+        self.ok_or_else(|| "no answer found".to_string())?.answer()
+    }
+}
+
+// Parts start from what `parse` made of the input, so parsing happens once
+// and can be timed on its own.
+pub trait Solver {
+    type Parsed<'a>;
+    type Error: Display;
+
+    fn day() -> u8;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error>;
+    fn part1(parsed: &Self::Parsed<'_>) -> impl Answer;
+    fn part2(parsed: &Self::Parsed<'_>) -> impl Answer;
+}
+
+pub type Step = fn(&str) -> Result<String, String>;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Registration {
+    pub day: u8,
+    pub parse: fn(&str) -> Result<(), String>,
+    pub parts: [Step; 2],
+}
+
+impl Registration {
+    pub fn of<S: Solver>() -> Self {
+        Self {
+            day: S::day(),
+            parse: |input| S::parse(input).map(drop).map_err(|err| err.to_string()),
+            parts: [
+                |input| S::part1(&S::parse(input).map_err(|err| err.to_string())?).answer(),
+                |input| S::part2(&S::parse(input).map_err(|err| err.to_string())?).answer(),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{solver::*, AocError};
+
+    struct Sum;
+
+    impl Solver for Sum {
+        type Parsed<'a> = Vec<u64>;
+        type Error = AocError;
+
+        fn day() -> u8 {
+            1
+        }
+
+        fn parse(input: &str) -> Result<Vec<u64>, AocError> {
+            crate::error::parse_lines(input, |line| crate::error::parse_number("number", line))
+        }
+
+        fn part1(numbers: &Self::Parsed<'_>) -> impl Answer {
+            numbers.iter().sum::<u64>()
+        }
+
+        fn part2(numbers: &Self::Parsed<'_>) -> impl Answer {
+            numbers.iter().max().copied()
+        }
+    }
+
+    #[test]
+    fn test_registration() {
+        let sum = Registration::of::<Sum>();
+        assert_eq!(sum.day, 1);
+        assert_eq!((sum.parse)("1\n2"), Ok(()));
+        assert_eq!((sum.parts[0])("1\n2"), Ok("3".to_string()));
+        assert_eq!((sum.parts[1])("1\n2"), Ok("2".to_string()));
+        assert_eq!((sum.parts[1])(""), Err("no answer found".to_string()));
+        assert_eq!(
+            (sum.parts[0])("1\nx"),
+            Err("line 2: invalid number `x`".to_string())
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::time::{Duration, Instant};
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Timings {
+    enabled: bool,
+    phases: Vec<(String, Duration)>,
+}
+
+impl Timings {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            phases: Vec::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn phases(&self) -> &[(String, Duration)] {
+        &self.phases
+    }
+
+    // Each phase is also a tracing span, so events logged while it runs say
+    // which phase they came from.
+    pub fn time<T>(&mut self, name: &str, f: impl FnOnce() -> T) -> T {
+        let _span = tracing::info_span!("phase", name).entered();
+        let start = Instant::now();
+        let result = f();
+        self.phases.push((name.to_string(), start.elapsed()));
+        result
+    }
+
+    // For phases that are only run to be measured, like parsing on days whose
+    // part functions parse the input themselves.
+    pub fn probe<T>(&mut self, name: &str, f: impl FnOnce() -> T) {
+        if self.enabled {
+            self.time(name, f);
+        }
+    }
+
+    pub fn report(&self) -> String {
+        let width = self.phases.iter().map(|(name, _)| name.len()).max();
+        self.phases
+            .iter()
+            .map(|(name, duration)| {
+                format!(
+                    "{:<width$}  {}\n",
+                    name,
+                    format_duration(*duration),
+                    width = width.unwrap_or(0)
+                )
+            })
+            .collect()
+    }
+
+    pub fn print(&self) {
+        if self.enabled {
+            eprint!("{}", self.report());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timing::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.35µs");
+        assert_eq!(format_duration(Duration::from_micros(4_560)), "4.56ms");
+        assert_eq!(format_duration(Duration::from_millis(81_250)), "81.25s");
+    }
+
+    #[test]
+    fn test_timings() {
+        let mut timings = Timings::new(false);
+        timings.probe("parse", || unreachable!());
+        assert_eq!(timings.time("part 1", || 42), 42);
+        assert_eq!(timings.phases().len(), 1);
+        timings.phases[0].1 = Duration::from_millis(3);
+        timings
+            .phases
+            .push(("read".to_string(), Duration::from_micros(20)));
+        assert_eq!(timings.report(), "part 1  3.00ms\nread    20.00µs\n");
+    }
+}
@@ -0,0 +1,35 @@
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+#include <sys/types.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define AOC_ERR_UNKNOWN -1 /* day or part out of range */
+#define AOC_ERR_UTF8 -2    /* input is not valid UTF-8 */
+#define AOC_ERR_FAILED -3  /* the solver rejected the input */
+#define AOC_ERR_PANIC -4   /* the solver panicked */
+
+/* Number of implemented days. */
+uint8_t aoc_days(void);
+
+/*
+ * Solves one part of a day and writes the answer to out_buf as a
+ * NUL-terminated string, truncated to fit out_cap bytes.
+ *
+ * Returns the full answer length like snprintf, so a result >= out_cap means
+ * the answer was truncated. On failure returns a negative AOC_ERR_* code and
+ * writes the error message to out_buf instead.
+ */
+ssize_t aoc_solve(uint8_t day, uint8_t part, const uint8_t *input_ptr,
+                  size_t input_len, char *out_buf, size_t out_cap);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,128 @@
+use aoc_solvers::{solve, DAYS};
+use std::{
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    slice,
+};
+
+// The C API predates multi-year support and always solves this year.
+const YEAR: u16 = 2023;
+
+pub const AOC_ERR_UNKNOWN: isize = -1;
+pub const AOC_ERR_UTF8: isize = -2;
+pub const AOC_ERR_FAILED: isize = -3;
+pub const AOC_ERR_PANIC: isize = -4;
+
+// Copies as much of `text` as fits into `out`, always NUL-terminating when
+// there is any room at all.
+unsafe fn write_out(text: &str, out: *mut c_char, cap: usize) {
+    if out.is_null() || cap == 0 {
+        return;
+    }
+    let len = text.len().min(cap - 1);
+    std::ptr::copy_nonoverlapping(text.as_ptr(), out as *mut u8, len);
+    *out.add(len) = 0;
+}
+
+#[no_mangle]
+pub extern "C" fn aoc_days() -> u8 {
+    DAYS
+}
+
+/// Solves one part of a day, writing the answer into `out_buf` as a
+/// NUL-terminated string.
+///
+/// Like `snprintf`, returns the full answer length even when `out_cap` was
+/// too small for it. On failure returns one of the negative `AOC_ERR_*`
+/// codes and writes the error message into `out_buf` instead.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `input_len` readable bytes and `out_buf` to
+/// `out_cap` writable bytes. Either may be null when its length is zero.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut c_char,
+    out_cap: usize,
+) -> isize {
+    if !(1..=DAYS).contains(&day) || !(1..=2).contains(&part) {
+        write_out("unknown day or part", out_buf, out_cap);
+        return AOC_ERR_UNKNOWN;
+    }
+    let input = match input_len {
+        0 => &[][..],
+        _ => slice::from_raw_parts(input_ptr, input_len),
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        write_out("input is not valid UTF-8", out_buf, out_cap);
+        return AOC_ERR_UTF8;
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| solve(YEAR, day, part, input))) {
+        Ok(Ok(answer)) => {
+            write_out(&answer, out_buf, out_cap);
+            answer.len() as isize
+        }
+        Ok(Err(message)) => {
+            write_out(&message, out_buf, out_cap);
+            AOC_ERR_FAILED
+        }
+        Err(_) => {
+            write_out("solver panicked", out_buf, out_cap);
+            AOC_ERR_PANIC
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::ffi::CStr;
+
+    fn call(day: u8, part: u8, input: &[u8], cap: usize) -> (isize, String) {
+        let mut out = vec![0 as c_char; cap];
+        let ret = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                cap,
+            )
+        };
+        let text = match cap {
+            0 => String::new(),
+            _ => unsafe { CStr::from_ptr(out.as_ptr()) }
+                .to_string_lossy()
+                .into_owned(),
+        };
+        (ret, text)
+    }
+
+    #[test]
+    fn test_aoc_solve() {
+        assert_eq!(
+            call(1, 2, b"two1nine\neightwothree", 16),
+            (3, "112".to_string())
+        );
+        assert_eq!(
+            call(1, 2, b"two1nine\neightwothree", 3),
+            (3, "11".to_string())
+        );
+        assert_eq!(call(1, 2, b"two1nine", 0), (2, String::new()));
+        assert_eq!(call(26, 1, b"", 64).0, AOC_ERR_UNKNOWN);
+        assert_eq!(call(1, 1, b"\xff", 64).0, AOC_ERR_UTF8);
+        assert_eq!(
+            call(2, 1, b"Game 1: 3 teal", 64),
+            (AOC_ERR_FAILED, "line 1: invalid color `teal`".to_string())
+        );
+        assert_eq!(
+            call(1, 1, b"eightwothree", 64),
+            (AOC_ERR_FAILED, "line 1: missing digit".to_string())
+        );
+    }
+}
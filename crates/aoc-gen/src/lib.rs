@@ -0,0 +1,433 @@
+use rand::{
+    rngs::Xoshiro256PlusPlus,
+    seq::{IndexedRandom, SliceRandom},
+    RngExt, SeedableRng,
+};
+use std::collections::HashSet;
+
+type Rng = Xoshiro256PlusPlus;
+
+pub struct Generator {
+    pub day: u8,
+    // Roughly the size of a real input, in the unit `generate` takes.
+    pub default_size: usize,
+    pub unit: &'static str,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+pub const GENERATORS: [Generator; 12] = [
+    Generator {
+        day: 1,
+        default_size: 1000,
+        unit: "lines",
+        generate: day1,
+    },
+    Generator {
+        day: 2,
+        default_size: 100,
+        unit: "games",
+        generate: day2,
+    },
+    Generator {
+        day: 5,
+        default_size: 10,
+        unit: "seed ranges",
+        generate: day5,
+    },
+    Generator {
+        day: 9,
+        default_size: 200,
+        unit: "histories",
+        generate: day9,
+    },
+    Generator {
+        day: 11,
+        default_size: 140,
+        unit: "tiles per side",
+        generate: day11,
+    },
+    Generator {
+        day: 14,
+        default_size: 100,
+        unit: "tiles per side",
+        generate: day14,
+    },
+    Generator {
+        day: 15,
+        default_size: 4000,
+        unit: "steps",
+        generate: day15,
+    },
+    Generator {
+        day: 16,
+        default_size: 110,
+        unit: "tiles per side",
+        generate: day16,
+    },
+    Generator {
+        day: 17,
+        default_size: 141,
+        unit: "blocks per side",
+        generate: day17,
+    },
+    Generator {
+        day: 18,
+        default_size: 300,
+        unit: "columns",
+        generate: day18,
+    },
+    Generator {
+        day: 19,
+        default_size: 550,
+        unit: "workflows",
+        generate: day19,
+    },
+    Generator {
+        day: 20,
+        default_size: 12,
+        unit: "bits per counter (2 to 15)",
+        generate: day20,
+    },
+];
+
+pub fn find(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+impl Generator {
+    // The same seed and size always give the same input.
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        (self.generate)(&mut Rng::seed_from_u64(seed), size.max(1))
+    }
+}
+
+fn grid(rng: &mut Rng, size: usize, tile: impl Fn(&mut Rng) -> char) -> String {
+    (0..size)
+        .map(|_| (0..size).map(|_| tile(rng)).collect::<String>() + "\n")
+        .collect()
+}
+
+// Distinct lowercase names, none of them in `reserved`. They are 2 or 3
+// letters long like real ones, longer when that many won't fit.
+fn names(rng: &mut Rng, count: usize, reserved: &[&str]) -> Vec<String> {
+    let mut seen: HashSet<String> = reserved.iter().map(|name| name.to_string()).collect();
+    let mut names = Vec::with_capacity(count);
+    let mut shortest = 2;
+    while 26usize.pow(shortest) < 4 * (count + reserved.len()) {
+        shortest += 1;
+    }
+    while names.len() < count {
+        let len = rng.random_range(shortest..=shortest + 1);
+        let name: String = (0..len)
+            .map(|_| rng.random_range(b'a'..=b'z') as char)
+            .collect();
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn day1(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut line = rng.random_range(1..=9).to_string();
+            for _ in 0..rng.random_range(2..12) {
+                match rng.random_range(0..4) {
+                    0 => line.push(rng.random_range(b'1'..=b'9') as char),
+                    1 => line += DIGIT_WORDS.choose(rng).unwrap(),
+                    _ => line.push(rng.random_range(b'a'..=b'z') as char),
+                }
+            }
+            let mut chars: Vec<char> = line.chars().collect();
+            let shift = rng.random_range(0..chars.len());
+            chars.rotate_left(shift);
+            chars.into_iter().collect::<String>() + "\n"
+        })
+        .collect()
+}
+
+fn day2(rng: &mut Rng, size: usize) -> String {
+    (1..=size)
+        .map(|game| {
+            let draws: Vec<String> = (0..rng.random_range(1..=6))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    colors.shuffle(rng);
+                    colors[..rng.random_range(1..=3)]
+                        .iter()
+                        .map(|color| format!("{} {}", rng.random_range(1..=20), color))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            format!("Game {}: {}\n", game, draws.join("; "))
+        })
+        .collect()
+}
+
+const ALMANAC: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+// Numbers stay small and the seed ranges short, unlike real almanacs, so
+// mapping every seed on its own stays cheap.
+fn day5(rng: &mut Rng, size: usize) -> String {
+    let seeds: Vec<String> = (0..size)
+        .map(|_| format!("{} {}", rng.random_range(0..1000), rng.random_range(1..=40)))
+        .collect();
+    let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+    for name in ALMANAC {
+        almanac += &format!("\n{} map:\n", name);
+        // Sources never overlap, like in the puzzle.
+        let mut start = rng.random_range(0..100);
+        for _ in 0..rng.random_range(2..=8) {
+            let len = rng.random_range(1..=150);
+            almanac += &format!("{} {} {}\n", rng.random_range(0..1000), start, len);
+            start += len + rng.random_range(0..40);
+        }
+    }
+    almanac
+}
+
+// Every history is a polynomial, so the differences always reach zero.
+fn day9(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let coefficients: Vec<i64> = (0..=rng.random_range(0..=6))
+                .map(|_| rng.random_range(-10..=10))
+                .collect();
+            (0..21i64)
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, coefficient| acc * x + coefficient)
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+                + "\n"
+        })
+        .collect()
+}
+
+fn day11(rng: &mut Rng, size: usize) -> String {
+    let empty_rows: HashSet<usize> = (0..size).filter(|_| rng.random_bool(0.1)).collect();
+    let empty_cols: HashSet<usize> = (0..size).filter(|_| rng.random_bool(0.1)).collect();
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let blank = empty_rows.contains(&y) || empty_cols.contains(&x);
+                    match !blank && rng.random_bool(0.02) {
+                        true => '#',
+                        false => '.',
+                    }
+                })
+                .collect::<String>()
+                + "\n"
+        })
+        .collect()
+}
+
+fn day14(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, |rng| match rng.random_range(0..100) {
+        0..15 => '#',
+        15..40 => 'O',
+        _ => '.',
+    })
+}
+
+fn day15(rng: &mut Rng, size: usize) -> String {
+    // A small pool of labels so steps keep replacing and removing lenses.
+    let labels = names(rng, (size / 4).max(1), &[]);
+    (0..size)
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            match rng.random_bool(0.3) {
+                true => format!("{}-", label),
+                false => format!("{}={}", label, rng.random_range(1..=9)),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+        + "\n"
+}
+
+fn day16(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, |rng| match rng.random_range(0..100) {
+        0..3 => '/',
+        3..6 => '\\',
+        6..9 => '|',
+        9..12 => '-',
+        _ => '.',
+    })
+}
+
+fn day17(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size.max(5), |rng| {
+        rng.random_range(b'1'..=b'9') as char
+    })
+}
+
+// A skyline: up from the origin, across the tops of `columns` columns of
+// differing heights, down to the baseline and back. It never touches itself.
+fn skyline(rng: &mut Rng, columns: usize, max_step: u32) -> Vec<(char, u32)> {
+    let mut heights: Vec<u32> = vec![rng.random_range(1..=max_step)];
+    while heights.len() < columns {
+        let height = rng.random_range(1..=max_step);
+        if Some(&height) != heights.last() {
+            heights.push(height);
+        }
+    }
+    let widths: Vec<u32> = (0..columns)
+        .map(|_| rng.random_range(1..=max_step))
+        .collect();
+    let mut steps = vec![('U', heights[0]), ('R', widths[0])];
+    for idx in 1..columns {
+        let (prev, next) = (heights[idx - 1], heights[idx]);
+        match next > prev {
+            true => steps.push(('U', next - prev)),
+            false => steps.push(('D', prev - next)),
+        }
+        steps.push(('R', widths[idx]));
+    }
+    steps.push(('D', heights[columns - 1]));
+    steps.push(('L', widths.iter().sum()));
+    steps
+}
+
+fn day18(rng: &mut Rng, size: usize) -> String {
+    let literal = skyline(rng, size, 10);
+    let decoded = skyline(rng, size, 0xfffff / (size as u32 + 1));
+    literal
+        .iter()
+        .zip(decoded)
+        .map(|((direction, steps), (hex_direction, hex_steps))| {
+            let digit = match hex_direction {
+                'R' => 0,
+                'D' => 1,
+                'L' => 2,
+                _ => 3,
+            };
+            format!("{} {} (#{:05x}{})\n", direction, steps, hex_steps, digit)
+        })
+        .collect()
+}
+
+// Workflows only send parts to workflows after them, so every part ends up
+// accepted or rejected.
+fn day19(rng: &mut Rng, size: usize) -> String {
+    let mut workflows = vec!["in".to_string()];
+    workflows.extend(names(rng, size - 1, &["in"]));
+    let target = |rng: &mut Rng, idx: usize| match rng.random_range(idx + 1..=workflows.len() + 1) {
+        next if next < workflows.len() => workflows[next].clone(),
+        next if next == workflows.len() => "A".to_string(),
+        _ => "R".to_string(),
+    };
+    let mut text = String::new();
+    for (idx, name) in workflows.iter().enumerate() {
+        let rules: Vec<String> = (0..rng.random_range(1..=3))
+            .map(|_| {
+                format!(
+                    "{}{}{}:{}",
+                    ['x', 'm', 'a', 's'].choose(rng).unwrap(),
+                    ['<', '>'].choose(rng).unwrap(),
+                    rng.random_range(1..=4000),
+                    target(rng, idx)
+                )
+            })
+            .collect();
+        text += &format!("{}{{{},{}}}\n", name, rules.join(","), target(rng, idx));
+    }
+    text += "\n";
+    for _ in 0..size.max(2) {
+        let [x, m, a, s] = [(); 4].map(|_| rng.random_range(1..=4000));
+        text += &format!("{{x={},m={},a={},s={}}}\n", x, m, a, s);
+    }
+    text
+}
+
+// Four binary counters, the shape every real input has: each counts presses
+// up to an odd period, then its conjunction fires and resets it. Periods
+// stay below 2^15 so their product fits in 64 bits.
+fn day20(rng: &mut Rng, size: usize) -> String {
+    let bits = size.clamp(2, 15);
+    let mut names = names(rng, 4 * (bits + 2) + 1, &["rx"]).into_iter();
+    let hub = names.next().unwrap();
+    let mut lines = vec![format!("&{} -> rx", hub)];
+    let mut starts = vec![];
+    for _ in 0..4 {
+        let period: u32 = rng.random_range(1 << (bits - 1)..1 << bits) | 1;
+        let flip_flops: Vec<String> = names.by_ref().take(bits).collect();
+        let (conjunction, inverter) = (names.next().unwrap(), names.next().unwrap());
+        let mut from_conjunction = vec![inverter.clone()];
+        for (bit, name) in flip_flops.iter().enumerate() {
+            let mut outputs = flip_flops
+                .get(bit + 1)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            if period & (1 << bit) != 0 {
+                outputs.push(conjunction.clone());
+            }
+            if bit == 0 || period & (1 << bit) == 0 {
+                from_conjunction.push(name.clone());
+            }
+            lines.push(format!("%{} -> {}", name, outputs.join(", ")));
+        }
+        lines.push(format!(
+            "&{} -> {}",
+            conjunction,
+            from_conjunction.join(", ")
+        ));
+        lines.push(format!("&{} -> {}", inverter, hub));
+        starts.push(flip_flops[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", starts.join(", ")));
+    lines.shuffle(rng);
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for generator in GENERATORS.iter() {
+            let input = generator.generate(12, 7);
+            assert_eq!(input, generator.generate(12, 7));
+            assert_ne!(input, generator.generate(12, 8));
+            let solver = aoc_solvers::find(2023, generator.day).unwrap();
+            assert_eq!((solver.parse)(&input), Ok(()), "day {}", generator.day);
+            // Day 14's part 2 spins a billion times, too slow for a debug build.
+            let parts = if generator.day == 14 { 1 } else { 2 };
+            for part in &solver.parts[..parts] {
+                assert!(part(&input).is_ok(), "day {}:\n{}", generator.day, input);
+            }
+        }
+        assert!(find(3).is_none());
+    }
+
+    #[test]
+    fn test_day20_periods() {
+        // Three bit counters can only count to 5 or 7.
+        for seed in 0..4 {
+            let input = day20(&mut Rng::seed_from_u64(seed), 3);
+            let answer = aoc_solvers::solve(2023, 20, 2, &input).unwrap();
+            assert!(["5", "7", "35"].contains(&answer.as_str()), "{}", answer);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::sims::{load, Simulation, SIMULATIONS};
+use aoc_viz::{render_text, Frame, Palette};
+use eframe::egui::{self, ColorImage, TextureHandle, TextureOptions};
+use std::time::{Duration, Instant};
+
+pub struct App {
+    simulation: Simulation,
+    path: String,
+    frames: Vec<Frame>,
+    current: usize,
+    playing: bool,
+    fps: f32,
+    zoom: f32,
+    last_step: Instant,
+    error: Option<String>,
+    texture: Option<(usize, TextureHandle)>,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self {
+            simulation: SIMULATIONS[0],
+            path: String::new(),
+            frames: Vec::new(),
+            current: 0,
+            playing: false,
+            fps: 10.0,
+            zoom: 4.0,
+            last_step: Instant::now(),
+            error: None,
+            texture: None,
+        }
+    }
+}
+
+impl App {
+    fn load(&mut self) {
+        let frames =
+            load(&self.simulation, &self.path).and_then(|input| self.simulation.frames(&input));
+        match frames {
+            Ok(frames) => {
+                self.frames = frames;
+                self.error = None;
+            }
+            Err(err) => {
+                self.frames.clear();
+                self.error = Some(err);
+            }
+        }
+        self.current = 0;
+        self.playing = false;
+        self.texture = None;
+    }
+
+    fn last(&self) -> usize {
+        self.frames.len().saturating_sub(1)
+    }
+
+    fn toolbar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Day")
+                .selected_text(format!("{}: {}", self.simulation.day, self.simulation.name))
+                .show_ui(ui, |ui| {
+                    for simulation in SIMULATIONS {
+                        let label = format!("{}: {}", simulation.day, simulation.name);
+                        let selected = simulation.day == self.simulation.day;
+                        if ui.selectable_label(selected, label).clicked() && !selected {
+                            self.simulation = simulation;
+                            self.frames.clear();
+                            self.texture = None;
+                        }
+                    }
+                });
+            ui.label("Input");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.path)
+                    .hint_text(self.simulation.default_input().display().to_string()),
+            );
+            if ui.button("Example").clicked() {
+                self.path = self.simulation.example().display().to_string();
+                self.load();
+            }
+            if ui.button("Load").clicked() {
+                self.load();
+            }
+        });
+    }
+
+    fn controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let last = self.last();
+            if ui.button("⏮").clicked() {
+                self.current = 0;
+            }
+            if ui.button("◀").clicked() {
+                self.current = self.current.saturating_sub(1);
+            }
+            let play = if self.playing { "⏸" } else { "▶" };
+            if ui.button(play).clicked() {
+                self.playing = !self.playing;
+                self.last_step = Instant::now();
+            }
+            if ui.button("▶|").clicked() {
+                self.current = (self.current + 1).min(last);
+            }
+            if ui.button("⏭").clicked() {
+                self.current = last;
+            }
+            ui.add(egui::Slider::new(&mut self.current, 0..=last).text("frame"));
+            ui.add(egui::Slider::new(&mut self.fps, 1.0..=60.0).text("fps"));
+            if self.simulation.palette.is_some() {
+                ui.add(egui::Slider::new(&mut self.zoom, 1.0..=16.0).text("zoom"));
+            }
+        });
+        let caption = self
+            .frames
+            .get(self.current)
+            .map_or("", |frame| &frame.caption);
+        ui.label(caption);
+    }
+
+    // Advances playback, returning how long until the next frame is due.
+    fn tick(&mut self) -> Option<Duration> {
+        if !self.playing {
+            return None;
+        }
+        if self.current >= self.last() {
+            self.playing = false;
+            return None;
+        }
+        let interval = Duration::from_secs_f32(1.0 / self.fps);
+        let elapsed = self.last_step.elapsed();
+        if elapsed < interval {
+            return Some(interval - elapsed);
+        }
+        self.current += 1;
+        self.last_step = Instant::now();
+        Some(interval)
+    }
+
+    // Frames are drawn a pixel per tile and scaled up by egui.
+    fn texture(&mut self, ctx: &egui::Context, frame: &Frame, palette: &Palette) -> TextureHandle {
+        match &self.texture {
+            Some((current, texture)) if *current == self.current => texture.clone(),
+            _ => {
+                let image = render_text(&frame.text, 1, palette);
+                let size = [image.width() as usize, image.height() as usize];
+                let texture = ctx.load_texture(
+                    "frame",
+                    ColorImage::from_rgb(size, image.as_raw()),
+                    TextureOptions::NEAREST,
+                );
+                self.texture = Some((self.current, texture.clone()));
+                texture
+            }
+        }
+    }
+
+    fn view(&mut self, ui: &mut egui::Ui) {
+        if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::LIGHT_RED, error);
+            return;
+        }
+        let Some(frame) = self.frames.get(self.current).cloned() else {
+            ui.label("Load an input to start.");
+            return;
+        };
+        egui::ScrollArea::both().show(ui, |ui| {
+            if let Some(palette) = self.simulation.palette {
+                let texture = self.texture(ui.ctx(), &frame, &palette());
+                ui.image((texture.id(), texture.size_vec2() * self.zoom));
+            } else {
+                ui.monospace(&frame.text);
+            }
+        });
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if let Some(wait) = self.tick() {
+            ctx.request_repaint_after(wait);
+        }
+        egui::TopBottomPanel::top("toolbar").show(ctx, |ui| self.toolbar(ui));
+        egui::TopBottomPanel::bottom("controls").show(ctx, |ui| self.controls(ui));
+        egui::CentralPanel::default().show(ctx, |ui| self.view(ui));
+    }
+}
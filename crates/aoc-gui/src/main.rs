@@ -0,0 +1,10 @@
+mod app;
+mod sims;
+
+fn main() -> eframe::Result {
+    eframe::run_native(
+        "Advent of Code 2023",
+        eframe::NativeOptions::default(),
+        Box::new(|_| Ok(Box::<app::App>::default())),
+    )
+}
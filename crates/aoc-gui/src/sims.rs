@@ -0,0 +1,93 @@
+use aoc_viz::{take_frames, Frame, OnFrame, Palette};
+use std::path::{Path, PathBuf};
+
+pub const YEAR: u16 = 2023;
+// Day 20 never stops pressing the button on its own.
+pub const MAX_FRAMES: usize = 5_000;
+
+// A day that can be stepped through. Days with a palette are drawn as
+// images, the rest as text.
+#[derive(Clone, Copy)]
+pub struct Simulation {
+    pub day: u8,
+    pub name: &'static str,
+    pub palette: Option<fn() -> Palette>,
+    pub run: fn(&str, OnFrame) -> Result<(), String>,
+}
+
+pub const SIMULATIONS: [Simulation; 3] = [
+    Simulation {
+        day: 14,
+        name: "Tilting the platform",
+        palette: Some(day14::palette),
+        run: |input, on_frame| {
+            let map = day14::Map::try_from(input).map_err(|err| err.to_string())?;
+            day14::simulate(&map, on_frame);
+            Ok(())
+        },
+    },
+    Simulation {
+        day: 16,
+        name: "Beams through the contraption",
+        palette: Some(day16::palette),
+        run: |input, on_frame| {
+            let grid = day16::parse_input(input).map_err(|err| err.to_string())?;
+            day16::simulate(&grid, on_frame);
+            Ok(())
+        },
+    },
+    Simulation {
+        day: 20,
+        name: "Pulses between modules",
+        palette: None,
+        run: |input, on_frame| {
+            let state = day20::State::try_from(input).map_err(|err| err.to_string())?;
+            day20::simulate(&state, on_frame);
+            Ok(())
+        },
+    },
+];
+
+impl Simulation {
+    pub fn frames(&self, input: &str) -> Result<Vec<Frame>, String> {
+        let mut result = Ok(());
+        let frames = take_frames(MAX_FRAMES, |on_frame| result = (self.run)(input, on_frame));
+        result.map(|()| frames)
+    }
+
+    pub fn default_input(&self) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("../day{}/input.txt", self.day))
+    }
+
+    pub fn example(&self) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(format!("../../tests/data/day{}/example.txt", self.day))
+    }
+}
+
+// The input at `path`, or the day's own input, fetched if need be, when it's
+// left empty.
+pub fn load(simulation: &Simulation, path: &str) -> Result<String, String> {
+    match path.trim() {
+        "" => aoc_input::config()
+            .and_then(|config| config.load(YEAR, simulation.day, simulation.default_input())),
+        path => aoc_input::read(path),
+    }
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sims::*;
+
+    #[test]
+    fn test_examples() {
+        for simulation in SIMULATIONS {
+            let input = load(&simulation, simulation.example().to_str().unwrap()).unwrap();
+            let frames = simulation.frames(&input).unwrap();
+            assert!(frames.len() > 1, "day {}", simulation.day);
+            assert!(frames.len() <= MAX_FRAMES);
+        }
+        assert!(SIMULATIONS[2].frames("%a -> b").is_err());
+    }
+}
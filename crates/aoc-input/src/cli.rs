@@ -0,0 +1,221 @@
+use crate::{config, load, log, open, open_file, open_path, parsed, read, InputError};
+use clap::{ArgAction, Parser, ValueEnum};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    fmt::Display,
+    fs,
+    io::BufRead,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    #[default]
+    Both,
+}
+
+impl Part {
+    pub fn includes(self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Self::Both, _) | (Self::One, 1) | (Self::Two, 2)
+        )
+    }
+}
+
+fn example_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join("../../tests/data")
+        .join(format!("day{}", day))
+        .join("example.txt")
+}
+
+// Flags every day binary understands. Days with options of their own flatten
+// this into their own parser.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Parser)]
+pub struct DayArgs {
+    /// Which part to solve
+    #[arg(long, value_enum, default_value_t)]
+    pub part: Part,
+    /// Read the puzzle input from PATH or an http(s) URL, or stdin for `-`
+    #[arg(long = "input", value_name = "PATH", conflicts_with = "example")]
+    input: Option<String>,
+    #[arg(value_name = "INPUT", hide = true, conflicts_with_all = ["input", "example"])]
+    positional: Option<String>,
+    /// Use the example from the puzzle description
+    #[arg(long)]
+    pub example: bool,
+    /// Print the answers as a JSON object
+    #[arg(long)]
+    pub json: bool,
+    /// Print how long each phase took to stderr
+    #[arg(long)]
+    pub time: bool,
+    /// Reuse the parsed input saved by an earlier run, where the day supports it
+    #[arg(long)]
+    pub parse_cache: bool,
+    /// Log debug events to stderr, -vv for trace events and phase timings too
+    #[arg(short, long, action = ArgAction::Count)]
+    pub verbose: u8,
+    /// Record every span to PATH in Chrome trace format, for chrome://tracing or Perfetto
+    #[arg(long, value_name = "PATH")]
+    pub profile: Option<PathBuf>,
+}
+
+impl DayArgs {
+    pub fn from_env() -> Self {
+        Self::parse()
+    }
+
+    // Keep the guard until the answers are out, dropping it finishes the
+    // profile.
+    pub fn init_logging(&self) -> log::Guard {
+        log::init(self.verbose, self.profile.as_deref())
+    }
+
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_deref().or(self.positional.as_deref())
+    }
+
+    pub fn runs(&self, part: u8) -> bool {
+        self.part.includes(part)
+    }
+
+    // `manifest_dir` is the day crate's own directory, which holds the
+    // default input.txt.
+    pub fn load(&self, year: u16, day: u8, manifest_dir: &str) -> Result<String, InputError> {
+        let dir = Path::new(manifest_dir);
+        if let Some(path) = self.input() {
+            return read(path);
+        }
+        if self.example {
+            let path = example_path(dir, day);
+            return fs::read_to_string(&path).map_err(|err| InputError::Read(path, err));
+        }
+        load(year, day, dir.join("input.txt"))
+    }
+
+    // `load` a line at a time, for `--stream` style flags.
+    pub fn open(
+        &self,
+        year: u16,
+        day: u8,
+        manifest_dir: &str,
+    ) -> Result<Box<dyn BufRead>, InputError> {
+        let dir = Path::new(manifest_dir);
+        if let Some(path) = self.input() {
+            return open_path(path);
+        }
+        if self.example {
+            return open_file(&example_path(dir, day));
+        }
+        open(year, day, dir.join("input.txt"))
+    }
+
+    // Parses through the cache under `--parse-cache`. Without a cache
+    // directory this is a plain parse.
+    pub fn parse_cached<T, E>(
+        &self,
+        year: u16,
+        day: u8,
+        tag: &str,
+        input: &str,
+        parse: impl FnOnce(&str) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let path = match self.parse_cache {
+            true => config()
+                .ok()
+                .and_then(|config| config.parsed_path(year, day, &parsed::key(tag, input))),
+            false => None,
+        };
+        match path {
+            Some(path) => parsed::cached(&path, input, parse),
+            None => parse(input),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    json: Option<Map<String, Value>>,
+}
+
+impl Answers {
+    pub fn new(args: &DayArgs) -> Self {
+        Self {
+            json: args.json.then(Map::new),
+        }
+    }
+
+    pub fn report(&mut self, part: u8, answer: impl Display) {
+        match &mut self.json {
+            Some(json) => {
+                json.insert(format!("part{}", part), answer.to_string().into());
+            }
+            None => println!("Part {}: {}", part, answer),
+        }
+    }
+
+    pub fn finish(self) {
+        if let Some(json) = self.json {
+            println!("{}", Value::Object(json));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::*;
+
+    fn parse(s: &str) -> Result<DayArgs, clap::Error> {
+        DayArgs::try_parse_from(s.split_whitespace())
+    }
+
+    #[test]
+    fn test_day_args() {
+        let args = parse("day5 --part 2 --input big.txt --time").unwrap();
+        assert_eq!(args.part, Part::Two);
+        assert_eq!(args.input(), Some("big.txt"));
+        assert!(args.time && !args.json && !args.parse_cache);
+        assert!(!args.runs(1) && args.runs(2));
+        assert_eq!(parse("day5 -").unwrap().input(), Some("-"));
+        assert!(parse("day5 --parse-cache").unwrap().parse_cache);
+        assert_eq!(parse("day5 -vv").unwrap().verbose, 2);
+        assert_eq!(parse("day5 -v --verbose").unwrap().verbose, 2);
+        assert_eq!(
+            parse("day5 --profile out.json").unwrap().profile,
+            Some(PathBuf::from("out.json"))
+        );
+        let args = parse("day5 --example --json").unwrap();
+        assert_eq!((args.part, args.input()), (Part::Both, None));
+        assert!(args.example && args.json);
+        assert!(parse("day5 --part 3").is_err());
+        assert!(parse("day5 --example --input big.txt").is_err());
+        assert!(matches!(
+            parse("day5 --input /nonexistent/input.txt")
+                .unwrap()
+                .load(2023, 5, "."),
+            Err(InputError::Read(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_example() {
+        let args = parse("day6 --example").unwrap();
+        let input = args.load(2023, 6, env!("CARGO_MANIFEST_DIR")).unwrap();
+        assert!(input.starts_with("Time:"));
+        let mut streamed = String::new();
+        args.open(2023, 6, env!("CARGO_MANIFEST_DIR"))
+            .unwrap()
+            .read_to_string(&mut streamed)
+            .unwrap();
+        assert_eq!(streamed, input);
+    }
+}
@@ -0,0 +1,356 @@
+use crate::{home_dir, InputError, YEAR};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use url::{Host, Url};
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(InputError::Config(format!(
+                "invalid format `{}`, expected text or json",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub session: Option<String>,
+    pub input_dir: Option<PathBuf>,
+    pub year: u16,
+    pub jobs: Option<NonZeroUsize>,
+    pub format: Format,
+    // Host to header name to value.
+    pub input_headers: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            input_dir: None,
+            year: YEAR,
+            jobs: None,
+            format: Format::Text,
+            input_headers: BTreeMap::new(),
+        }
+    }
+}
+
+fn parse_env<T: FromStr>(name: &str, value: String) -> Result<T, InputError> {
+    value
+        .parse()
+        .map_err(|_| InputError::Config(format!("{}: invalid value `{}`", name, value)))
+}
+
+fn is_loopback(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
+// `HOST Name: value` lines.
+fn parse_headers(s: &str) -> Result<BTreeMap<String, BTreeMap<String, String>>, InputError> {
+    let mut headers: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    for line in s.lines().filter(|line| !line.trim().is_empty()) {
+        let (host, name, value) = line
+            .trim()
+            .split_once(char::is_whitespace)
+            .and_then(|(host, header)| {
+                let (name, value) = header.split_once(':')?;
+                Some((host, name, value))
+            })
+            .ok_or_else(|| {
+                InputError::Config(format!(
+                    "AOC_INPUT_HEADERS: invalid line `{}`, expected `HOST Name: value`",
+                    line
+                ))
+            })?;
+        headers
+            .entry(host.to_string())
+            .or_default()
+            .insert(name.trim().to_string(), value.trim().to_string());
+    }
+    Ok(headers)
+}
+
+impl Config {
+    pub fn parse(s: &str) -> Result<Self, InputError> {
+        toml::from_str(s).map_err(|err| InputError::Config(err.to_string()))
+    }
+
+    // Environment variables win over the file so one-off runs don't need it
+    // edited, e.g. `AOC_FORMAT=json aoc all`.
+    pub fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self, InputError> {
+        let var = |name| var(name).filter(|value| !value.is_empty());
+        if let Some(session) = var("AOC_SESSION") {
+            self.session = Some(session);
+        }
+        if let Some(dir) = var("AOC_INPUT_DIR") {
+            self.input_dir = Some(dir.into());
+        }
+        if let Some(year) = var("AOC_YEAR") {
+            self.year = parse_env("AOC_YEAR", year)?;
+        }
+        if let Some(jobs) = var("AOC_JOBS") {
+            self.jobs = Some(parse_env("AOC_JOBS", jobs)?);
+        }
+        if let Some(format) = var("AOC_FORMAT") {
+            self.format = format.parse()?;
+        }
+        if let Some(headers) = var("AOC_INPUT_HEADERS") {
+            self.input_headers = parse_headers(&headers)?;
+        }
+        Ok(self)
+    }
+
+    // The nearest aoc.toml above the working directory, falling back to the
+    // one at the root of this workspace.
+    pub fn find() -> Option<PathBuf> {
+        let cwd = env::current_dir().ok()?;
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let found = cwd
+            .ancestors()
+            .chain([workspace.as_path()])
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|path| path.is_file());
+        found
+    }
+
+    pub fn read(path: &Path) -> Result<Self, InputError> {
+        let text = fs::read_to_string(path).map_err(|err| InputError::Read(path.into(), err))?;
+        let mut config = Self::parse(&text)
+            .map_err(|err| InputError::Config(format!("{}: {}", path.display(), err)))?;
+        if let (Some(dir), Some(root)) = (&config.input_dir, path.parent()) {
+            config.input_dir = Some(root.join(dir));
+        }
+        Ok(config)
+    }
+
+    pub fn discover() -> Result<Self, InputError> {
+        match Self::find() {
+            Some(path) => Self::read(&path),
+            None => Ok(Self::default()),
+        }?
+        .with_env(|name| env::var(name).ok())
+    }
+
+    pub fn session(&self) -> Option<String> {
+        self.session
+            .clone()
+            .or_else(|| fs::read_to_string(crate::session_path()?).ok())
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty())
+    }
+
+    // The headers configured for the host `url` points at. They are never
+    // sent to adventofcode.com, which only gets the session cookie, and
+    // never in the clear: asking for them over http is an error, unless the
+    // host is this machine.
+    pub fn input_headers(&self, url: &str) -> Result<Vec<(&str, &str)>, InputError> {
+        let url = Url::parse(url)
+            .map_err(|err| InputError::Config(format!("invalid URL `{}`: {}", url, err)))?;
+        let Some(host) = url.host_str() else {
+            return Ok(Vec::new());
+        };
+        if host == "adventofcode.com" || host.ends_with(".adventofcode.com") {
+            return Ok(Vec::new());
+        }
+        let Some((_, headers)) = self
+            .input_headers
+            .iter()
+            .find(|(configured, _)| configured.eq_ignore_ascii_case(host))
+        else {
+            return Ok(Vec::new());
+        };
+        if url.scheme() != "https" && !is_loopback(&url) {
+            return Err(InputError::Config(format!(
+                "refusing to send the input headers for {} over {}",
+                host,
+                url.scheme()
+            )));
+        }
+        Ok(headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect())
+    }
+
+    fn cache_dir(&self) -> Option<PathBuf> {
+        match &self.input_dir {
+            Some(dir) => Some(dir.clone()),
+            None => home_dir("XDG_CACHE_HOME", ".cache"),
+        }
+    }
+
+    fn day_dir(&self, year: u16, day: u8) -> Option<PathBuf> {
+        self.cache_dir()
+            .map(|dir| dir.join(year.to_string()).join(format!("day{}", day)))
+    }
+
+    pub fn cache_path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        self.day_dir(year, day).map(|dir| dir.join("input.txt"))
+    }
+
+    pub fn puzzle_path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        self.day_dir(year, day).map(|dir| dir.join("puzzle.html"))
+    }
+
+    pub fn parsed_path(&self, year: u16, day: u8, key: &str) -> Option<PathBuf> {
+        self.day_dir(year, day)
+            .map(|dir| dir.join("parsed").join(format!("{}.bin", key)))
+    }
+
+    pub fn answers_path(&self) -> Option<PathBuf> {
+        self.cache_dir().map(|dir| dir.join("answers.json"))
+    }
+
+    pub fn history_path(&self) -> Option<PathBuf> {
+        self.cache_dir().map(|dir| dir.join("history.sqlite"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            "session = \"abc\"\ninput-dir = \"inputs\"\nyear = 2022\njobs = 4\nformat = \"json\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                session: Some("abc".to_string()),
+                input_dir: Some("inputs".into()),
+                year: 2022,
+                jobs: NonZeroUsize::new(4),
+                format: Format::Json,
+                input_headers: BTreeMap::new(),
+            }
+        );
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert!(matches!(
+            Config::parse("jobs = 0"),
+            Err(InputError::Config(_))
+        ));
+        assert!(matches!(
+            Config::parse("colour = true"),
+            Err(InputError::Config(_))
+        ));
+        assert!(Config::default()
+            .cache_path(2023, 7)
+            .unwrap()
+            .ends_with("aoc/2023/day7/input.txt"));
+        assert_eq!(
+            config.cache_path(2022, 7),
+            Some(PathBuf::from("inputs/2022/day7/input.txt"))
+        );
+        assert_eq!(
+            config.parsed_path(2022, 7, "ab12"),
+            Some(PathBuf::from("inputs/2022/day7/parsed/ab12.bin"))
+        );
+        assert_eq!(
+            config.answers_path(),
+            Some(PathBuf::from("inputs/answers.json"))
+        );
+        assert_eq!(
+            config.history_path(),
+            Some(PathBuf::from("inputs/history.sqlite"))
+        );
+    }
+
+    #[test]
+    fn test_with_env() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        let config = Config::parse("year = 2022\njobs = 4")
+            .unwrap()
+            .with_env(env(&[
+                ("AOC_YEAR", "2024"),
+                ("AOC_FORMAT", "json"),
+                ("AOC_SESSION", ""),
+            ]))
+            .unwrap();
+        assert_eq!(config.year, 2024);
+        assert_eq!(config.jobs, NonZeroUsize::new(4));
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(config.session, None);
+        assert!(Config::default()
+            .with_env(env(&[("AOC_JOBS", "0")]))
+            .is_err());
+        let config = Config::parse("[input-headers.\"gist.example.com\"]\nX-Team = \"elves\"")
+            .unwrap()
+            .with_env(env(&[(
+                "AOC_INPUT_HEADERS",
+                "gist.example.com Authorization: token abc\nadventofcode.com Accept: text/plain",
+            )]))
+            .unwrap();
+        assert_eq!(
+            config
+                .input_headers("https://Gist.Example.com:8443/raw/input.txt")
+                .unwrap(),
+            [("Authorization", "token abc")]
+        );
+        assert!(config
+            .input_headers("https://adventofcode.com/2023/day/1/input")
+            .unwrap()
+            .is_empty());
+        assert!(config
+            .input_headers("https://elsewhere.example.com/input.txt")
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            config
+                .input_headers("http://gist.example.com/raw/input.txt")
+                .unwrap_err()
+                .to_string(),
+            "refusing to send the input headers for gist.example.com over http"
+        );
+        let config = Config::parse("[input-headers.localhost]\nX-Team = \"elves\"").unwrap();
+        assert_eq!(
+            config
+                .input_headers("http://localhost:8080/input.txt")
+                .unwrap(),
+            [("X-Team", "elves")]
+        );
+        assert!(Config::default()
+            .with_env(env(&[("AOC_INPUT_HEADERS", "Authorization: token")]))
+            .is_err());
+        assert!(Config::default()
+            .with_env(env(&[("AOC_FORMAT", "yaml")]))
+            .is_err());
+    }
+}
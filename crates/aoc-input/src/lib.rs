@@ -0,0 +1,398 @@
+pub mod cli;
+pub mod config;
+pub mod log;
+pub mod parsed;
+pub mod puzzle;
+
+pub use config::{Config, Format};
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Cursor},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+use url::Url;
+
+pub const YEAR: u16 = 2023;
+
+const USER_AGENT: &str = concat!(
+    "github.com/lilyrcodes/aoc2023 by ",
+    env!("CARGO_PKG_AUTHORS")
+);
+
+#[derive(Debug)]
+pub enum InputError {
+    MissingSession,
+    Status { year: u16, day: u8, code: u16 },
+    UrlStatus { url: String, code: u16 },
+    Transport(String),
+    Read(PathBuf, io::Error),
+    Io(io::Error),
+    Config(String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSession => write!(
+                f,
+                "no session token, set AOC_SESSION, add `session` to {} or write it to {}",
+                config::FILE_NAME,
+                session_path().map_or("~/.config/aoc/session".into(), |path| path
+                    .display()
+                    .to_string())
+            ),
+            Self::Status { year, day, code } => write!(
+                f,
+                "fetching {} day {} failed with HTTP {} (expired session or locked puzzle?)",
+                year, day, code
+            ),
+            Self::UrlStatus { url, code } => {
+                write!(f, "fetching {} failed with HTTP {}", url, code)
+            }
+            Self::Transport(err) => write!(f, "{}", err),
+            Self::Read(path, err) => write!(f, "{}: {}", path.display(), err),
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Config(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+impl From<io::Error> for InputError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+fn home_dir(xdg_var: &str, fallback: &str) -> Option<PathBuf> {
+    env::var_os(xdg_var)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(fallback)))
+        .map(|dir| dir.join("aoc"))
+}
+
+fn session_path() -> Option<PathBuf> {
+    home_dir("XDG_CONFIG_HOME", ".config").map(|dir| dir.join("session"))
+}
+
+// Loaded once per process so every binary sees the same settings.
+pub fn config() -> Result<&'static Config, InputError> {
+    static CONFIG: OnceLock<Result<Config, String>> = OnceLock::new();
+    CONFIG
+        .get_or_init(|| Config::discover().map_err(|err| err.to_string()))
+        .as_ref()
+        .map_err(|message| InputError::Config(message.clone()))
+}
+
+// As many as ureq follows on its own.
+const MAX_REDIRECTS: usize = 5;
+
+fn request(
+    agent: &ureq::Agent,
+    url: &str,
+    headers: &[(&str, &str)],
+    status: impl FnOnce(u16) -> InputError,
+) -> Result<ureq::Response, InputError> {
+    let mut request = agent.get(url).set("User-Agent", USER_AGENT);
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+    request.call().map_err(|err| match err {
+        ureq::Error::Status(code, _) => status(code),
+        ureq::Error::Transport(err) => InputError::Transport(err.to_string()),
+    })
+}
+
+fn get(
+    url: &str,
+    headers: &[(&str, &str)],
+    status: impl FnOnce(u16) -> InputError,
+) -> Result<String, InputError> {
+    Ok(request(&ureq::agent(), url, headers, status)?.into_string()?)
+}
+
+fn get_puzzle(url: &str, year: u16, day: u8, session: Option<&str>) -> Result<String, InputError> {
+    let cookie = session.map(|session| format!("session={}", session));
+    let headers: Vec<(&str, &str)> = cookie
+        .iter()
+        .map(|cookie| ("Cookie", cookie.as_str()))
+        .collect();
+    get(url, &headers, |code| InputError::Status { year, day, code })
+}
+
+fn is_url(path: &str) -> bool {
+    path.starts_with("https://") || path.starts_with("http://")
+}
+
+pub fn puzzle_url(year: u16, day: u8) -> String {
+    format!("https://adventofcode.com/{}/day/{}", year, day)
+}
+
+pub fn download(year: u16, day: u8, session: &str) -> Result<String, InputError> {
+    let url = format!("{}/input", puzzle_url(year, day));
+    get_puzzle(&url, year, day, Some(session))
+}
+
+// Part 1 is public, the session is only needed to see part 2.
+pub fn download_puzzle(year: u16, day: u8, session: Option<&str>) -> Result<String, InputError> {
+    get_puzzle(&puzzle_url(year, day), year, day, session)
+}
+
+impl Config {
+    pub fn fetch(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let session = self.session().ok_or(InputError::MissingSession)?;
+        let input = download(year, day, &session)?;
+        if let Some(path) = self.cache_path(year, day) {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, &input)?;
+        }
+        Ok(input)
+    }
+
+    // A cached page is only refreshed while part 2 is still missing from it,
+    // and is used as is when the site can't be reached.
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let path = self.puzzle_path(year, day);
+        let cached = path.as_ref().and_then(|path| fs::read_to_string(path).ok());
+        if let Some(html) = cached
+            .as_ref()
+            .filter(|html| puzzle::articles(html).len() > 1)
+        {
+            return Ok(html.clone());
+        }
+        let html = match download_puzzle(year, day, self.session().as_deref()) {
+            Ok(html) => html,
+            Err(err) => return cached.ok_or(err),
+        };
+        if let Some(path) = path {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, &html)?;
+        }
+        Ok(html)
+    }
+
+    pub fn download_url(&self, url: &str) -> Result<String, InputError> {
+        Ok(self.request_url(url)?.into_string()?)
+    }
+
+    // Redirects are followed here rather than by ureq, which would pass
+    // every header but Authorization on to whichever host it is sent to.
+    // Each hop only gets the headers configured for its own host.
+    fn request_url(&self, url: &str) -> Result<ureq::Response, InputError> {
+        let agent = ureq::AgentBuilder::new().redirects(0).build();
+        let mut current = url.to_string();
+        for _ in 0..=MAX_REDIRECTS {
+            let headers = self.input_headers(&current)?;
+            let response = request(&agent, &current, &headers, |code| InputError::UrlStatus {
+                url: url.to_string(),
+                code,
+            })?;
+            if !(300..400).contains(&response.status()) {
+                return Ok(response);
+            }
+            let location = response.header("Location").unwrap_or_default();
+            current = Url::parse(&current)
+                .and_then(|base| base.join(location))
+                .map_err(|_| {
+                    InputError::Transport(format!("{}: bad redirect to `{}`", current, location))
+                })?
+                .into();
+        }
+        Err(InputError::Transport(format!(
+            "{}: more than {} redirects",
+            url, MAX_REDIRECTS
+        )))
+    }
+
+    pub fn load(&self, year: u16, day: u8, local: impl AsRef<Path>) -> Result<String, InputError> {
+        if local.as_ref().exists() {
+            return Ok(fs::read_to_string(local)?);
+        }
+        match self.cache_path(year, day).filter(|path| path.exists()) {
+            Some(path) => Ok(fs::read_to_string(path)?),
+            None => self.fetch(year, day),
+        }
+    }
+
+    // `load` for inputs too big to hold in memory, found the same way. A
+    // freshly fetched input is read whole all the same.
+    pub fn open(
+        &self,
+        year: u16,
+        day: u8,
+        local: impl AsRef<Path>,
+    ) -> Result<Box<dyn BufRead>, InputError> {
+        if local.as_ref().exists() {
+            return open_file(local.as_ref());
+        }
+        match self.cache_path(year, day).filter(|path| path.exists()) {
+            Some(path) => open_file(&path),
+            None => Ok(Box::new(Cursor::new(self.fetch(year, day)?))),
+        }
+    }
+}
+
+fn open_file(path: &Path) -> Result<Box<dyn BufRead>, InputError> {
+    let file = File::open(path).map_err(|err| InputError::Read(path.into(), err))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+pub fn fetch(year: u16, day: u8) -> Result<String, InputError> {
+    config()?.fetch(year, day)
+}
+
+pub fn load(year: u16, day: u8, local: impl AsRef<Path>) -> Result<String, InputError> {
+    config()?.load(year, day, local)
+}
+
+pub fn open(year: u16, day: u8, local: impl AsRef<Path>) -> Result<Box<dyn BufRead>, InputError> {
+    config()?.open(year, day, local)
+}
+
+pub fn read(path: &str) -> Result<String, InputError> {
+    if path == "-" {
+        return Ok(io::read_to_string(io::stdin())?);
+    }
+    if is_url(path) {
+        return config()?.download_url(path);
+    }
+    fs::read_to_string(path).map_err(|err| InputError::Read(path.into(), err))
+}
+
+// `read` a line at a time.
+pub fn open_path(path: &str) -> Result<Box<dyn BufRead>, InputError> {
+    if path == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    if is_url(path) {
+        let response = config()?.request_url(path)?;
+        return Ok(Box::new(BufReader::new(response.into_reader())));
+    }
+    open_file(Path::new(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_load_prefers_local_file() {
+        let path = env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        fs::write(&path, "local input").unwrap();
+        assert_eq!(
+            Config::default().load(2023, 1, &path).unwrap(),
+            "local input"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_download_url() {
+        use std::{
+            io::{BufRead, BufReader, Write},
+            net::TcpListener,
+            thread,
+        };
+
+        // Answers each connection with the next of `responses`, and returns
+        // the request lines and headers it got.
+        fn serve(
+            listener: TcpListener,
+            responses: Vec<String>,
+        ) -> thread::JoinHandle<Vec<Vec<String>>> {
+            thread::spawn(move || {
+                let mut requests = Vec::new();
+                for response in responses {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(&stream);
+                    let mut request = Vec::new();
+                    let mut line = String::new();
+                    while reader.read_line(&mut line).unwrap() > 2 {
+                        request.push(line.trim_end().to_string());
+                        line.clear();
+                    }
+                    write!(&stream, "{}", response).unwrap();
+                    requests.push(request);
+                }
+                requests
+            })
+        }
+        let ok = |status: &str| {
+            format!(
+                "HTTP/1.1 {}\r\nContent-Length: 6\r\nConnection: close\r\n\r\n1\n2\n3\n",
+                status
+            )
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/input.txt", listener.local_addr().unwrap());
+        let elsewhere = TcpListener::bind("127.0.0.1:0").unwrap();
+        let moved = format!(
+            "http://localhost:{}/moved.txt",
+            elsewhere.local_addr().unwrap().port()
+        );
+        let server = serve(
+            listener,
+            vec![
+                ok("200 OK"),
+                ok("404 Not Found"),
+                format!(
+                    "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    moved
+                ),
+            ],
+        );
+        let redirected = serve(elsewhere, vec![ok("200 OK")]);
+        let config = Config {
+            input_headers: [(
+                "127.0.0.1".to_string(),
+                [
+                    ("Authorization".to_string(), "Bearer elf".to_string()),
+                    ("X-Team".to_string(), "elves".to_string()),
+                ]
+                .into(),
+            )]
+            .into(),
+            ..Config::default()
+        };
+        assert_eq!(config.download_url(&url).unwrap(), "1\n2\n3\n");
+        assert_eq!(
+            config.download_url(&url).unwrap_err().to_string(),
+            format!("fetching {} failed with HTTP 404", url)
+        );
+        assert_eq!(config.download_url(&url).unwrap(), "1\n2\n3\n");
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0][0], "GET /input.txt HTTP/1.1");
+        assert!(requests[0].contains(&"Authorization: Bearer elf".to_string()));
+        assert!(requests[0].contains(&"X-Team: elves".to_string()));
+        assert!(requests[2].contains(&"X-Team: elves".to_string()));
+        // The redirect leaves for another host, which gets none of them.
+        let requests = redirected.join().unwrap();
+        assert_eq!(requests[0][0], "GET /moved.txt HTTP/1.1");
+        assert!(!requests[0]
+            .iter()
+            .any(|header| header.starts_with("Authorization") || header.starts_with("X-Team")));
+    }
+
+    #[test]
+    fn test_puzzle_uses_complete_cache() {
+        let dir = env::temp_dir().join(format!("aoc-puzzle-{}", std::process::id()));
+        let config = Config {
+            input_dir: Some(dir.clone()),
+            ..Config::default()
+        };
+        let html =
+            "<article class=\"day-desc\">one</article><article class=\"day-desc\">two</article>";
+        let path = config.puzzle_path(2023, 6).unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, html).unwrap();
+        assert_eq!(config.puzzle(2023, 6).unwrap(), html);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,57 @@
+use std::{fs::File, io, path::Path};
+use tracing::Level;
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{
+    filter::LevelFilter, fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt, Layer,
+};
+
+fn level(verbosity: u8) -> Level {
+    match verbosity {
+        0 => Level::WARN,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+// Writes out the rest of the profile when dropped.
+#[must_use]
+pub struct Guard {
+    _profile: Option<FlushGuard>,
+}
+
+// Logs go to stderr so they never mix with the answers. At the most verbose
+// level every closed span is logged too, with how long it ran. A profile
+// records every span whatever the verbosity, so trace-level spans on hot
+// functions only cost anything while profiling.
+pub fn init(verbosity: u8, profile: Option<&Path>) -> Guard {
+    let span_events = match verbosity {
+        0 | 1 => FmtSpan::NONE,
+        _ => FmtSpan::CLOSE,
+    };
+    let fmt = tracing_subscriber::fmt::layer()
+        .with_span_events(span_events)
+        .with_writer(io::stderr)
+        // No timestamps, but unlike `without_time` this keeps span timings.
+        .with_timer(())
+        .with_target(false)
+        .with_filter(LevelFilter::from_level(level(verbosity)));
+    let (chrome, guard) = match profile.map(|path| (path, File::create(path))) {
+        Some((_, Ok(file))) => {
+            let (layer, guard) = ChromeLayerBuilder::new()
+                .writer(file)
+                .include_args(true)
+                .build();
+            (Some(layer), Some(guard))
+        }
+        Some((path, Err(err))) => {
+            eprintln!("Could not write {}: {}", path.display(), err);
+            (None, None)
+        }
+        None => (None, None),
+    };
+    let _ = tracing_subscriber::registry()
+        .with(fmt)
+        .with(chrome)
+        .try_init();
+    Guard { _profile: guard }
+}
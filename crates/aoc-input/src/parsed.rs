@@ -0,0 +1,75 @@
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fs, io, path::Path};
+
+// Parsed inputs saved with bincode, for days where parsing a large input
+// costs more than solving it. `tag` names the parsed type and should change
+// whenever its shape does, so stale files are never read back as the new one.
+pub fn key(tag: &str, input: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update([0]);
+    hasher.update(input);
+    format!("{:x}", hasher.finalize())
+}
+
+// Missing and unreadable files are both just a cache miss.
+pub fn load<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    bincode::deserialize(&fs::read(path).ok()?).ok()
+}
+
+pub fn save<T: Serialize>(path: &Path, parsed: &T) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let bytes = bincode::serialize(parsed).map_err(io::Error::other)?;
+    fs::write(path, bytes)
+}
+
+pub fn cached<T, E>(
+    path: &Path,
+    input: &str,
+    parse: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<T, E>
+where
+    T: Serialize + DeserializeOwned,
+{
+    if let Some(parsed) = load(path) {
+        return Ok(parsed);
+    }
+    let parsed = parse(input)?;
+    if let Err(err) = save(path, &parsed) {
+        eprintln!("Could not save {}: {}", path.display(), err);
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parsed::*;
+    use std::{env, process};
+
+    #[test]
+    fn test_cached() {
+        let path = env::temp_dir()
+            .join(format!("aoc-parsed-{}", process::id()))
+            .join("test.bin");
+        let parse = |input: &str| {
+            input
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<Vec<u32>, _>>()
+        };
+        assert_eq!(cached(&path, "1,2,3", parse), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            cached(&path, "ignored", |_| Err("parsed again")),
+            Ok(vec![1, 2, 3])
+        );
+        fs::write(&path, b"junk").unwrap();
+        assert_eq!(cached(&path, "4", parse), Ok(vec![4]));
+        assert!(cached(&path.with_file_name("other.bin"), "x", parse).is_err());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_ne!(key("stack-1", "1,2"), key("stack-2", "1,2"));
+        assert_ne!(key("a", "bc"), key("ab", "c"));
+    }
+}
@@ -0,0 +1,178 @@
+// Turns the puzzle page into markdown for reading in a terminal. Puzzle pages
+// only use a handful of tags, so this is a small tag scanner rather than a
+// full HTML parser.
+
+const WIDTH: usize = 80;
+const SITE: &str = "https://adventofcode.com";
+
+// Each part's description is its own `<article class="day-desc">`, so there
+// are two once part 2 is unlocked.
+pub fn articles(html: &str) -> Vec<&str> {
+    html.split("<article class=\"day-desc\">")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("</article>").map(|(article, _)| article))
+        .collect()
+}
+
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    tag[start..].split('"').next()
+}
+
+fn wrap(text: &str, bullet: &str, out: &mut String) {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if bullet.len() + line.len() + 1 + word.len() <= WIDTH => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    for (idx, line) in lines.iter().enumerate() {
+        match idx {
+            0 => out.push_str(bullet),
+            _ => out.push_str(&" ".repeat(bullet.len())),
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    for article in articles(html) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        article_markdown(article, &mut out);
+    }
+    out
+}
+
+fn article_markdown(article: &str, out: &mut String) {
+    let mut text = String::new();
+    let mut pre = false;
+    let mut href = None;
+    let mut rest = article;
+    while let Some(start) = rest.find('<') {
+        let chunk = decode(&rest[..start]);
+        match pre {
+            true => text.push_str(&chunk),
+            false => text.push_str(&chunk.replace('\n', " ")),
+        }
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+        let name = tag.split_whitespace().next().unwrap_or("");
+        match name {
+            "h2" | "p" | "li" | "ul" => text.clear(),
+            "pre" => {
+                pre = true;
+                text.clear();
+            }
+            "/pre" => {
+                pre = false;
+                out.push_str("```\n");
+                out.push_str(text.trim_end_matches('\n'));
+                out.push_str("\n```\n\n");
+                text.clear();
+            }
+            "em" => text.push('*'),
+            "/em" => text.push('*'),
+            "code" | "/code" if !pre => text.push('`'),
+            "a" => {
+                href = attr(tag, "href").map(|link| match link.starts_with('/') {
+                    true => format!("{}{}", SITE, link),
+                    false => link.to_string(),
+                });
+                text.push('[');
+            }
+            "/a" => {
+                let link = href.take().unwrap_or_default();
+                text.push_str(&format!("]({})", link));
+            }
+            "/h2" => {
+                out.push_str(&format!("## {}\n\n", text.trim()));
+                text.clear();
+            }
+            "/p" => {
+                wrap(&text, "", out);
+                out.push('\n');
+                text.clear();
+            }
+            "/li" => {
+                wrap(&text, "- ", out);
+                text.clear();
+            }
+            "/ul" => out.push('\n'),
+            _ => {}
+        }
+    }
+    while out.ends_with("\n\n") {
+        out.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzle::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 6: Wait For It ---</h2><p>The <em>ferry</em> quickly brings you across <a href="/2023/day/5">Island Island</a>.</p>
+<pre><code>Time:      7  15   30
+Distance:  9  40  200
+</code></pre>
+<ul>
+<li>Hold the button for <code>0</code> milliseconds &amp; the boat won't move.</li>
+</ul>
+<p>What do you get if you multiply these numbers together?</p>
+</article>
+<p>Your puzzle answer was <code>861300</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>There's really only <em>one race</em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_articles() {
+        assert_eq!(articles(PAGE).len(), 2);
+        assert_eq!(articles("<article class=\"day-desc\">unclosed").len(), 0);
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        assert_eq!(
+            to_markdown(PAGE),
+            "\
+## --- Day 6: Wait For It ---
+
+The *ferry* quickly brings you across [Island
+Island](https://adventofcode.com/2023/day/5).
+
+```
+Time:      7  15   30
+Distance:  9  40  200
+```
+
+- Hold the button for `0` milliseconds & the boat won't move.
+
+What do you get if you multiply these numbers together?
+
+## --- Part Two ---
+
+There's really only *one race*.
+"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+// Examples live in `tests/data/dayN/example*.txt`. The answers they should
+// give go next to them with an `.answers` extension, one `part N: answer`
+// line per part; parts without a line are only parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    pub path: PathBuf,
+    pub input: String,
+    pub answers: Vec<(u8, String)>,
+}
+
+pub fn data_dir(year: u16) -> PathBuf {
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/data");
+    match year {
+        2023 => data,
+        _ => data.join(year.to_string()),
+    }
+}
+
+pub fn parse_answers(s: &str) -> Result<Vec<(u8, String)>, String> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let invalid = || format!("invalid answer line `{}`", line);
+            let (part, answer) = line.split_once(':').ok_or_else(invalid)?;
+            let part = match part.trim() {
+                "part 1" => 1,
+                "part 2" => 2,
+                _ => return Err(invalid()),
+            };
+            Ok((part, answer.trim().to_string()))
+        })
+        .collect()
+}
+
+fn read_example(day: u8, path: PathBuf) -> io::Result<Example> {
+    let input = fs::read_to_string(&path)?;
+    let answers = match fs::read_to_string(path.with_extension("answers")) {
+        Ok(answers) => parse_answers(&answers)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(err),
+    };
+    Ok(Example {
+        day,
+        path,
+        input,
+        answers,
+    })
+}
+
+// Every example for the year, by day and then by file name.
+pub fn examples(year: u16) -> io::Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for entry in fs::read_dir(data_dir(year))? {
+        let dir = entry?.path();
+        let Some(day) = dir
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("day")?.parse().ok())
+        else {
+            continue;
+        };
+        for file in fs::read_dir(&dir)? {
+            let path = file?.path();
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("");
+            if name.starts_with("example") && name.ends_with(".txt") {
+                examples.push(read_example(day, path)?);
+            }
+        }
+    }
+    examples.sort_by(|a, b| (a.day, &a.path).cmp(&(b.day, &b.path)));
+    Ok(examples)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{examples::*, find, solve, DAYS};
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(
+            parse_answers("part 1: 142\n\npart 2:  Push the button\n"),
+            Ok(vec![
+                (1, "142".to_string()),
+                (2, "Push the button".to_string())
+            ])
+        );
+        assert_eq!(
+            parse_answers("part 3: 1"),
+            Err("invalid answer line `part 3: 1`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples = examples(2023).unwrap();
+        for day in 1..=DAYS {
+            assert!(
+                examples.iter().any(|example| example.day == day),
+                "day {}",
+                day
+            );
+        }
+        for example in examples {
+            let name = example.path.display();
+            let solver = find(2023, example.day).unwrap();
+            assert_eq!((solver.parse)(&example.input), Ok(()), "{}", name);
+            for (part, expected) in &example.answers {
+                let actual = solve(2023, example.day, *part, &example.input);
+                assert_eq!(actual.as_ref(), Ok(expected), "{} part {}", name, part);
+            }
+        }
+    }
+}
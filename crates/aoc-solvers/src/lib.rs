@@ -0,0 +1,344 @@
+pub mod examples;
+
+use aoc_core::{
+    progress::{self, Sink},
+    solver::Step,
+    Answer, Registration,
+};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc::Sender, Arc},
+    time::{Duration, Instant},
+};
+
+pub const DAYS: u8 = 25;
+
+// Every year with solutions in this workspace, oldest first. A new year gets
+// its own `aocYYYY` registry function and an arm in `registry`.
+pub const YEARS: [u16; 1] = [2023];
+
+fn aoc2023() -> [Registration; DAYS as usize] {
+    [
+        Registration::of::<day1::Day1>(),
+        Registration::of::<day2::Day2>(),
+        Registration::of::<day3::Day3>(),
+        Registration::of::<day4::Day4>(),
+        Registration::of::<day5::Day5>(),
+        Registration::of::<day6::Day6>(),
+        Registration::of::<day7::Day7>(),
+        Registration::of::<day8::Day8>(),
+        Registration::of::<day9::Day9>(),
+        Registration::of::<day10::Day10>(),
+        Registration::of::<day11::Day11>(),
+        Registration::of::<day12::Day12>(),
+        Registration::of::<day13::Day13>(),
+        Registration::of::<day14::Day14>(),
+        Registration::of::<day15::Day15>(),
+        Registration::of::<day16::Day16>(),
+        Registration::of::<day17::Day17>(),
+        Registration::of::<day18::Day18>(),
+        Registration::of::<day19::Day19>(),
+        Registration::of::<day20::Day20>(),
+        Registration::of::<day21::Day21>(),
+        Registration::of::<day22::Day22>(),
+        Registration::of::<day23::Day23>(),
+        Registration::of::<day24::Day24>(),
+        Registration::of::<day25::Day25>(),
+    ]
+}
+
+pub fn registry(year: u16) -> Vec<Registration> {
+    match year {
+        2023 => aoc2023().into(),
+        _ => Vec::new(),
+    }
+}
+
+pub fn find(year: u16, day: u8) -> Option<Registration> {
+    registry(year).into_iter().find(|solver| solver.day == day)
+}
+
+fn lookup(year: u16, day: u8) -> Result<Registration, String> {
+    if !YEARS.contains(&year) {
+        return Err(format!("there are no solutions for {}", year));
+    }
+    find(year, day).ok_or_else(|| format!("{} day {} is not implemented", year, day))
+}
+
+pub fn solve(year: u16, day: u8, part: u8, input: &str) -> Result<String, String> {
+    let solver = lookup(year, day)?;
+    match part {
+        1 | 2 => solver.parts[part as usize - 1](input),
+        _ => Err(format!("there is no part {}", part)),
+    }
+}
+
+// What `solve_with_events` reports as it goes, for frontends that show a
+// day's status live instead of waiting for both answers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveEvent {
+    ParseDone(Result<Duration, String>),
+    Progress {
+        part: u8,
+        percent: u8,
+    },
+    PartDone {
+        part: u8,
+        result: Result<(String, Duration), String>,
+    },
+}
+
+// A panic is reported like any other failure, so one broken day doesn't take
+// the frontend down with it.
+fn catch<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| Err("panicked".to_string()))
+}
+
+// Parses the input and solves both parts on the calling thread, sending
+// events as it goes. Nothing is solved when the input doesn't parse, and
+// solving stops once the receiver hangs up.
+pub fn solve_with_events(year: u16, day: u8, input: &str, events: Sender<SolveEvent>) {
+    let solver = match lookup(year, day) {
+        Ok(solver) => solver,
+        Err(message) => {
+            let _ = events.send(SolveEvent::ParseDone(Err(message)));
+            return;
+        }
+    };
+    let start = Instant::now();
+    let parsed = catch(|| (solver.parse)(input)).map(|()| start.elapsed());
+    let failed = parsed.is_err();
+    if events.send(SolveEvent::ParseDone(parsed)).is_err() || failed {
+        return;
+    }
+    for part in 1..=2 {
+        let progress = events.clone();
+        let sink: Sink = Arc::new(move |percent| {
+            let _ = progress.send(SolveEvent::Progress { part, percent });
+        });
+        let start = Instant::now();
+        let result = progress::with_sink(sink, || catch(|| solver.parts[part as usize - 1](input)))
+            .map(|answer| (answer, start.elapsed()));
+        if events.send(SolveEvent::PartDone { part, result }).is_err() {
+            return;
+        }
+    }
+}
+
+// Another way to solve a part, kept around to check the registered solutions
+// against. Slow variants are only fit for small generated inputs.
+#[derive(Clone, Copy, Debug)]
+pub struct Variant {
+    pub part: u8,
+    pub name: &'static str,
+    pub slow: bool,
+    pub solve: Step,
+}
+
+const fn variant(part: u8, name: &'static str, solve: Step) -> Variant {
+    Variant {
+        part,
+        name,
+        slow: false,
+        solve,
+    }
+}
+
+fn variants2023(day: u8) -> Vec<Variant> {
+    use day17::Algorithm as Search;
+    use day18::Algorithm as Area;
+    use day19::Algorithm as Ranges;
+    match day {
+        5 => vec![
+            variant(2, "intervals", |input| day5::part2(input).answer()),
+            Variant {
+                slow: true,
+                ..variant(2, "brute force", |input| day5::part2_brute(input).answer())
+            },
+        ],
+        11 => vec![
+            variant(1, "expand", |input| day11::part1(input).answer()),
+            variant(1, "count", |input| {
+                day11::parse_map(input)
+                    .map(|map| day11::expanded_distances(&map, 2))
+                    .answer()
+            }),
+            variant(1, "arithmetic", |input| {
+                day11::parse_map(input)
+                    .map(|map| day11::axis_distances(&map, 2))
+                    .answer()
+            }),
+            variant(2, "count", |input| day11::part2(input).answer()),
+            variant(2, "arithmetic", |input| {
+                day11::parse_map(input)
+                    .map(|map| day11::axis_distances(&map, 1_000_000))
+                    .answer()
+            }),
+        ],
+        #[cfg(feature = "gpu")]
+        16 => vec![
+            variant(2, "cpu", |input| day16::part2(input).answer()),
+            variant(2, "gpu", |input| day16::part2_gpu(input).answer()),
+        ],
+        17 => vec![
+            variant(1, "dijkstra", |input| {
+                day17::part1_with(input, Search::Dijkstra).answer()
+            }),
+            variant(1, "astar", |input| {
+                day17::part1_with(input, Search::AStar).answer()
+            }),
+            variant(1, "bidirectional", |input| {
+                day17::part1_with(input, Search::Bidirectional).answer()
+            }),
+            variant(2, "dijkstra", |input| {
+                day17::part2_with(input, Search::Dijkstra).answer()
+            }),
+            variant(2, "astar", |input| {
+                day17::part2_with(input, Search::AStar).answer()
+            }),
+            variant(2, "bidirectional", |input| {
+                day17::part2_with(input, Search::Bidirectional).answer()
+            }),
+        ],
+        18 => vec![
+            variant(1, "shoelace", |input| {
+                day18::part1_with(input, Area::Shoelace).answer()
+            }),
+            variant(1, "scanline", |input| {
+                day18::part1_with(input, Area::Scanline).answer()
+            }),
+            variant(1, "sweep", |input| {
+                day18::part1_with(input, Area::Sweep).answer()
+            }),
+            variant(2, "shoelace", |input| {
+                day18::part2_with(input, Area::Shoelace).answer()
+            }),
+            // Walks every row of a trench millions of rows tall.
+            Variant {
+                slow: true,
+                ..variant(2, "scanline", |input| {
+                    day18::part2_with(input, Area::Scanline).answer()
+                })
+            },
+            variant(2, "sweep", |input| {
+                day18::part2_with(input, Area::Sweep).answer()
+            }),
+        ],
+        19 => vec![
+            variant(2, "graph", |input| {
+                day19::part2_with(input, Ranges::Graph).answer()
+            }),
+            variant(2, "split", |input| {
+                day19::part2_with(input, Ranges::Split).answer()
+            }),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+// Empty for days with a single implementation.
+pub fn variants(year: u16, day: u8) -> Vec<Variant> {
+    match year {
+        2023 => variants2023(day),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_registry() {
+        let days = registry(2023);
+        assert!((1..=DAYS).eq(days.iter().map(|solver| solver.day)));
+        assert!(registry(2022).is_empty());
+        assert_eq!((days[1].parse)("Game 1: 3 blue"), Ok(()));
+        assert_eq!(
+            (days[1].parse)("Game 1: 3 teal"),
+            Err("line 1: invalid color `teal`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve(2023, 1, 2, "two1nine\neightwothree"),
+            Ok("112".to_string())
+        );
+        assert_eq!(
+            solve(2023, 25, 2, ""),
+            Ok("Push the big red button".to_string())
+        );
+        assert_eq!(
+            solve(2023, 26, 1, ""),
+            Err("2023 day 26 is not implemented".to_string())
+        );
+        assert_eq!(
+            solve(2022, 1, 1, ""),
+            Err("there are no solutions for 2022".to_string())
+        );
+        assert_eq!(solve(2023, 1, 3, ""), Err("there is no part 3".to_string()));
+        assert!(solve(2023, 19, 1, "in{x>10:A}").is_err());
+    }
+
+    #[test]
+    fn test_solve_with_events() {
+        let events = |day, input: &str| {
+            let (tx, rx) = std::sync::mpsc::channel();
+            solve_with_events(2023, day, input, tx);
+            rx.into_iter().collect::<Vec<_>>()
+        };
+        let example = include_str!("../../../tests/data/day12/example.txt");
+        let solved = events(12, example);
+        assert!(matches!(solved[0], SolveEvent::ParseDone(Ok(_))));
+        let progress: Vec<u8> = solved
+            .iter()
+            .filter_map(|event| match event {
+                SolveEvent::Progress { part: 2, percent } => Some(*percent),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(progress.last(), Some(&100));
+        let answers: Vec<&str> = solved
+            .iter()
+            .filter_map(|event| match event {
+                SolveEvent::PartDone {
+                    result: Ok((answer, _)),
+                    ..
+                } => Some(answer.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(answers, ["21", "525152"]);
+        assert_eq!(
+            events(26, ""),
+            [SolveEvent::ParseDone(Err(
+                "2023 day 26 is not implemented".to_string()
+            ))]
+        );
+        assert_eq!(
+            events(2, "Game 1: 3 teal"),
+            [SolveEvent::ParseDone(Err(
+                "line 1: invalid color `teal`".to_string()
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        for example in examples::examples(2023).unwrap() {
+            for variant in variants(2023, example.day) {
+                assert_eq!(
+                    (variant.solve)(&example.input),
+                    solve(2023, example.day, variant.part, &example.input),
+                    "{} part {} {}",
+                    example.path.display(),
+                    variant.part,
+                    variant.name
+                );
+            }
+        }
+        assert!(variants(2022, 18).is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+use aoc_core::Grid;
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, DynamicImage, Frame as GifFrame, ImageFormat, Rgb as Pixel,
+};
+use std::{collections::HashMap, fs::File, io::BufWriter, ops::ControlFlow, path::Path};
+
+pub use image::{ImageError, RgbImage};
+
+pub type Rgb = [u8; 3];
+
+// Pixels per tile edge; puzzle grids are ~140 tiles across, so this gives
+// images that fit on a screen without squinting.
+pub const SCALE: u32 = 4;
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colors: HashMap<char, Rgb>,
+    background: Rgb,
+}
+
+impl Palette {
+    pub fn new(background: Rgb) -> Self {
+        Self {
+            colors: HashMap::new(),
+            background,
+        }
+    }
+
+    // Gives every character in `tiles` the same color.
+    pub fn with(mut self, tiles: &str, color: Rgb) -> Self {
+        for tile in tiles.chars() {
+            self.colors.insert(tile, color);
+        }
+        self
+    }
+
+    pub fn color(&self, tile: char) -> Rgb {
+        self.colors.get(&tile).copied().unwrap_or(self.background)
+    }
+}
+
+// Linear blend, `t` is clamped to 0..=1.
+pub fn gradient(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    [0, 1, 2].map(|idx| (from[idx] as f64 + (to[idx] as f64 - from[idx] as f64) * t).round() as u8)
+}
+
+pub fn render(
+    width: usize,
+    height: usize,
+    scale: u32,
+    tile: impl Fn(usize, usize) -> Rgb,
+) -> RgbImage {
+    let scale = scale.max(1);
+    RgbImage::from_fn(width as u32 * scale, height as u32 * scale, |x, y| {
+        Pixel(tile((x / scale) as usize, (y / scale) as usize))
+    })
+}
+
+pub fn render_grid<T>(grid: &Grid<T>, scale: u32, color: impl Fn(&T) -> Rgb) -> RgbImage {
+    render(grid.width(), grid.height(), scale, |x, y| {
+        color(&grid[(x, y)])
+    })
+}
+
+// One tile per character. Short lines are padded with the background.
+pub fn render_text(text: &str, scale: u32, palette: &Palette) -> RgbImage {
+    let rows: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    render(width, rows.len(), scale, |x, y| match rows[y].get(x) {
+        Some(&tile) => palette.color(tile),
+        None => palette.background,
+    })
+}
+
+pub fn save_png(image: &RgbImage, path: impl AsRef<Path>) -> Result<(), ImageError> {
+    image.save_with_format(path, ImageFormat::Png)
+}
+
+// An endlessly looping animation showing each frame for `delay_ms`.
+pub fn save_gif(
+    frames: impl IntoIterator<Item = RgbImage>,
+    delay_ms: u32,
+    path: impl AsRef<Path>,
+) -> Result<(), ImageError> {
+    let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+    encoder.set_repeat(Repeat::Infinite)?;
+    let delay = Delay::from_numer_denom_ms(delay_ms, 1);
+    encoder.encode_frames(
+        frames
+            .into_iter()
+            .map(|image| GifFrame::from_parts(DynamicImage::from(image).into_rgba8(), 0, 0, delay)),
+    )
+}
+
+// One step of a simulation, as text `render_text` can draw, with a line
+// saying what just happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub text: String,
+    pub caption: String,
+}
+
+impl Frame {
+    pub fn new(text: impl Into<String>, caption: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            caption: caption.into(),
+        }
+    }
+}
+
+// Simulations hand every frame to one of these and stop as soon as it
+// breaks, so the viewer decides how far they run.
+pub type OnFrame<'a> = &'a mut dyn FnMut(Frame) -> ControlFlow<()>;
+
+// Up to `limit` frames of `simulate`.
+pub fn take_frames(limit: usize, simulate: impl FnOnce(OnFrame)) -> Vec<Frame> {
+    let mut frames = Vec::new();
+    simulate(&mut |frame| {
+        frames.push(frame);
+        match frames.len() < limit {
+            true => ControlFlow::Continue(()),
+            false => ControlFlow::Break(()),
+        }
+    });
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::{env, fs};
+
+    #[test]
+    fn test_render_text() {
+        let palette = Palette::new(BLACK).with("#", WHITE).with("<>", [255, 0, 0]);
+        let image = render_text("#.\n>", 2, &palette);
+        assert_eq!(image.dimensions(), (4, 4));
+        assert_eq!(image.get_pixel(1, 1).0, WHITE);
+        assert_eq!(image.get_pixel(2, 0).0, BLACK);
+        assert_eq!(image.get_pixel(0, 3).0, [255, 0, 0]);
+        assert_eq!(image.get_pixel(3, 3).0, BLACK);
+        let grid = Grid::new(2, 1, vec![0.0, 1.0]);
+        let image = render_grid(&grid, 1, |&t| gradient(BLACK, WHITE, t));
+        assert_eq!(image.get_pixel(1, 0).0, WHITE);
+        assert_eq!(gradient(BLACK, [200, 100, 0], 0.5), [100, 50, 0]);
+    }
+
+    #[test]
+    fn test_take_frames() {
+        let count = |on_frame: OnFrame| {
+            for n in 0.. {
+                if on_frame(Frame::new(n.to_string(), "")).is_break() {
+                    return;
+                }
+            }
+        };
+        let frames = take_frames(3, count);
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[2], Frame::new("2", ""));
+    }
+
+    #[test]
+    fn test_save_png() {
+        let path = env::temp_dir().join(format!("aoc-viz-{}.png", std::process::id()));
+        let image = render(3, 2, SCALE, |x, _| gradient(BLACK, WHITE, x as f64 / 2.0));
+        save_png(&image, &path).unwrap();
+        let loaded = image::open(&path).unwrap().to_rgb8();
+        assert_eq!(loaded, image);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_save_gif() {
+        let path = env::temp_dir().join(format!("aoc-viz-{}.gif", std::process::id()));
+        let frames = [BLACK, WHITE].map(|color| render(2, 2, 1, |_, _| color));
+        save_gif(frames, 100, &path).unwrap();
+        let loaded = image::open(&path).unwrap().to_rgb8();
+        assert_eq!(loaded.get_pixel(0, 0).0, BLACK);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+use aoc_solvers::{find, DAYS};
+use wasm_bindgen::prelude::*;
+
+const YEAR: u16 = 2023;
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Solution {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub errors: Vec<String>,
+}
+
+impl Solution {
+    pub fn new(day: u8, input: &str) -> Result<Self, String> {
+        let [part1, part2] = find(YEAR, day)
+            .ok_or_else(|| format!("day {} is not implemented (1..={})", day, DAYS))?
+            .parts
+            .map(|solver| solver(input));
+        let errors = [(1, &part1), (2, &part2)]
+            .into_iter()
+            .filter_map(|(part, result)| {
+                let message = result.as_ref().err()?;
+                Some(format!("part {}: {}", part, message))
+            })
+            .collect();
+        Ok(Self {
+            part1: part1.ok(),
+            part2: part2.ok(),
+            errors,
+        })
+    }
+}
+
+#[wasm_bindgen]
+pub fn days() -> u8 {
+    DAYS
+}
+
+// wasm32-unknown-unknown can't unwind, so a solver that panics traps the whole
+// instance instead of coming back as an error. Bad input has to be reported
+// through the solvers' errors to reach the caller.
+#[wasm_bindgen]
+pub fn solve(day: u8, input: &str) -> Result<JsValue, JsError> {
+    Ok(Solution::new(day, input)
+        .map_err(|err| JsError::new(&err))?
+        .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_solution() {
+        let solution = Solution::new(2, "Game 1: 3 blue, 4 red, 1 green").unwrap();
+        assert_eq!(solution.part1.as_deref(), Some("1"));
+        assert_eq!(solution.part2.as_deref(), Some("12"));
+        assert!(solution.errors.is_empty());
+        let solution = Solution::new(2, "Game 1: 3 teal").unwrap();
+        assert_eq!(solution.part1, None);
+        assert_eq!(solution.errors[0], "part 1: line 1: invalid color `teal`");
+        assert!(Solution::new(26, "").is_err());
+    }
+}
@@ -0,0 +1,24 @@
+// Build the package first with `wasm-pack build crates/aoc-wasm --target web --out-dir www/pkg`.
+import init, { days, solve } from "./pkg/aoc_wasm.js";
+
+await init();
+
+const day = document.getElementById("day");
+for (let n = 1; n <= days(); n++) {
+  day.add(new Option(n, n));
+}
+
+document.getElementById("solve").addEventListener("click", () => {
+  const show = (id, text) => (document.getElementById(id).textContent = text ?? "");
+  try {
+    const solution = solve(Number(day.value), document.getElementById("input").value);
+    show("part1", solution.part1);
+    show("part2", solution.part2);
+    show("errors", solution.errors.join("\n"));
+    solution.free();
+  } catch (err) {
+    show("part1");
+    show("part2");
+    show("errors", err.message);
+  }
+});
@@ -0,0 +1,278 @@
+use aoc_core::{matcher::Match, parallel::*, Answer, AocError, Matcher, Solver};
+use std::{fmt::Display, io, io::BufRead};
+
+// Digits and their spelled-out names, each worth its index mod 10 once the
+// zero is skipped.
+pub const TOKENS: [&str; 19] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "one", "two", "three", "four", "five", "six",
+    "seven", "eight", "nine",
+];
+
+pub fn token_value(pattern: usize) -> i64 {
+    match pattern {
+        0..=9 => pattern as i64,
+        _ => pattern as i64 - 9,
+    }
+}
+
+pub fn token_matcher() -> Matcher {
+    Matcher::new(&TOKENS)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Token {
+    pub text: &'static str,
+    // Byte offsets into the line.
+    pub start: usize,
+    pub end: usize,
+    pub value: i64,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} at {}..{}", self.text, self.start, self.end)
+    }
+}
+
+// How one line was read, for comparing against other solutions line by line.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LineReport<'a> {
+    // Counted from 1.
+    pub number: usize,
+    pub line: &'a str,
+    pub first: Option<Token>,
+    pub last: Option<Token>,
+    pub value: Option<i64>,
+}
+
+impl Display for LineReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {} -> ", self.number, self.line)?;
+        match (self.first, self.last, self.value) {
+            (Some(first), Some(last), Some(value)) => {
+                write!(f, "first {}, last {} = {}", first, last, value)
+            }
+            _ => write!(f, "no digits"),
+        }
+    }
+}
+
+fn line_reports<'a>(
+    input: &'a str,
+    tokens: &'static [&'static str],
+) -> impl Iterator<Item = LineReport<'a>> {
+    let matcher = Matcher::new(tokens);
+    input.lines().enumerate().map(move |(i, line)| {
+        let token = |m: Match| Token {
+            text: tokens[m.pattern],
+            start: m.start,
+            end: m.end,
+            value: token_value(m.pattern),
+        };
+        let found = matcher.first_last(line.as_bytes());
+        let first = found.map(|(first, _)| token(first));
+        let last = found.map(|(_, last)| token(last));
+        LineReport {
+            number: i + 1,
+            line,
+            first,
+            last,
+            value: first
+                .zip(last)
+                .map(|(first, last)| first.value * 10 + last.value),
+        }
+    })
+}
+
+// Part 2's reading of every line, spelled-out digits included.
+pub fn calibrations(input: &str) -> impl Iterator<Item = LineReport<'_>> {
+    line_reports(input, &TOKENS)
+}
+
+// Part 1's reading, digits only.
+pub fn digit_calibrations(input: &str) -> impl Iterator<Item = LineReport<'_>> {
+    line_reports(input, &TOKENS[..10])
+}
+
+fn extract_calibration_value_part2(matcher: &Matcher, s: &str) -> Option<i64> {
+    let (first, last) = matcher.first_last(s.as_bytes())?;
+    Some(token_value(first.pattern) * 10 + token_value(last.pattern))
+}
+
+fn extract_calibration_value_part1(s: &str) -> Option<i64> {
+    let mut digits = s
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| (b - b'0') as i64);
+    let first = digits.next()?;
+    Some(first * 10 + digits.next_back().unwrap_or(first))
+}
+
+// What both parts are summed into, whether the input is read whole or a line
+// at a time. It would take 10^35 lines to overflow.
+pub type Total = u128;
+
+// Sums what `read` finds on every line, or names the first line it finds
+// nothing on.
+fn sum_values(
+    lines: &[&str],
+    read: impl Fn(&str) -> Option<i64> + Send + Sync,
+) -> Result<Total, AocError> {
+    par_iter(lines)
+        .enumerate()
+        .map(|(idx, line)| {
+            read(line)
+                .map(|value| value as Total)
+                .ok_or_else(|| AocError::Missing("digit").at_line(idx + 1))
+        })
+        .sum()
+}
+
+pub fn digit_total(lines: &[&str]) -> Result<Total, AocError> {
+    sum_values(lines, extract_calibration_value_part1)
+}
+
+pub fn spelled_total(lines: &[&str]) -> Result<Total, AocError> {
+    let matcher = token_matcher();
+    sum_values(lines, |line| {
+        extract_calibration_value_part2(&matcher, line)
+    })
+}
+
+pub fn part1(input: &str) -> Result<Total, AocError> {
+    digit_total(&input.lines().collect::<Vec<_>>())
+}
+
+pub fn part2(input: &str) -> Result<Total, AocError> {
+    spelled_total(&input.lines().collect::<Vec<_>>())
+}
+
+// Both parts summed a line at a time, for inputs too big to read into
+// memory. A part is None once a line has nothing for it to read.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Sums {
+    pub part1: Option<Total>,
+    pub part2: Option<Total>,
+}
+
+pub fn sum_lines(reader: impl BufRead) -> io::Result<Sums> {
+    let matcher = token_matcher();
+    let mut sums = Sums {
+        part1: Some(0),
+        part2: Some(0),
+    };
+    let add = |sum: Option<Total>, value: Option<i64>| Some(sum? + value? as Total);
+    for line in reader.lines() {
+        let line = line?;
+        sums.part1 = add(sums.part1, extract_calibration_value_part1(&line));
+        sums.part2 = add(sums.part2, extract_calibration_value_part2(&matcher, &line));
+    }
+    Ok(sums)
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Parsed<'a> = Vec<&'a str>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        1
+    }
+
+    fn parse(input: &str) -> Result<Vec<&'_ str>, AocError> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(lines: &Self::Parsed<'_>) -> impl Answer {
+        digit_total(lines)
+    }
+
+    fn part2(lines: &Self::Parsed<'_>) -> impl Answer {
+        spelled_total(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day1/example.txt");
+    const TEST_INPUT2: &str = include_str!("../../../tests/data/day1/example2.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(TEST_INPUT), Ok(142));
+        assert_eq!(
+            part1("1\nabc").unwrap_err().to_string(),
+            "line 2: missing digit"
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(TEST_INPUT2), Ok(281));
+        // Spelled-out digits may share letters.
+        assert_eq!(part2("xoneight\n7twone"), Ok(18 + 71));
+        assert_eq!(
+            part2("abc").unwrap_err().to_string(),
+            "line 1: missing digit"
+        );
+    }
+
+    #[test]
+    fn test_calibrations() {
+        let reports: Vec<LineReport> = calibrations(TEST_INPUT2).collect();
+        assert_eq!(
+            reports[1],
+            LineReport {
+                number: 2,
+                line: "eightwothree",
+                first: Some(Token {
+                    text: "eight",
+                    start: 0,
+                    end: 5,
+                    value: 8,
+                }),
+                last: Some(Token {
+                    text: "three",
+                    start: 7,
+                    end: 12,
+                    value: 3,
+                }),
+                value: Some(83),
+            }
+        );
+        assert_eq!(
+            reports[1].to_string(),
+            "line 2: eightwothree -> first \"eight\" at 0..5, last \"three\" at 7..12 = 83"
+        );
+        let total: i64 = reports.iter().filter_map(|report| report.value).sum();
+        assert_eq!(Ok(total as Total), part2(TEST_INPUT2));
+        let digits: Vec<LineReport> = digit_calibrations(TEST_INPUT2).collect();
+        assert_eq!(digits[1].to_string(), "line 2: eightwothree -> no digits");
+        assert_eq!(digits[0].value, Some(11));
+    }
+
+    #[test]
+    fn test_sum_lines() {
+        assert_eq!(
+            sum_lines(TEST_INPUT.as_bytes()).unwrap(),
+            Sums {
+                part1: Some(142),
+                part2: Some(142),
+            }
+        );
+        assert_eq!(
+            sum_lines(TEST_INPUT2.as_bytes()).unwrap(),
+            Sums {
+                part1: None,
+                part2: Some(281),
+            }
+        );
+        assert_eq!(
+            sum_lines("7\r\n".repeat(1000).as_bytes()).unwrap().part1,
+            Some(77000)
+        );
+    }
+}
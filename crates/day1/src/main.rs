@@ -1,85 +1,82 @@
-use std::{collections::HashMap, fs::read_to_string};
+use aoc_core::{error::OrExit, timing::Timings};
+use aoc_input::cli::{Answers, DayArgs};
+use clap::Parser;
+use day1::*;
+use std::error::Error;
 
-fn extract_calibration_value_part2(s: &str) -> i64 {
-    let lookup = HashMap::from([
-        ("0", 0),
-        ("1", 1),
-        ("2", 2),
-        ("3", 3),
-        ("4", 4),
-        ("5", 5),
-        ("6", 6),
-        ("7", 7),
-        ("8", 8),
-        ("9", 9),
-        ("one", 1),
-        ("two", 2),
-        ("three", 3),
-        ("four", 4),
-        ("five", 5),
-        ("six", 6),
-        ("seven", 7),
-        ("eight", 8),
-        ("nine", 9),
-    ]);
-    let (_, first_key) = lookup
-        .keys()
-        .filter_map(|c| s.find(c).map(|pos| (pos, *c)))
-        .min()
-        .unwrap();
-    let (_, last_key) = lookup
-        .keys()
-        .filter_map(|c| s.rfind(c).map(|pos| (pos, *c)))
-        .max()
-        .unwrap();
-    lookup.get(first_key).unwrap() * 10 + lookup.get(last_key).unwrap()
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    day: DayArgs,
+    /// Sum the input a line at a time rather than reading it all first, for
+    /// files too big for memory
+    #[arg(long)]
+    stream: bool,
+    /// Print the first and last digit found on every line and the value they
+    /// make
+    #[arg(long, conflicts_with_all = ["stream", "json"])]
+    explain: bool,
 }
 
-fn extract_calibration_value_part1(s: &str) -> i64 {
-    let digits: Vec<u32> = s.chars().filter_map(|c| c.to_digit(10)).collect();
-    (*digits.first().unwrap() as i64) * 10 + (*digits.last().unwrap() as i64)
-}
-
-fn sum_calibration_values_part1(input: &str) -> i64 {
-    input.lines().map(extract_calibration_value_part1).sum()
+fn explain<'a>(part: u8, reports: impl Iterator<Item = LineReport<'a>>) {
+    println!("Part {}:", part);
+    for report in reports {
+        println!("  {}", report);
+    }
 }
 
-fn sum_calibration_values_part2(input: &str) -> i64 {
-    input.lines().map(extract_calibration_value_part2).sum()
+fn stream(args: &DayArgs, timings: &mut Timings) {
+    let sums = timings
+        .time("stream", || -> Result<Sums, Box<dyn Error>> {
+            let reader = args.open(2023, 1, env!("CARGO_MANIFEST_DIR"))?;
+            Ok(sum_lines(reader)?)
+        })
+        .or_exit("Could not read input");
+    let mut answers = Answers::new(args);
+    for (part, sum) in [(1, sums.part1), (2, sums.part2)] {
+        match sum {
+            Some(sum) if args.runs(part) => answers.report(part, sum),
+            None if args.runs(part) => eprintln!("Part {}: a line has no digits", part),
+            _ => {}
+        }
+    }
+    answers.finish();
 }
 
 fn main() {
-    let input = read_to_string("input.txt").unwrap();
-    let total = sum_calibration_values_part1(&input);
-    println!("Part 1: {}", total);
-    let total = sum_calibration_values_part2(&input);
-    println!("Part 2: {}", total);
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{sum_calibration_values_part1, sum_calibration_values_part2};
-
-    #[test]
-    fn basic_test_part1() {
-        let basic_input = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-        let sum = sum_calibration_values_part1(basic_input);
-        assert_eq!(sum, 142);
+    let cli = Cli::parse();
+    let args = &cli.day;
+    let _log = args.init_logging();
+    let mut timings = Timings::new(args.time);
+    if cli.stream {
+        stream(args, &mut timings);
+        timings.print();
+        return;
     }
-
-    #[test]
-    fn basic_test_part2() {
-        let basic_input = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-        let sum = sum_calibration_values_part2(basic_input);
-        assert_eq!(sum, 281);
+    let input = timings
+        .time("read", || args.load(2023, 1, env!("CARGO_MANIFEST_DIR")))
+        .or_exit("Could not load input");
+    if cli.explain {
+        if args.runs(1) {
+            explain(1, digit_calibrations(&input));
+        }
+        if args.runs(2) {
+            explain(2, calibrations(&input));
+        }
+    }
+    let mut answers = Answers::new(args);
+    if args.runs(1) {
+        answers.report(
+            1,
+            timings.time("part 1", || part1(&input)).or_exit("Part 1"),
+        );
+    }
+    if args.runs(2) {
+        answers.report(
+            2,
+            timings.time("part 2", || part2(&input)).or_exit("Part 2"),
+        );
     }
+    answers.finish();
+    timings.print();
 }
@@ -0,0 +1,273 @@
+use aoc_core::{geometry::step, Answer, AocError, Direction, Grid, Solver};
+use aoc_viz::{render_text, Palette, RgbImage, SCALE};
+use std::collections::{HashSet, VecDeque};
+
+fn char_to_directions(c: char) -> Vec<Direction> {
+    match c {
+        'S' => Direction::ALL.to_vec(),
+        '|' => vec![Direction::Up, Direction::Down],
+        '-' => vec![Direction::Left, Direction::Right],
+        'L' => vec![Direction::Up, Direction::Right],
+        'J' => vec![Direction::Up, Direction::Left],
+        '7' => vec![Direction::Down, Direction::Left],
+        'F' => vec![Direction::Down, Direction::Right],
+        _ => vec![],
+    }
+}
+
+// The tiles as rows, along with where S is. Anything that isn't a pipe is
+// ground, like the I and O marks in the examples.
+pub struct Pipes {
+    tiles: Vec<Vec<char>>,
+    start: (usize, usize),
+}
+
+pub fn parse_pipes(s: &str) -> Result<Pipes, AocError> {
+    let grid = Grid::<char>::try_from(s)?;
+    let tiles = grid.rows().map(<[char]>::to_vec).collect();
+    let start = grid
+        .find(|ch| *ch == 'S')
+        .ok_or(AocError::Missing("start tile"))?;
+    Ok(Pipes { tiles, start })
+}
+
+fn add_to_explore_queue(
+    queue: &mut VecDeque<((usize, usize), usize, Direction)>,
+    valid_directions: &[Direction],
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    dist: usize,
+) {
+    for &direction in valid_directions {
+        if let Some(pos) = step((x, y), direction, width, height) {
+            queue.push_back((pos, dist + 1, direction.opposite()));
+        }
+    }
+}
+
+pub fn farthest_distance(pipes: &Pipes) -> usize {
+    let (map, start) = (&pipes.tiles, pipes.start);
+    let (width, height) = (map[0].len(), map.len());
+    let mut distance_map: Vec<Vec<usize>> = vec![vec![0; width]; height];
+    let mut queue: VecDeque<((usize, usize), usize, Direction)> = VecDeque::new();
+    let mut explored: HashSet<(usize, usize)> = HashSet::new();
+    queue.push_back((start, 0, Direction::Up));
+    while let Some(((x, y), dist, incoming_dir)) = queue.pop_front() {
+        if explored.contains(&(x, y)) {
+            continue;
+        }
+        let valid_directions = char_to_directions(map[y][x]);
+        if !valid_directions.contains(&incoming_dir) {
+            continue;
+        }
+        distance_map[y][x] = dist;
+        explored.insert((x, y));
+        add_to_explore_queue(&mut queue, &valid_directions, x, y, width, height, dist);
+    }
+    distance_map.into_iter().flatten().max().unwrap_or(0)
+}
+
+fn get_start_character(map: &[Vec<char>], x: usize, y: usize) -> char {
+    let has_left = x > 0 && "-FL".contains(map[y][x - 1]);
+    let has_up = y > 0 && "|F7".contains(map[y - 1][x]);
+    let has_down = y < map.len() - 1 && "|JL".contains(map[y + 1][x]);
+    if has_up {
+        if has_down {
+            '|'
+        } else if has_left {
+            'J'
+        } else {
+            'L'
+        }
+    } else if has_down {
+        if has_left {
+            '7'
+        } else {
+            'F'
+        }
+    } else {
+        '-'
+    }
+}
+
+// The main loop, with every other tile cleared to '.' and S replaced by the
+// pipe it stands for.
+pub fn loop_map(pipes: &Pipes) -> Vec<Vec<char>> {
+    let (map, (start_x, start_y)) = (&pipes.tiles, pipes.start);
+    let (width, height) = (map[0].len(), map.len());
+    let mut pipe_map: Vec<Vec<char>> = vec![vec!['.'; width]; height];
+    let mut queue: VecDeque<((usize, usize), usize, Direction)> = VecDeque::new();
+    let mut explored: HashSet<(usize, usize)> = HashSet::new();
+    queue.push_back(((start_x, start_y), 0, Direction::Up));
+    while let Some(((x, y), dist, incoming_dir)) = queue.pop_front() {
+        if explored.contains(&(x, y)) {
+            continue;
+        }
+        let valid_directions = char_to_directions(map[y][x]);
+        if !valid_directions.contains(&incoming_dir) {
+            continue;
+        }
+        pipe_map[y][x] = map[y][x];
+        explored.insert((x, y));
+        add_to_explore_queue(&mut queue, &valid_directions, x, y, width, height, dist);
+    }
+    pipe_map[start_y][start_x] = get_start_character(&pipe_map, start_x, start_y);
+    pipe_map
+}
+
+fn enclosed_tiles(pipe_map: &[Vec<char>]) -> Vec<(usize, usize)> {
+    let mut tiles = Vec::new();
+    for (y, line) in pipe_map.iter().enumerate() {
+        let mut in_boundary = false;
+        let mut stack: Vec<char> = Vec::default();
+        for (x, &ch) in line.iter().enumerate() {
+            match ch {
+                '|' => in_boundary = !in_boundary,
+                'F' | 'L' => stack.push(ch),
+                'J' if stack.pop() != Some('L') => in_boundary = !in_boundary,
+                '7' if stack.pop() != Some('F') => in_boundary = !in_boundary,
+                _ => {}
+            }
+            if in_boundary && ch == '.' {
+                tiles.push((x, y));
+            }
+        }
+    }
+    tiles
+}
+
+pub fn enclosed_count(pipes: &Pipes) -> usize {
+    let enclosed = enclosed_tiles(&loop_map(pipes)).len();
+    tracing::debug!(enclosed, "tiles inside the loop");
+    tracing::trace!("loop map:\n{}", draw_terminal(pipes, false));
+    enclosed
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    Ok(farthest_distance(&parse_pipes(s)?))
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    Ok(enclosed_count(&parse_pipes(s)?))
+}
+
+fn enclosed_map(pipes: &Pipes) -> Vec<Vec<char>> {
+    let mut pipe_map = loop_map(pipes);
+    for (x, y) in enclosed_tiles(&pipe_map) {
+        pipe_map[y][x] = 'I';
+    }
+    pipe_map
+}
+
+// The loop with enclosed tiles marked `I`, as drawn in the puzzle.
+pub fn render_enclosed(s: &str) -> Result<String, AocError> {
+    Ok(enclosed_map(&parse_pipes(s)?)
+        .iter()
+        .map(|line| line.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+// The loop in box-drawing characters with the start cell marked `S`, colored
+// with ANSI escapes unless `color` is false.
+pub fn render_terminal(s: &str, color: bool) -> Result<String, AocError> {
+    Ok(draw_terminal(&parse_pipes(s)?, color))
+}
+
+fn draw_terminal(pipes: &Pipes, color: bool) -> String {
+    let start = pipes.start;
+    enclosed_map(pipes)
+        .iter()
+        .enumerate()
+        .map(|(y, line)| {
+            line.iter()
+                .enumerate()
+                .map(|(x, &ch)| {
+                    let (glyph, code) = match ch {
+                        _ if (x, y) == start => ('S', "1;31"),
+                        '|' => ('│', "33"),
+                        '-' => ('─', "33"),
+                        'L' => ('└', "33"),
+                        'J' => ('┘', "33"),
+                        '7' => ('┐', "33"),
+                        'F' => ('┌', "33"),
+                        'I' => ('I', "32"),
+                        _ => return ch.to_string(),
+                    };
+                    match color {
+                        true => format!("\x1b[{}m{}\x1b[0m", code, glyph),
+                        false => glyph.to_string(),
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn render_image(s: &str) -> Result<RgbImage, AocError> {
+    let palette = Palette::new([24, 24, 32])
+        .with("|-LJ7F", [255, 160, 40])
+        .with("I", [60, 200, 90]);
+    Ok(render_text(&render_enclosed(s)?, SCALE, &palette))
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Parsed<'a> = Pipes;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        10
+    }
+
+    fn parse(input: &str) -> Result<Pipes, AocError> {
+        parse_pipes(input)
+    }
+
+    fn part1(pipes: &Self::Parsed<'_>) -> impl Answer {
+        farthest_distance(pipes)
+    }
+
+    fn part2(pipes: &Self::Parsed<'_>) -> impl Answer {
+        enclosed_count(pipes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT_3: &str = include_str!("../../../tests/data/day10/example3.txt");
+    const TEST_INPUT_4: &str = include_str!("../../../tests/data/day10/example4.txt");
+    const TEST_INPUT_6: &str = include_str!("../../../tests/data/day10/example6.txt");
+
+    #[test]
+    fn test_render_enclosed() {
+        insta::assert_snapshot!(render_enclosed(TEST_INPUT_6).unwrap());
+        assert_eq!(render_image(TEST_INPUT_6).unwrap().dimensions(), (40, 36));
+    }
+
+    #[test]
+    fn test_render_terminal() {
+        insta::assert_snapshot!(render_terminal(TEST_INPUT_4, false).unwrap());
+        let colored = render_terminal(TEST_INPUT_3, true).unwrap();
+        assert!(colored.starts_with("..\x1b[33m┌\x1b[0m"));
+        assert!(colored.contains("\x1b[1;31mS\x1b[0m"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part2("F-7\nL-J").unwrap_err().to_string(),
+            "missing start tile"
+        );
+        assert_eq!(
+            part1("S-7\n|.").unwrap_err().to_string(),
+            "line 2: expected 3 columns, found 2"
+        );
+    }
+}
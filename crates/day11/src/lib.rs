@@ -0,0 +1,218 @@
+use aoc_core::{Answer, AocError, Grid, Solver};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point {
+    x: usize,
+    y: usize,
+}
+
+impl Point {
+    pub fn distance_to(&self, other: &Self) -> usize {
+        self.y.abs_diff(other.y) + self.x.abs_diff(other.x)
+    }
+}
+
+fn transpose_map(map: Vec<Vec<char>>) -> Vec<Vec<char>> {
+    let width = map.first().unwrap().len();
+    let height = map.len();
+    let mut new_map = vec![vec!['.'; height]; width];
+    for (y, line) in map.into_iter().enumerate() {
+        for (x, ch) in line.into_iter().enumerate() {
+            new_map[x][y] = ch;
+        }
+    }
+    new_map
+}
+
+fn expand_map_vertical(map: Vec<Vec<char>>) -> Vec<Vec<char>> {
+    map.into_iter()
+        .flat_map(|line| {
+            if line.iter().all(|c| *c == '.') {
+                vec![line.clone(), line].into_iter()
+            } else {
+                vec![line].into_iter()
+            }
+        })
+        .collect()
+}
+
+fn expand_map(map: Vec<Vec<char>>) -> Vec<Vec<char>> {
+    transpose_map(expand_map_vertical(transpose_map(expand_map_vertical(map))))
+}
+
+pub fn parse_map(s: &str) -> Result<Vec<Vec<char>>, AocError> {
+    let grid = Grid::<char>::try_from(s)?;
+    if grid.width() == 0 {
+        return Err(AocError::Missing("map"));
+    }
+    Ok(grid.rows().map(<[char]>::to_vec).collect())
+}
+
+fn get_points(map: &[Vec<char>]) -> Vec<Point> {
+    map.iter()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.iter().enumerate().filter_map(move |(x, ch)| {
+                if *ch == '#' {
+                    Some(Point { x, y })
+                } else {
+                    None
+                }
+            })
+        })
+        .collect()
+}
+
+pub fn galaxy_distances(map: &[Vec<char>]) -> usize {
+    let map = expand_map(map.to_vec());
+    let points = get_points(&map);
+
+    points
+        .iter()
+        .enumerate()
+        .flat_map(|(skip, point1)| {
+            points
+                .iter()
+                .skip(skip)
+                .map(|point2| point1.distance_to(point2))
+        })
+        .sum()
+}
+
+pub fn expanded_distances(map: &[Vec<char>], expand_factor: usize) -> usize {
+    let empty_y: Vec<usize> = map
+        .iter()
+        .enumerate()
+        .filter_map(|(y, line)| {
+            if line.iter().all(|c| *c == '.') {
+                Some(y)
+            } else {
+                None
+            }
+        })
+        .collect();
+    let mut empty_x: Vec<usize> = Vec::default();
+    for x in 0..map.first().unwrap().len() {
+        let mut all_empty = true;
+        for (y, _) in map.iter().enumerate() {
+            if map[y][x] != '.' {
+                all_empty = false;
+                break;
+            }
+        }
+        if all_empty {
+            empty_x.push(x);
+        }
+    }
+    let points = get_points(map);
+    points
+        .iter()
+        .enumerate()
+        .flat_map(|(skip, point1)| {
+            points.iter().skip(skip).map(|point2| {
+                point1.distance_to(point2)
+                    + empty_x
+                        .iter()
+                        .filter(|x_line| {
+                            point1.x.min(point2.x) < **x_line && **x_line < point1.x.max(point2.x)
+                        })
+                        .count()
+                        * (expand_factor - 1)
+                    + empty_y
+                        .iter()
+                        .filter(|y_line| {
+                            point1.y.min(point2.y) < **y_line && **y_line < point1.y.max(point2.y)
+                        })
+                        .count()
+                        * (expand_factor - 1)
+            })
+        })
+        .sum()
+}
+
+// Sums the distances along each axis separately: with the galaxies sorted by
+// their expanded coordinate, the one at index `i` is the far end of `i` pairs
+// and the near end of the rest.
+pub fn axis_distances(map: &[Vec<char>], expand_factor: usize) -> usize {
+    let points = get_points(map);
+    let axis = |coords: Vec<usize>, len: usize| {
+        let mut occupied = vec![false; len];
+        for &pos in &coords {
+            occupied[pos] = true;
+        }
+        let mut expanded = Vec::with_capacity(len);
+        let mut offset = 0;
+        for (pos, occupied) in occupied.into_iter().enumerate() {
+            expanded.push(pos + offset);
+            if !occupied {
+                offset += expand_factor - 1;
+            }
+        }
+        let mut coords: Vec<usize> = coords.into_iter().map(|pos| expanded[pos]).collect();
+        coords.sort_unstable();
+        let mut before = 0;
+        let mut total = 0;
+        for (idx, pos) in coords.into_iter().enumerate() {
+            total += pos * idx - before;
+            before += pos;
+        }
+        total
+    };
+    axis(points.iter().map(|point| point.x).collect(), map[0].len())
+        + axis(points.iter().map(|point| point.y).collect(), map.len())
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    Ok(galaxy_distances(&parse_map(s)?))
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    Ok(expanded_distances(&parse_map(s)?, 1_000_000))
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Parsed<'a> = Vec<Vec<char>>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        11
+    }
+
+    fn parse(input: &str) -> Result<Vec<Vec<char>>, AocError> {
+        parse_map(input)
+    }
+
+    fn part1(map: &Self::Parsed<'_>) -> impl Answer {
+        galaxy_distances(map)
+    }
+
+    fn part2(map: &Self::Parsed<'_>) -> impl Answer {
+        expanded_distances(map, 1_000_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day11/example.txt");
+
+    #[test]
+    fn test_expansion() {
+        let map = parse_map(TEST_INPUT).unwrap();
+        assert_eq!(expanded_distances(&map, 100), 8410);
+        assert_eq!(axis_distances(&map, 2), 374);
+        assert_eq!(axis_distances(&map, 100), 8410);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(part1("").unwrap_err().to_string(), "missing map");
+        assert_eq!(
+            part2("#..\n.#").unwrap_err().to_string(),
+            "line 2: expected 3 columns, found 2"
+        );
+    }
+}
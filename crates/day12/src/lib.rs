@@ -0,0 +1,245 @@
+use aoc_core::{
+    error::{parse_lines, parse_number, split_once},
+    parallel::*,
+    progress::Progress,
+    Answer, AocError, Solver,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Spring {
+    Unknown,
+    Damaged,
+    Operational,
+}
+
+impl TryFrom<char> for Spring {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '?' => Ok(Self::Unknown),
+            '#' => Ok(Self::Damaged),
+            '.' => Ok(Self::Operational),
+            _ => Err(AocError::invalid("spring", &value.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Line {
+    springs: Vec<Spring>,
+    counts: Vec<usize>,
+}
+
+impl TryFrom<&str> for Line {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (chars, counts) = split_once(value, " ", "line")?;
+        let springs = chars
+            .chars()
+            .map(Spring::try_from)
+            .collect::<Result<_, _>>()?;
+        let counts = counts
+            .split(',')
+            .map(|num| parse_number("count", num))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { springs, counts })
+    }
+}
+
+fn get_counts_recursive(
+    map: &mut HashMap<(Line, Spring), usize>,
+    line: Line,
+    prev: Spring,
+) -> usize {
+    let key = (line.clone(), prev);
+    if let Some(result) = map.get(&key) {
+        return *result;
+    }
+    let (springs, target_counts) = (line.springs, line.counts);
+    if springs.is_empty()
+        && (target_counts.is_empty() || (target_counts.len() == 1 && target_counts[0] == 0))
+    {
+        return 1;
+    } else if springs.is_empty() {
+        return 0;
+    } else if target_counts.is_empty() {
+        // Invalid if no targets and still some damaged.
+        if springs.contains(&Spring::Damaged) {
+            return 0;
+        }
+    }
+
+    let result = match (prev, springs[0]) {
+        (Spring::Operational, Spring::Operational) => get_counts_recursive(
+            map,
+            Line {
+                springs: springs.into_iter().skip(1).collect(),
+                counts: target_counts,
+            },
+            Spring::Operational,
+        ),
+        (Spring::Damaged, Spring::Operational) => {
+            if target_counts[0] == 0 {
+                get_counts_recursive(
+                    map,
+                    Line {
+                        springs: springs.into_iter().skip(1).collect(),
+                        counts: target_counts.into_iter().skip(1).collect(),
+                    },
+                    Spring::Operational,
+                )
+            } else {
+                0
+            }
+        }
+        (_, Spring::Damaged) => {
+            if target_counts[0] == 0 {
+                0
+            } else {
+                get_counts_recursive(
+                    map,
+                    Line {
+                        springs: springs.into_iter().skip(1).collect(),
+                        counts: Some(target_counts[0] - 1)
+                            .into_iter()
+                            .chain(target_counts.into_iter().skip(1))
+                            .collect::<Vec<usize>>(),
+                    },
+                    Spring::Damaged,
+                )
+            }
+        }
+        (_, Spring::Unknown) => {
+            get_counts_recursive(
+                map,
+                Line {
+                    springs: Some(Spring::Damaged)
+                        .into_iter()
+                        .chain(springs.iter().copied().skip(1))
+                        .collect::<Vec<Spring>>(),
+                    counts: target_counts.clone(),
+                },
+                prev,
+            ) + get_counts_recursive(
+                map,
+                Line {
+                    springs: Some(Spring::Operational)
+                        .into_iter()
+                        .chain(springs.into_iter().skip(1))
+                        .collect::<Vec<Spring>>(),
+                    counts: target_counts,
+                },
+                prev,
+            )
+        }
+        (_, _) => panic!("Shouldn't be able to have 'Unknown' as prev"),
+    };
+    map.insert(key, result);
+    result
+}
+
+impl Line {
+    pub fn count_line_variants(&self) -> usize {
+        get_counts_recursive(&mut HashMap::new(), self.clone(), Spring::Operational)
+    }
+
+    // Five copies of the springs joined by unknowns, and the counts repeated
+    // to match.
+    pub fn unfold(&self) -> Self {
+        let mut springs = self.springs.clone();
+        for _ in 1..5 {
+            springs.push(Spring::Unknown);
+            springs.extend_from_slice(&self.springs);
+        }
+        Self {
+            springs,
+            counts: self.counts.repeat(5),
+        }
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Line>, AocError> {
+    parse_lines(s, Line::try_from)
+}
+
+pub fn arrangements(lines: &[Line]) -> usize {
+    par_iter(lines).map(Line::count_line_variants).sum()
+}
+
+pub fn unfolded_arrangements(lines: &[Line]) -> usize {
+    let progress = Progress::new(lines.len());
+    par_iter(lines)
+        .map(|line| {
+            let count = line.unfold().count_line_variants();
+            progress.tick();
+            count
+        })
+        .sum()
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    Ok(arrangements(&parse_input(s)?))
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    Ok(unfolded_arrangements(&parse_input(s)?))
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Parsed<'a> = Vec<Line>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        12
+    }
+
+    fn parse(input: &str) -> Result<Vec<Line>, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(lines: &Self::Parsed<'_>) -> impl Answer {
+        arrangements(lines)
+    }
+
+    fn part2(lines: &Self::Parsed<'_>) -> impl Answer {
+        unfolded_arrangements(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1("??? 2,1"), Ok(0));
+        assert_eq!(part1("???? 2,1"), Ok(1));
+        assert_eq!(part1("???.### 1,1,3"), Ok(1));
+        assert_eq!(part1(".??..??...?##. 1,1,3"), Ok(4));
+        assert_eq!(part1("?#?#?#?#?#?#?#? 1,3,1,6"), Ok(1));
+        assert_eq!(part1("????.#...#... 4,1,1"), Ok(1));
+        assert_eq!(part1("????.######..#####. 1,6,5"), Ok(4));
+        assert_eq!(part1("?###???????? 3,2,1"), Ok(10));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part1("??? 1\n?x? 1").unwrap_err().to_string(),
+            "line 2: invalid spring `x`"
+        );
+        assert_eq!(
+            part2("??? 1,a").unwrap_err().to_string(),
+            "line 1: invalid count `a`"
+        );
+        assert_eq!(
+            part2("???").unwrap_err().to_string(),
+            "line 1: invalid line `???`"
+        );
+    }
+}
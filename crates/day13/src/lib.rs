@@ -0,0 +1,191 @@
+use aoc_core::{Answer, AocError, Grid, Solver};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Tile {
+    Rock,
+    Empty,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '#' => Ok(Tile::Rock),
+            '.' => Ok(Tile::Empty),
+            _ => Err(AocError::invalid("tile", &value.to_string())),
+        }
+    }
+}
+
+pub type Map = Grid<Tile>;
+
+pub fn make_maps(s: &str) -> Result<Vec<Map>, AocError> {
+    s.split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| Grid::<char>::try_from(block)?.try_map(|c| Tile::try_from(*c)))
+        .collect()
+}
+
+fn is_palindrome_at(r: &[Tile], idx: usize) -> bool {
+    let (left, right) = r.split_at(idx);
+    right.iter().zip(left.iter().rev()).all(|(a, b)| a == b)
+}
+
+fn find_possible_horiz_points(r: &[Tile]) -> Vec<usize> {
+    (1..r.len())
+        .filter(|idx| is_palindrome_at(r, *idx))
+        .collect()
+}
+
+fn calc_map_points(
+    m: &Map,
+    remove_horiz: Option<usize>,
+    remove_vert: Option<usize>,
+) -> Option<usize> {
+    let horiz_points = m
+        .rows()
+        .map(find_possible_horiz_points)
+        .fold::<Vec<usize>, _>((0..m.width()).collect::<Vec<usize>>(), |acc, val| {
+            acc.into_iter()
+                .filter(|num| val.contains(num))
+                .collect::<Vec<usize>>()
+        })
+        .into_iter()
+        .filter(|size| {
+            if let Some(remove_horiz) = remove_horiz {
+                *size != remove_horiz
+            } else {
+                true
+            }
+        })
+        .collect::<Vec<usize>>();
+    let vert_points = m
+        .transpose()
+        .rows()
+        .map(find_possible_horiz_points)
+        .fold::<Vec<usize>, _>((0..m.height()).collect::<Vec<usize>>(), |acc, val| {
+            acc.into_iter()
+                .filter(|num| val.contains(num))
+                .collect::<Vec<usize>>()
+        })
+        .into_iter()
+        .filter(|size| {
+            if let Some(remove_vert) = remove_vert {
+                *size != remove_vert
+            } else {
+                true
+            }
+        })
+        .collect::<Vec<usize>>();
+    if !horiz_points.is_empty() {
+        horiz_points.first().copied()
+    } else {
+        vert_points.first().copied().map(|p| p * 100)
+    }
+}
+
+fn get_map_variants(m: &Map) -> Vec<Map> {
+    m.positions()
+        .map(|pos| {
+            let mut map = m.clone();
+            map[pos] = match m[pos] {
+                Tile::Rock => Tile::Empty,
+                Tile::Empty => Tile::Rock,
+            };
+            map
+        })
+        .collect()
+}
+
+fn find_smudge_line(m: &Map, original_line: usize) -> Option<usize> {
+    let remove_vert = if original_line >= 100 {
+        Some(original_line / 100)
+    } else {
+        None
+    };
+    let remove_horiz = if original_line < 100 {
+        Some(original_line)
+    } else {
+        None
+    };
+    get_map_variants(m)
+        .iter()
+        .find_map(|map| calc_map_points(map, remove_horiz, remove_vert))
+}
+
+fn reflection(map: &Map) -> Result<usize, AocError> {
+    calc_map_points(map, None, None).ok_or(AocError::Missing("line of reflection"))
+}
+
+pub fn reflection_sum(maps: &[Map]) -> Result<usize, AocError> {
+    maps.iter().map(reflection).sum()
+}
+
+pub fn smudge_sum(maps: &[Map]) -> Result<usize, AocError> {
+    maps.iter()
+        .map(|map| find_smudge_line(map, reflection(map)?).ok_or(AocError::Missing("smudge")))
+        .sum()
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    reflection_sum(&make_maps(s)?)
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    smudge_sum(&make_maps(s)?)
+}
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Parsed<'a> = Vec<Map>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        13
+    }
+
+    fn parse(input: &str) -> Result<Vec<Map>, AocError> {
+        make_maps(input)
+    }
+
+    fn part1(maps: &Self::Parsed<'_>) -> impl Answer {
+        reflection_sum(maps)
+    }
+
+    fn part2(maps: &Self::Parsed<'_>) -> impl Answer {
+        smudge_sum(maps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_horiz_points() {
+        assert_eq!(
+            find_possible_horiz_points(
+                &"#.##..##."
+                    .chars()
+                    .map(|c| Tile::try_from(c).unwrap())
+                    .collect::<Vec<_>>()
+            ),
+            vec![5, 7]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(part1("#.\n.x").unwrap_err().to_string(), "invalid tile `x`");
+        assert_eq!(
+            part1("#.\n.").unwrap_err().to_string(),
+            "line 2: expected 2 columns, found 1"
+        );
+        assert_eq!(
+            part2("#.#\n.##").unwrap_err().to_string(),
+            "missing line of reflection"
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use aoc_core::{Answer, AocError, Grid, Solver};
+use aoc_viz::{render_grid, Frame, OnFrame, Palette, Rgb, RgbImage, SCALE};
+use std::{collections::HashMap, fmt::Debug, rc::Rc};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Tile {
+    Empty,
+    Flat,
+    Round,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Empty),
+            '#' => Ok(Self::Flat),
+            'O' => Ok(Self::Round),
+            _ => Err(AocError::invalid("tile", &value.to_string())),
+        }
+    }
+}
+
+impl From<Tile> for char {
+    fn from(value: Tile) -> Self {
+        match value {
+            Tile::Empty => '.',
+            Tile::Flat => '#',
+            Tile::Round => 'O',
+        }
+    }
+}
+
+pub type Rows = Rc<Grid<Tile>>;
+type Tilt = fn(&mut Map);
+
+#[derive(Eq, Clone)]
+pub struct Map {
+    rows: Rows,
+    compressed: usize,
+    compressed_cache: Vec<Rc<[Tile]>>,
+    cache: HashMap<usize, (Rows, usize)>,
+    frames: Option<Vec<Rows>>,
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.compressed == other.compressed
+            || self.compressed_cache[self.compressed] == other.compressed_cache[other.compressed]
+    }
+}
+
+impl Debug for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.rows)
+    }
+}
+
+impl TryFrom<&str> for Map {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let rows = Grid::<char>::try_from(value)?.try_map(|c| Tile::try_from(*c))?;
+        let compressed_cache = vec![rows.cells().into()];
+        Ok(Self {
+            rows: rows.into(),
+            compressed: 0,
+            compressed_cache,
+            cache: HashMap::default(),
+            frames: None,
+        })
+    }
+}
+
+impl Map {
+    fn update_compression(&mut self) {
+        let compressed: Rc<[Tile]> = self.rows.cells().into();
+        if let Some(pos) = self.compressed_cache.iter().position(|e| e == &compressed) {
+            self.compressed = pos;
+        } else {
+            self.compressed_cache.push(compressed);
+            self.compressed = self.compressed_cache.len() - 1;
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn tilt_north(&mut self) {
+        let mut rows = (*self.rows).clone();
+        for x in 0..rows.width() {
+            for y in 0..rows.height() {
+                if rows[(x, y)] == Tile::Round {
+                    let mut new_y = y;
+                    for check_y in (0..y).rev() {
+                        if rows[(x, check_y)] == Tile::Empty {
+                            new_y = check_y;
+                        } else {
+                            break;
+                        }
+                    }
+                    if y != new_y {
+                        rows[(x, new_y)] = Tile::Round;
+                        rows[(x, y)] = Tile::Empty;
+                    }
+                }
+            }
+        }
+        self.rows = Rc::from(rows);
+        self.update_compression();
+        self.capture();
+    }
+
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn tilt_south(&mut self) {
+        let mut rows = (*self.rows).clone();
+        for x in 0..rows.width() {
+            for y in (0..rows.height() - 1).rev() {
+                if rows[(x, y)] == Tile::Round {
+                    let new_y = (y + 1..rows.height())
+                        .take_while(|check_y| rows[(x, *check_y)] == Tile::Empty)
+                        .last()
+                        .unwrap_or(y);
+                    if y != new_y {
+                        rows[(x, new_y)] = Tile::Round;
+                        rows[(x, y)] = Tile::Empty;
+                    }
+                }
+            }
+        }
+        self.rows = Rc::from(rows);
+        self.update_compression();
+        self.capture();
+    }
+
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn tilt_west(&mut self) {
+        let mut rows = (*self.rows).clone();
+        for y in 0..rows.height() {
+            for x in 0..rows.width() {
+                if rows[(x, y)] == Tile::Round {
+                    let mut new_x = x;
+                    for check_x in (0..x).rev() {
+                        if rows[(check_x, y)] == Tile::Empty {
+                            new_x = check_x;
+                        } else {
+                            break;
+                        }
+                    }
+                    if x != new_x {
+                        rows[(new_x, y)] = Tile::Round;
+                        rows[(x, y)] = Tile::Empty;
+                    }
+                }
+            }
+        }
+        self.rows = Rc::from(rows);
+        self.update_compression();
+        self.capture();
+    }
+
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn tilt_east(&mut self) {
+        let mut rows = (*self.rows).clone();
+        for y in 0..rows.height() {
+            for x in (0..rows.width() - 1).rev() {
+                if rows[(x, y)] == Tile::Round {
+                    let new_x = (x + 1..rows.width())
+                        .take_while(|check_x| rows[(*check_x, y)] == Tile::Empty)
+                        .last()
+                        .unwrap_or(x);
+                    if x != new_x {
+                        rows[(new_x, y)] = Tile::Round;
+                        rows[(x, y)] = Tile::Empty;
+                    }
+                }
+            }
+        }
+        self.rows = Rc::from(rows);
+        self.update_compression();
+        self.capture();
+    }
+
+    // Records the board after every tilt while frames are being captured.
+    fn capture(&mut self) {
+        if let Some(frames) = &mut self.frames {
+            frames.push(self.rows.clone());
+        }
+    }
+
+    fn rotate(&mut self) {
+        // A cache hit would skip the tilts, and with them the frames.
+        if let (None, Some(cached_row)) = (&self.frames, self.cache.get(&self.compressed)) {
+            self.rows = cached_row.0.clone();
+            self.compressed = cached_row.1;
+            return;
+        }
+        let old = self.compressed;
+
+        self.tilt_north();
+        self.tilt_west();
+        self.tilt_south();
+        self.tilt_east();
+
+        self.cache.insert(old, (self.rows.clone(), self.compressed));
+    }
+
+    fn compute_load(&self) -> usize {
+        self.rows
+            .rows()
+            .rev()
+            .enumerate()
+            .map(|(y, line)| (y + 1) * line.iter().filter(|t| **t == Tile::Round).count())
+            .sum()
+    }
+}
+
+impl Map {
+    pub fn grid(&self) -> &Grid<Tile> {
+        &self.rows
+    }
+}
+
+// Every tilt of the first `cycles` spin cycles, followed by one full period of
+// the loop the board settles into if that starts later.
+pub fn spin_frames(map: &Map, cycles: usize) -> Vec<Rows> {
+    let mut map = map.clone();
+    map.frames = Some(vec![map.rows.clone()]);
+    let mut frames = vec![];
+    let mut seen = HashMap::from([(map.compressed, 0)]);
+    let mut cycle = 0;
+    let start = loop {
+        if cycle == cycles {
+            frames = map.frames.take().unwrap_or_default();
+        }
+        map.rotate();
+        cycle += 1;
+        if let Some(start) = seen.insert(map.compressed, cycle) {
+            break start;
+        }
+    };
+    frames.extend(map.frames.take().unwrap_or_default());
+    if cycle > cycles {
+        map.frames = Some(vec![]);
+        for _ in start..cycle {
+            map.rotate();
+        }
+        frames.extend(map.frames.take().unwrap_or_default());
+    }
+    frames
+}
+
+// Spin cycles from the start until the board comes back round to one it has
+// been in before, one frame per tilt.
+pub fn simulate(map: &Map, on_frame: OnFrame) {
+    let mut map = map.clone();
+    let mut seen = HashMap::from([(map.compressed, 0)]);
+    let load = |map: &Map| map.compute_load();
+    if on_frame(Frame::new(
+        map.rows.to_string(),
+        format!("Start, load {}", load(&map)),
+    ))
+    .is_break()
+    {
+        return;
+    }
+    let tilts: [(&str, Tilt); 4] = [
+        ("north", Map::tilt_north),
+        ("west", Map::tilt_west),
+        ("south", Map::tilt_south),
+        ("east", Map::tilt_east),
+    ];
+    for cycle in 1.. {
+        for (name, tilt) in tilts {
+            tilt(&mut map);
+            let caption = format!("Cycle {}, tilted {}, load {}", cycle, name, load(&map));
+            if on_frame(Frame::new(map.rows.to_string(), caption)).is_break() {
+                return;
+            }
+        }
+        if let Some(start) = seen.insert(map.compressed, cycle) {
+            let caption = format!("Cycle {} repeats cycle {}", cycle, start);
+            let _ = on_frame(Frame::new(map.rows.to_string(), caption));
+            return;
+        }
+    }
+}
+
+pub fn palette() -> Palette {
+    [Tile::Empty, Tile::Flat, Tile::Round]
+        .iter()
+        .fold(Palette::new(tile_color(&Tile::Empty)), |palette, tile| {
+            palette.with(&char::from(*tile).to_string(), tile_color(tile))
+        })
+}
+
+fn tile_color(tile: &Tile) -> Rgb {
+    match tile {
+        Tile::Empty => [24, 24, 32],
+        Tile::Flat => [110, 110, 120],
+        Tile::Round => [230, 200, 120],
+    }
+}
+
+pub fn render_frames(map: &Map, cycles: usize) -> Vec<RgbImage> {
+    spin_frames(map, cycles)
+        .iter()
+        .map(|rows| render_grid(rows, SCALE, tile_color))
+        .collect()
+}
+
+// The board part 1 is scored on, after rolling every rock north.
+pub fn render_image(map: &Map) -> RgbImage {
+    let mut map = map.clone();
+    map.tilt_north();
+    render_grid(map.grid(), SCALE, tile_color)
+}
+
+pub fn north_load(map: &Map) -> usize {
+    let mut map = map.clone();
+    map.tilt_north();
+    map.compute_load()
+}
+
+pub fn spun_load(map: &Map) -> usize {
+    let mut map = map.clone();
+    for _ in 0..1_000_000_000 {
+        map.rotate();
+    }
+    map.compute_load()
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    Ok(north_load(&Map::try_from(s)?))
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    Ok(spun_load(&Map::try_from(s)?))
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Parsed<'a> = Map;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        14
+    }
+
+    fn parse(input: &str) -> Result<Map, AocError> {
+        Map::try_from(input)
+    }
+
+    fn part1(map: &Self::Parsed<'_>) -> impl Answer {
+        north_load(map)
+    }
+
+    fn part2(map: &Self::Parsed<'_>) -> impl Answer {
+        spun_load(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day14/example.txt");
+
+    fn parse(s: &str) -> Map {
+        Map::try_from(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(part1("O.\n.x").unwrap_err().to_string(), "invalid tile `x`");
+        assert_eq!(
+            part2("O.#\n.").unwrap_err().to_string(),
+            "line 2: expected 3 columns, found 1"
+        );
+    }
+
+    #[test]
+    fn test_render_image() {
+        let image = render_image(&parse(TEST_INPUT));
+        assert_eq!(image.dimensions(), (40, 40));
+        assert_eq!(image.get_pixel(0, 0).0, [230, 200, 120]);
+        assert_eq!(image.get_pixel(4 * 5, 0).0, [110, 110, 120]);
+    }
+
+    #[test]
+    fn test_spin_frames() {
+        let frames = spin_frames(&parse(TEST_INPUT), 1);
+        assert_eq!(frames.len(), 1 + 4 + 7 * 4);
+        let mut map = parse(TEST_INPUT);
+        map.tilt_north();
+        assert_eq!(frames[1], map.rows);
+        map.tilt_west();
+        map.tilt_south();
+        map.tilt_east();
+        assert_eq!(frames[4], map.rows);
+        // The example settles into a loop of 7 cycles after the third.
+        let all = spin_frames(&parse(TEST_INPUT), 20);
+        assert_eq!(all.len(), 1 + 10 * 4);
+        assert_eq!(frames[5..], all[13..]);
+    }
+
+    #[test]
+    fn test_simulate() {
+        let map = parse(TEST_INPUT);
+        let frames = aoc_viz::take_frames(usize::MAX, |on_frame| simulate(&map, on_frame));
+        assert_eq!(frames.len(), 1 + 10 * 4 + 1);
+        assert_eq!(frames[0].text, TEST_INPUT.trim_end());
+        assert_eq!(frames[1].caption, "Cycle 1, tilted north, load 136");
+        assert_eq!(frames[41].caption, "Cycle 10 repeats cycle 3");
+        assert_eq!(palette().color('O'), tile_color(&Tile::Round));
+    }
+
+    #[test]
+    fn test_tilt_north() {
+        let mut map = parse(TEST_INPUT);
+        map.tilt_north();
+        insta::assert_debug_snapshot!(map);
+    }
+
+    #[test]
+    fn test_tilt_west() {
+        let mut map = parse(TEST_INPUT);
+        map.tilt_west();
+        insta::assert_debug_snapshot!(map);
+    }
+
+    #[test]
+    fn test_tilt_south() {
+        let mut map = parse(TEST_INPUT);
+        map.tilt_south();
+        insta::assert_debug_snapshot!(map);
+    }
+
+    #[test]
+    fn test_tilt_east() {
+        let mut map = parse(TEST_INPUT);
+        map.tilt_east();
+        insta::assert_debug_snapshot!(map);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut map = parse(TEST_INPUT);
+        map.rotate();
+        insta::assert_debug_snapshot!(map);
+    }
+}
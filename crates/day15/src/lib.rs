@@ -0,0 +1,171 @@
+use aoc_core::{error::parse_number, Answer, AocError, Solver};
+use std::collections::VecDeque;
+
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Lens {
+    label: String,
+    value: u8,
+}
+
+pub enum Operation {
+    Insert(Lens),
+    Remove(String),
+}
+
+// A step of the sequence as written, which part 1 hashes, and what it does.
+pub struct Step<'a> {
+    text: &'a str,
+    operation: Operation,
+}
+
+impl TryFrom<&str> for Operation {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if let Some((label, num)) = value.split_once('=') {
+            Ok(Self::Insert(Lens {
+                label: String::from(label),
+                value: parse_number("focal length", num)?,
+            }))
+        } else if let Some(label) = value.strip_suffix('-') {
+            Ok(Self::Remove(String::from(label)))
+        } else {
+            Err(AocError::invalid("step", value))
+        }
+    }
+}
+
+pub struct HashMap {
+    boxes: Vec<VecDeque<Lens>>,
+}
+
+impl Default for HashMap {
+    fn default() -> Self {
+        let mut result = Self {
+            boxes: Vec::with_capacity(256),
+        };
+        for _ in 0..256 {
+            result.boxes.push(VecDeque::default());
+        }
+        result
+    }
+}
+
+impl HashMap {
+    pub fn insert(&mut self, lens: Lens) {
+        let h = hash(&lens.label);
+        if let Some(old_lens) = self.boxes[h].iter_mut().find(|l| l.label == lens.label) {
+            old_lens.value = lens.value;
+        } else {
+            self.boxes[h].push_back(lens);
+        }
+    }
+
+    pub fn remove(&mut self, label: &str) {
+        let h = hash(label);
+        self.boxes[h].retain(|l| l.label != label);
+    }
+}
+
+pub fn hash(s: &str) -> usize {
+    s.as_bytes()
+        .iter()
+        .copied()
+        .fold(0, |acc, b| ((acc + b as usize) * 17) % 256)
+}
+
+fn sequence(s: &str) -> Result<&str, AocError> {
+    s.lines()
+        .next()
+        .ok_or(AocError::Missing("initialization sequence"))
+}
+
+pub fn parse_steps(s: &str) -> Result<Vec<Step<'_>>, AocError> {
+    sequence(s)?
+        .split(',')
+        .map(|text| {
+            Ok(Step {
+                text,
+                operation: Operation::try_from(text)?,
+            })
+        })
+        .collect()
+}
+
+pub fn hash_sum(steps: &[Step]) -> usize {
+    steps.iter().map(|step| hash(step.text)).sum()
+}
+
+pub fn focusing_power(steps: &[Step]) -> usize {
+    let mut map = HashMap::default();
+    for step in steps {
+        match &step.operation {
+            Operation::Insert(lens) => map.insert(lens.clone()),
+            Operation::Remove(label) => map.remove(label),
+        }
+    }
+    let mut total = 0;
+    for (box_number, bx) in map.boxes.iter().enumerate() {
+        for (slot_number, lens) in bx.iter().enumerate() {
+            total += (1 + box_number) * (1 + slot_number) * lens.value as usize;
+        }
+    }
+    total
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    Ok(hash_sum(&parse_steps(s)?))
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    Ok(focusing_power(&parse_steps(s)?))
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Parsed<'a> = Vec<Step<'a>>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        15
+    }
+
+    fn parse(input: &str) -> Result<Vec<Step<'_>>, AocError> {
+        parse_steps(input)
+    }
+
+    fn part1(steps: &Self::Parsed<'_>) -> impl Answer {
+        hash_sum(steps)
+    }
+
+    fn part2(steps: &Self::Parsed<'_>) -> impl Answer {
+        focusing_power(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("HASH"), 52);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part2("rn=1,cm").unwrap_err().to_string(),
+            "invalid step `cm`"
+        );
+        assert_eq!(
+            part2("rn=x").unwrap_err().to_string(),
+            "invalid focal length `x`"
+        );
+        assert_eq!(
+            part1("").unwrap_err().to_string(),
+            "missing initialization sequence"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+// One invocation per starting laser. A state is `cell * 4 + direction`, so a
+// cell's four states share a nibble of the visited bitmap and counting the
+// non-empty nibbles counts the energized tiles.
+
+struct Params {
+    width: u32,
+    height: u32,
+    starts: u32,
+    // Visited words and stack slots each invocation owns.
+    words: u32,
+    stack: u32,
+}
+
+const UP: u32 = 0u;
+const DOWN: u32 = 1u;
+const LEFT: u32 = 2u;
+const RIGHT: u32 = 3u;
+const NONE: u32 = 0xffffffffu;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> grid: array<u32>;
+@group(0) @binding(2) var<storage, read> starts: array<u32>;
+@group(0) @binding(3) var<storage, read_write> visited: array<u32>;
+@group(0) @binding(4) var<storage, read_write> stack: array<u32>;
+@group(0) @binding(5) var<storage, read_write> energized: array<u32>;
+
+// The one or two directions a beam leaves `tile` in, NONE for no second one.
+fn outgoing(tile: u32, dir: u32) -> vec2<u32> {
+    switch tile {
+        case 1u: {
+            var turns = array(RIGHT, LEFT, DOWN, UP);
+            return vec2(turns[dir], NONE);
+        }
+        case 2u: {
+            var turns = array(LEFT, RIGHT, UP, DOWN);
+            return vec2(turns[dir], NONE);
+        }
+        case 3u: {
+            if dir == UP || dir == DOWN { return vec2(LEFT, RIGHT); }
+            return vec2(dir, NONE);
+        }
+        case 4u: {
+            if dir == LEFT || dir == RIGHT { return vec2(UP, DOWN); }
+            return vec2(dir, NONE);
+        }
+        default: { return vec2(dir, NONE); }
+    }
+}
+
+// The neighbouring cell in `dir`, or NONE past the edge.
+fn neighbour(cell: u32, dir: u32) -> u32 {
+    let x = cell % params.width;
+    let y = cell / params.width;
+    switch dir {
+        case 0u: { if y > 0u { return cell - params.width; } }
+        case 1u: { if y + 1u < params.height { return cell + params.width; } }
+        case 2u: { if x > 0u { return cell - 1u; } }
+        default: { if x + 1u < params.width { return cell + 1u; } }
+    }
+    return NONE;
+}
+
+// Marks `state` visited, returning whether it was new.
+fn mark(base: u32, state: u32) -> bool {
+    let word = base + state / 32u;
+    let bit = 1u << (state % 32u);
+    let old = visited[word];
+    visited[word] = old | bit;
+    return (old & bit) == 0u;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let start = id.x;
+    if start >= params.starts {
+        return;
+    }
+    let base = start * params.words;
+    let stack_base = start * params.stack;
+    var sp = 0u;
+    var state = starts[start];
+    mark(base, state);
+    loop {
+        // Follow one outgoing beam and stack the other, so straight runs
+        // never touch the stack.
+        let out = outgoing(grid[state / 4u], state % 4u);
+        var next = NONE;
+        for (var i = 0u; i < 2u; i++) {
+            if out[i] == NONE {
+                continue;
+            }
+            let cell = neighbour(state / 4u, out[i]);
+            if cell == NONE || !mark(base, cell * 4u + out[i]) {
+                continue;
+            }
+            if next == NONE {
+                next = cell * 4u + out[i];
+            } else {
+                stack[stack_base + sp] = cell * 4u + out[i];
+                sp++;
+            }
+        }
+        if next != NONE {
+            state = next;
+            continue;
+        }
+        if sp == 0u {
+            break;
+        }
+        sp--;
+        state = stack[stack_base + sp];
+    }
+    var count = 0u;
+    for (var i = 0u; i < params.words; i++) {
+        let v = visited[base + i];
+        count += countOneBits((v | (v >> 1u) | (v >> 2u) | (v >> 3u)) & 0x11111111u);
+    }
+    energized[start] = count;
+}
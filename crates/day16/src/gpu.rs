@@ -0,0 +1,201 @@
+use crate::{Laser, Tile};
+use aoc_core::Direction;
+use wgpu::util::DeviceExt;
+
+const WORKGROUP: u32 = 64;
+
+fn tile_code(tile: Tile) -> u32 {
+    match tile {
+        Tile::Empty => 0,
+        Tile::FMirror => 1,
+        Tile::BMirror => 2,
+        Tile::HSplitter => 3,
+        Tile::VSplitter => 4,
+    }
+}
+
+fn direction_code(direction: Direction) -> u32 {
+    match direction {
+        Direction::Up => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Right => 3,
+    }
+}
+
+fn bytes(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.to_le_bytes()).collect()
+}
+
+struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    limits: wgpu::Limits,
+}
+
+impl Gpu {
+    fn new() -> Result<Self, String> {
+        let instance = wgpu::Instance::default();
+        let adapter = pollster::block_on(instance.request_adapter(&Default::default()))
+            .map_err(|err| err.to_string())?;
+        let limits = adapter.limits();
+        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+            label: Some("day16"),
+            required_limits: limits.clone(),
+            ..Default::default()
+        }))
+        .map_err(|err| err.to_string())?;
+        Ok(Self {
+            device,
+            queue,
+            limits,
+        })
+    }
+
+    fn storage(&self, label: &str, words: &[u32], read_only: bool) -> wgpu::Buffer {
+        let usage = match read_only {
+            true => wgpu::BufferUsages::STORAGE,
+            false => wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        };
+        self.device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(label),
+                contents: &bytes(words),
+                usage,
+            })
+    }
+}
+
+// How many tiles each laser in `starts` energizes, all fired at once on the
+// GPU. Errors when there is no adapter or the grid needs more memory than it
+// allows, so callers can fall back to the CPU.
+pub fn energized(grid: &[Vec<Tile>], starts: &[Laser]) -> Result<Vec<usize>, String> {
+    let height = grid.len() as u32;
+    let width = grid.first().map_or(0, Vec::len) as u32;
+    if starts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let cells: Vec<u32> = grid.iter().flatten().map(|&tile| tile_code(tile)).collect();
+    let splitters = grid
+        .iter()
+        .flatten()
+        .filter(|tile| matches!(tile, Tile::HSplitter | Tile::VSplitter))
+        .count() as u32;
+    let states: Vec<u32> = starts
+        .iter()
+        .map(|laser| {
+            (laser.y as u32 * width + laser.x as u32) * 4 + direction_code(laser.direction)
+        })
+        .collect();
+    let words = (width * height).div_ceil(8);
+    // A beam only stacks a state when a splitter sends it two ways, and each
+    // splitter does that for at most two of its incoming states.
+    let stack = 2 * splitters + 1;
+    let count = starts.len() as u32;
+
+    let gpu = Gpu::new()?;
+    let largest = u64::from(count) * u64::from(words.max(stack)) * 4;
+    if largest > gpu.limits.max_storage_buffer_binding_size {
+        return Err(format!(
+            "{} bytes of beam state is over the adapter's {} byte limit",
+            largest, gpu.limits.max_storage_buffer_binding_size
+        ));
+    }
+    let device = &gpu.device;
+    let params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("params"),
+        contents: &bytes(&[width, height, count, words, stack]),
+        usage: wgpu::BufferUsages::UNIFORM,
+    });
+    let grid = gpu.storage("grid", &cells, true);
+    let starts = gpu.storage("starts", &states, true);
+    let visited = gpu.storage("visited", &vec![0; (count * words) as usize], false);
+    let stack = gpu.storage("stack", &vec![0; (count * stack) as usize], false);
+    let energized = gpu.storage("energized", &vec![0; count as usize], false);
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("readback"),
+        size: u64::from(count) * 4,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("beams"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("beams.wgsl").into()),
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("beams"),
+        layout: None,
+        module: &module,
+        entry_point: Some("main"),
+        compilation_options: Default::default(),
+        cache: None,
+    });
+    let entries: Vec<wgpu::BindGroupEntry> =
+        [&params, &grid, &starts, &visited, &stack, &energized]
+            .into_iter()
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("beams"),
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &entries,
+    });
+
+    let mut encoder = device.create_command_encoder(&Default::default());
+    {
+        let mut pass = encoder.begin_compute_pass(&Default::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(count.div_ceil(WORKGROUP), 1, 1);
+    }
+    encoder.copy_buffer_to_buffer(&energized, 0, &readback, 0, None);
+    gpu.queue.submit([encoder.finish()]);
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    readback.map_async(wgpu::MapMode::Read, .., move |result| {
+        let _ = tx.send(result);
+    });
+    device
+        .poll(wgpu::PollType::wait_indefinitely())
+        .map_err(|err| err.to_string())?;
+    rx.recv()
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())?;
+    let view = readback
+        .get_mapped_range(..)
+        .map_err(|err| err.to_string())?;
+    Ok(view
+        .chunks_exact(4)
+        .map(|word| u32::from_le_bytes(word.try_into().unwrap()) as usize)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{gpu::*, *};
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day16/example.txt");
+
+    #[test]
+    fn test_matches_cpu() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        let starts = start_lasers(grid[0].len(), grid.len());
+        let counts = match energized(&grid, &starts) {
+            Ok(counts) => counts,
+            Err(err) => {
+                eprintln!("skipping, no GPU: {}", err);
+                return;
+            }
+        };
+        let cpu: Vec<usize> = starts
+            .iter()
+            .map(|&laser| fire_laser(&grid, laser))
+            .collect();
+        assert_eq!(counts, cpu);
+    }
+}
@@ -0,0 +1,440 @@
+#[cfg(feature = "gpu")]
+pub mod gpu;
+
+use aoc_core::{
+    geometry::step, parallel::*, progress::Progress, Answer, AocError, Direction, Grid, Solver,
+};
+use aoc_viz::{render_text, Frame, OnFrame, Palette, RgbImage, SCALE};
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Write},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    FMirror,
+    BMirror,
+    HSplitter,
+    VSplitter,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Laser {
+    x: usize,
+    y: usize,
+    direction: Direction,
+}
+
+impl From<Tile> for char {
+    fn from(value: Tile) -> Self {
+        match value {
+            Tile::Empty => '.',
+            Tile::FMirror => '/',
+            Tile::BMirror => '\\',
+            Tile::HSplitter => '-',
+            Tile::VSplitter => '|',
+        }
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Tile::Empty),
+            '/' => Ok(Tile::FMirror),
+            '\\' => Ok(Tile::BMirror),
+            '-' => Ok(Tile::HSplitter),
+            '|' => Ok(Tile::VSplitter),
+            _ => Err(AocError::invalid("tile", &value.to_string())),
+        }
+    }
+}
+
+impl Debug for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char((*self).into())
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Vec<Tile>>, AocError> {
+    let grid = Grid::<char>::try_from(s)?.try_map(|c| Tile::try_from(*c))?;
+    if grid.width() == 0 {
+        return Err(AocError::Missing("contraption"));
+    }
+    Ok(grid.rows().map(<[Tile]>::to_vec).collect())
+}
+
+fn next_tile(width: usize, height: usize, laser: &Laser) -> Option<Laser> {
+    let (x, y) = step((laser.x, laser.y), laser.direction, width, height)?;
+    Some(Laser { x, y, ..*laser })
+}
+
+fn new_directions(tile: Tile, direction: Direction) -> Vec<Direction> {
+    match (tile, direction) {
+        (Tile::FMirror, Direction::Up) => vec![Direction::Right],
+        (Tile::FMirror, Direction::Down) => vec![Direction::Left],
+        (Tile::FMirror, Direction::Left) => vec![Direction::Down],
+        (Tile::FMirror, Direction::Right) => vec![Direction::Up],
+        (Tile::BMirror, Direction::Up) => vec![Direction::Left],
+        (Tile::BMirror, Direction::Down) => vec![Direction::Right],
+        (Tile::BMirror, Direction::Left) => vec![Direction::Up],
+        (Tile::BMirror, Direction::Right) => vec![Direction::Down],
+        (Tile::HSplitter, Direction::Up | Direction::Down) => {
+            vec![Direction::Left, Direction::Right]
+        }
+        (Tile::VSplitter, Direction::Left | Direction::Right) => {
+            vec![Direction::Up, Direction::Down]
+        }
+        _ => vec![direction],
+    }
+}
+
+fn step_frontier(grid: &[Vec<Tile>], frontier: &[Laser], seen: &mut HashSet<Laser>) -> Vec<Laser> {
+    let height = grid.len();
+    let width = grid[0].len();
+    let mut next_frontier = Vec::new();
+    for laser in frontier {
+        if !seen.insert(*laser) {
+            continue;
+        }
+        for new_direction in new_directions(grid[laser.y][laser.x], laser.direction) {
+            if let Some(laser) = next_tile(
+                width,
+                height,
+                &Laser {
+                    direction: new_direction,
+                    ..*laser
+                },
+            ) {
+                next_frontier.push(laser);
+            }
+        }
+    }
+    next_frontier
+}
+
+fn energized_tiles(grid: &[Vec<Tile>], seen: &HashSet<Laser>) -> Vec<Vec<bool>> {
+    let mut result: Vec<Vec<bool>> = grid
+        .iter()
+        .map(|line| line.iter().map(|_| false).collect())
+        .collect();
+    for laser in seen {
+        result[laser.y][laser.x] = true;
+    }
+    result
+}
+
+fn fire_laser(grid: &[Vec<Tile>], start_laser: Laser) -> usize {
+    let mut seen = HashSet::new();
+    let mut frontier = vec![start_laser];
+    while !frontier.is_empty() {
+        frontier = step_frontier(grid, &frontier, &mut seen);
+    }
+    energized_tiles(grid, &seen)
+        .into_iter()
+        .flatten()
+        .filter(|e| *e)
+        .count()
+}
+
+fn render_frontier(grid: &[Vec<Tile>], seen: &HashSet<Laser>, frontier: &[Laser]) -> String {
+    let mut lines: Vec<Vec<char>> = grid
+        .iter()
+        .zip(energized_tiles(grid, seen))
+        .map(|(line, energized)| {
+            line.iter()
+                .zip(energized)
+                .map(|(tile, energized)| if energized { '#' } else { char::from(*tile) })
+                .collect()
+        })
+        .collect();
+    for laser in frontier {
+        lines[laser.y][laser.x] = laser.direction.arrow();
+    }
+    lines
+        .into_iter()
+        .map(|line| line.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// The part 1 beam spreading through the contraption, one frame per step.
+pub fn simulate(grid: &[Vec<Tile>], on_frame: OnFrame) {
+    let mut seen = HashSet::new();
+    let mut frontier = vec![Laser {
+        x: 0,
+        y: 0,
+        direction: Direction::Right,
+    }];
+    let mut generation = 0;
+    while !frontier.is_empty() {
+        let frame = Frame::new(
+            render_frontier(grid, &seen, &frontier),
+            format!("Generation {}: {} beams", generation, frontier.len()),
+        );
+        if on_frame(frame).is_break() {
+            return;
+        }
+        frontier = step_frontier(grid, &frontier, &mut seen);
+        generation += 1;
+    }
+}
+
+// Tiles the part 1 beam energizes, drawn as `#` over the contraption.
+pub fn render_energized(grid: &[Vec<Tile>]) -> String {
+    let mut seen = HashSet::new();
+    let mut frontier = vec![Laser {
+        x: 0,
+        y: 0,
+        direction: Direction::Right,
+    }];
+    while !frontier.is_empty() {
+        frontier = step_frontier(grid, &frontier, &mut seen);
+    }
+    render_frontier(grid, &seen, &[])
+}
+
+pub fn palette() -> Palette {
+    Palette::new([24, 24, 32])
+        .with("/\\-|", [110, 110, 120])
+        .with("#", [255, 220, 90])
+        .with("^v<>", [255, 120, 60])
+}
+
+pub fn render_image(grid: &[Vec<Tile>]) -> RgbImage {
+    render_text(&render_energized(grid), SCALE, &palette())
+}
+
+pub fn corner_energized(grid: &[Vec<Tile>]) -> usize {
+    let start_laser = Laser {
+        x: 0,
+        y: 0,
+        direction: Direction::Right,
+    };
+    fire_laser(grid, start_laser)
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    Ok(corner_energized(&parse_input(s)?))
+}
+
+fn start_lasers(width: usize, height: usize) -> Vec<Laser> {
+    let left_side = (0..height).map(|y| Laser {
+        x: 0,
+        y,
+        direction: Direction::Right,
+    });
+    let right_side = (0..height).map(|y| Laser {
+        x: width - 1,
+        y,
+        direction: Direction::Left,
+    });
+    let top_side = (0..width).map(|x| Laser {
+        x,
+        y: 0,
+        direction: Direction::Down,
+    });
+    let bottom_side = (0..width).map(|x| Laser {
+        x,
+        y: height - 1,
+        direction: Direction::Up,
+    });
+    left_side
+        .chain(right_side)
+        .chain(top_side)
+        .chain(bottom_side)
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct StartResult {
+    laser: Laser,
+    energized: usize,
+}
+
+impl StartResult {
+    fn edge(&self) -> &'static str {
+        match self.laser.direction {
+            Direction::Right => "left",
+            Direction::Left => "right",
+            Direction::Down => "top",
+            Direction::Up => "bottom",
+        }
+    }
+
+    fn direction(&self) -> &'static str {
+        match self.laser.direction {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+        }
+    }
+}
+
+pub fn start_results(grid: &[Vec<Tile>]) -> Vec<StartResult> {
+    let height = grid.len();
+    let width = grid[0].len();
+    let lasers = start_lasers(width, height);
+    let progress = Progress::new(lasers.len());
+    par_iter(lasers)
+        .map(|laser| {
+            let energized = fire_laser(grid, laser);
+            progress.tick();
+            StartResult { laser, energized }
+        })
+        .collect()
+}
+
+pub fn most_energized(grid: &[Vec<Tile>]) -> usize {
+    start_results(grid)
+        .into_iter()
+        .map(|result| result.energized)
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    Ok(most_energized(&parse_input(s)?))
+}
+
+#[cfg(feature = "gpu")]
+pub fn part2_gpu(s: &str) -> Result<usize, String> {
+    let grid = parse_input(s).map_err(|err| err.to_string())?;
+    let counts = gpu::energized(&grid, &start_lasers(grid[0].len(), grid.len()))?;
+    Ok(counts.into_iter().max().unwrap_or(0))
+}
+
+pub fn report_csv(results: &[StartResult]) -> String {
+    let max = results.iter().map(|r| r.energized).max().unwrap_or(0);
+    let mut out = String::from("edge,x,y,direction,energized,best\n");
+    for result in results {
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            result.edge(),
+            result.laser.x,
+            result.laser.y,
+            result.direction(),
+            result.energized,
+            result.energized == max
+        ));
+    }
+    out
+}
+
+pub fn report_json(results: &[StartResult]) -> String {
+    let max = results.iter().map(|r| r.energized).max().unwrap_or(0);
+    let entries: Vec<String> = results
+        .iter()
+        .map(|result| {
+            format!(
+                "  {{\"edge\": \"{}\", \"x\": {}, \"y\": {}, \"direction\": \"{}\", \"energized\": {}, \"best\": {}}}",
+                result.edge(),
+                result.laser.x,
+                result.laser.y,
+                result.direction(),
+                result.energized,
+                result.energized == max
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Parsed<'a> = Vec<Vec<Tile>>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        16
+    }
+
+    fn parse(input: &str) -> Result<Vec<Vec<Tile>>, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(grid: &Self::Parsed<'_>) -> impl Answer {
+        corner_energized(grid)
+    }
+
+    fn part2(grid: &Self::Parsed<'_>) -> impl Answer {
+        most_energized(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day16/example.txt");
+
+    #[test]
+    fn test_render_energized() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        let energized = render_energized(&grid);
+        assert_eq!(energized.matches('#').count(), 46);
+        insta::assert_snapshot!(energized);
+        assert_eq!(render_image(&grid).dimensions(), (40, 40));
+    }
+
+    #[test]
+    fn test_simulate() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        let frames = aoc_viz::take_frames(usize::MAX, |on_frame| simulate(&grid, on_frame));
+        assert_eq!(frames[0].caption, "Generation 0: 1 beams");
+        assert!(frames[0].text.starts_with(">|"));
+        let last = &frames[frames.len() - 1].text;
+        assert_eq!(last.matches(['#', '^', 'v', '<', '>']).count(), 46);
+        assert_eq!(
+            aoc_viz::take_frames(2, |on_frame| simulate(&grid, on_frame)).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_report_csv() {
+        let results = start_results(&parse_input(TEST_INPUT).unwrap());
+        assert_eq!(results.len(), 40);
+        let report = report_csv(&results);
+        let best: Vec<&str> = report.lines().filter(|l| l.ends_with(",true")).collect();
+        assert_eq!(best, vec!["top,3,0,down,51,true"]);
+    }
+
+    #[test]
+    fn test_report_json() {
+        let results = start_results(&parse_input("..\n..").unwrap());
+        assert_eq!(
+            report_json(&results[..1]),
+            "[\n  {\"edge\": \"left\", \"x\": 0, \"y\": 0, \"direction\": \"right\", \"energized\": 2, \"best\": true}\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_step_frontier_splits() {
+        let grid = parse_input("...\n.|.\n...").unwrap();
+        let mut seen = HashSet::new();
+        let frontier = vec![Laser {
+            x: 0,
+            y: 1,
+            direction: Direction::Right,
+        }];
+        let frontier = step_frontier(&grid, &frontier, &mut seen);
+        let frontier = step_frontier(&grid, &frontier, &mut seen);
+        insta::assert_snapshot!(render_frontier(&grid, &seen, &frontier));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(part1(".|\n.x").unwrap_err().to_string(), "invalid tile `x`");
+        assert_eq!(
+            part2(".|.\n.").unwrap_err().to_string(),
+            "line 2: expected 3 columns, found 1"
+        );
+        assert_eq!(part2("").unwrap_err().to_string(), "missing contraption");
+    }
+}
@@ -0,0 +1,1401 @@
+use aoc_core::{Answer, AocError, Solver};
+use aoc_viz::{gradient, render, RgbImage, SCALE, WHITE};
+use priority_queue::PriorityQueue;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{Debug, Display},
+    time::Instant,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    fn opposite(&self) -> Direction {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::UpLeft => Self::DownRight,
+            Self::UpRight => Self::DownLeft,
+            Self::DownLeft => Self::UpRight,
+            Self::DownRight => Self::UpLeft,
+        }
+    }
+
+    fn delta(&self) -> (isize, isize) {
+        match self {
+            Self::Up => (0, -1),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+            Self::Right => (1, 0),
+            Self::UpLeft => (-1, -1),
+            Self::UpRight => (1, -1),
+            Self::DownLeft => (-1, 1),
+            Self::DownRight => (1, 1),
+        }
+    }
+}
+
+pub trait Movement: Sync {
+    fn name(&self) -> &'static str;
+    fn directions(&self) -> &'static [Direction];
+    fn min_moves(&self, dx: usize, dy: usize) -> usize;
+}
+
+struct Orthogonal;
+
+impl Movement for Orthogonal {
+    fn name(&self) -> &'static str {
+        "orthogonal"
+    }
+
+    fn directions(&self) -> &'static [Direction] {
+        &[
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+    }
+
+    fn min_moves(&self, dx: usize, dy: usize) -> usize {
+        dx + dy
+    }
+}
+
+pub struct Diagonal;
+
+impl Movement for Diagonal {
+    fn name(&self) -> &'static str {
+        "diagonal"
+    }
+
+    fn directions(&self) -> &'static [Direction] {
+        &[
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+            Direction::UpLeft,
+            Direction::UpRight,
+            Direction::DownLeft,
+            Direction::DownRight,
+        ]
+    }
+
+    fn min_moves(&self, dx: usize, dy: usize) -> usize {
+        dx.max(dy)
+    }
+}
+
+impl Debug for dyn Movement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CrucibleRules {
+    min_straight: u8,
+    max_straight: u8,
+    turn_penalty: u64,
+    movement: &'static dyn Movement,
+}
+
+impl CrucibleRules {
+    pub const PART1: Self = Self {
+        min_straight: 1,
+        max_straight: 3,
+        turn_penalty: 0,
+        movement: &Orthogonal,
+    };
+    pub const PART2: Self = Self {
+        min_straight: 4,
+        max_straight: 10,
+        turn_penalty: 0,
+        movement: &Orthogonal,
+    };
+
+    pub fn with_turn_penalty(self, turn_penalty: u64) -> Self {
+        Self {
+            turn_penalty,
+            ..self
+        }
+    }
+
+    pub fn with_movement(self, movement: &'static dyn Movement) -> Self {
+        Self { movement, ..self }
+    }
+}
+
+impl TryFrom<&str> for CrucibleRules {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let invalid = || {
+            format!(
+                "invalid crucible rules {:?}, expected MIN,MAX with 0 < MIN <= MAX",
+                value
+            )
+        };
+        let (min_straight, max_straight) = value.split_once(',').ok_or_else(invalid)?;
+        let rules = Self {
+            min_straight: min_straight.trim().parse().map_err(|_| invalid())?,
+            max_straight: max_straight.trim().parse().map_err(|_| invalid())?,
+            turn_penalty: 0,
+            movement: &Orthogonal,
+        };
+        if rules.min_straight == 0 || rules.min_straight > rules.max_straight {
+            return Err(invalid());
+        }
+        Ok(rules)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    x: usize,
+    y: usize,
+    steps: u8,
+    direction: Direction,
+}
+
+impl State {
+    pub fn start() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            steps: 0,
+            direction: Direction::Right,
+        }
+    }
+
+    pub fn can_move(&self, direction: Direction, rules: CrucibleRules) -> bool {
+        if self.steps == 0 {
+            return true;
+        }
+        if self.direction.opposite() == direction {
+            return false;
+        }
+        if self.direction == direction {
+            return self.steps < rules.max_straight;
+        }
+        self.steps >= rules.min_straight
+    }
+
+    pub fn can_stop(&self, rules: CrucibleRules) -> bool {
+        self.steps >= rules.min_straight
+    }
+
+    pub fn in_bounds(&self, width: usize, height: usize, direction: Direction) -> bool {
+        let (dx, dy) = direction.delta();
+        let x_ok = match dx {
+            -1 => self.x > 0,
+            1 => self.x < width - 1,
+            _ => true,
+        };
+        let y_ok = match dy {
+            -1 => self.y > 0,
+            1 => self.y < height - 1,
+            _ => true,
+        };
+        x_ok && y_ok
+    }
+
+    pub fn apply_move(&self, direction: Direction) -> Self {
+        let (dx, dy) = direction.delta();
+        let x = self.x.wrapping_add_signed(dx);
+        let y = self.y.wrapping_add_signed(dy);
+        let steps = if self.direction == direction {
+            self.steps + 1
+        } else {
+            1
+        };
+        Self {
+            x,
+            y,
+            steps,
+            direction,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    InvalidCell {
+        row: usize,
+        column: usize,
+        token: String,
+    },
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "grid is empty"),
+            Self::InvalidCell { row, column, token } => write!(
+                f,
+                "invalid heat loss {:?} at row {}, column {}",
+                token, row, column
+            ),
+            Self::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} cells but row 1 has {}",
+                row, found, expected
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+fn parse_line(row: usize, line: &str) -> Result<Vec<u64>, ParseError> {
+    let invalid = |column: usize, token: &str| ParseError::InvalidCell {
+        row,
+        column,
+        token: token.to_string(),
+    };
+    if line.contains([',', ' ', '\t']) {
+        line.split([',', ' ', '\t'])
+            .filter(|cell| !cell.is_empty())
+            .enumerate()
+            .map(|(column, cell)| cell.parse().map_err(|_| invalid(column + 1, cell)))
+            .collect()
+    } else {
+        line.chars()
+            .enumerate()
+            .map(|(column, c)| {
+                c.to_digit(10)
+                    .map(u64::from)
+                    .ok_or_else(|| invalid(column + 1, &c.to_string()))
+            })
+            .collect()
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    let grid = s
+        .lines()
+        .enumerate()
+        .map(|(row, line)| parse_line(row + 1, line))
+        .collect::<Result<Vec<_>, _>>()?;
+    let expected = grid.first().map_or(0, Vec::len);
+    if expected == 0 {
+        return Err(ParseError::Empty);
+    }
+    for (row, line) in grid.iter().enumerate() {
+        if line.len() != expected {
+            return Err(ParseError::Ragged {
+                row: row + 1,
+                expected,
+                found: line.len(),
+            });
+        }
+    }
+    Ok(grid)
+}
+
+fn add_heat(distance: u64, cost: u64) -> u64 {
+    distance
+        .checked_add(cost)
+        .expect("Heat loss overflowed u64")
+}
+
+fn move_cost(grid: &[Vec<u64>], from: &State, to: &State, rules: CrucibleRules) -> u64 {
+    let turned = from.steps != 0 && from.direction != to.direction;
+    add_heat(
+        grid[to.y][to.x],
+        if turned { rules.turn_penalty } else { 0 },
+    )
+}
+
+fn get_neighbors(grid: &[Vec<u64>], state: &State, rules: CrucibleRules) -> Vec<State> {
+    let height = grid.len();
+    let width = grid[0].len();
+    let mut result = Vec::new();
+    for &direction in rules.movement.directions() {
+        if state.can_move(direction, rules) && state.in_bounds(width, height, direction) {
+            result.push(state.apply_move(direction));
+        }
+    }
+    result
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Algorithm {
+    Dijkstra,
+    AStar,
+    Bidirectional,
+}
+
+impl TryFrom<&str> for Algorithm {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "dijkstra" => Ok(Self::Dijkstra),
+            "astar" => Ok(Self::AStar),
+            "bidirectional" => Ok(Self::Bidirectional),
+            _ => Err(format!(
+                "invalid algorithm {:?}, expected dijkstra, astar or bidirectional",
+                value
+            )),
+        }
+    }
+}
+
+pub struct SearchResult {
+    pub heat_loss: u64,
+    pub expanded: usize,
+    pub pruned: usize,
+    path: Vec<State>,
+}
+
+impl SearchResult {
+    // The (x, y) of every block on the path, from the start to the factory.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.path.iter().map(|state| (state.x, state.y))
+    }
+}
+
+fn is_dominated(
+    dist: &HashMap<State, u64>,
+    state: &State,
+    distance: u64,
+    rules: CrucibleRules,
+) -> bool {
+    (rules.min_straight..state.steps).any(|steps| {
+        dist.get(&State { steps, ..*state })
+            .is_some_and(|&other_distance| other_distance <= distance)
+    })
+}
+
+fn reconstruct_path(prevs: &HashMap<State, State>, goal: State) -> Vec<State> {
+    let mut path = vec![goal];
+    let mut state = goal;
+    while let Some(prev) = prevs.get(&state) {
+        path.push(*prev);
+        state = *prev;
+    }
+    path.reverse();
+    path
+}
+
+fn heuristic(
+    grid: &[Vec<u64>],
+    rules: CrucibleRules,
+    algorithm: Algorithm,
+) -> impl Fn(&State) -> u64 {
+    let height = grid.len();
+    let width = grid[0].len();
+    let min_weight = match algorithm {
+        Algorithm::Dijkstra | Algorithm::Bidirectional => 0,
+        Algorithm::AStar => grid.iter().flatten().copied().min().unwrap_or(0),
+    };
+    move |state: &State| {
+        let remaining = rules
+            .movement
+            .min_moves(width - 1 - state.x, height - 1 - state.y);
+        (remaining as u64)
+            .checked_mul(min_weight)
+            .expect("Heat loss overflowed u64")
+    }
+}
+
+trait Frontier {
+    fn push(&mut self, state: State, priority: u64);
+    fn pop(&mut self) -> Option<(State, u64)>;
+    fn peek(&mut self) -> Option<u64>;
+}
+
+impl Frontier for PriorityQueue<State, Reverse<u64>> {
+    fn push(&mut self, state: State, priority: u64) {
+        self.push_increase(state, Reverse(priority));
+    }
+
+    fn pop(&mut self) -> Option<(State, u64)> {
+        PriorityQueue::pop(self).map(|(state, Reverse(priority))| (state, priority))
+    }
+
+    fn peek(&mut self) -> Option<u64> {
+        PriorityQueue::peek(self).map(|(_, Reverse(priority))| *priority)
+    }
+}
+
+struct RadixQueue {
+    buckets: Vec<Vec<(State, u64)>>,
+    last: u64,
+}
+
+impl Default for RadixQueue {
+    fn default() -> Self {
+        Self {
+            buckets: vec![Vec::new(); 65],
+            last: 0,
+        }
+    }
+}
+
+impl RadixQueue {
+    fn bucket(&self, priority: u64) -> usize {
+        (u64::BITS - (priority ^ self.last).leading_zeros()) as usize
+    }
+
+    fn settle(&mut self) -> bool {
+        if !self.buckets[0].is_empty() {
+            return true;
+        }
+        let Some(index) = self.buckets.iter().position(|bucket| !bucket.is_empty()) else {
+            return false;
+        };
+        let entries = std::mem::take(&mut self.buckets[index]);
+        let _span = tracing::trace_span!("rebucket", entries = entries.len()).entered();
+        self.last = entries.iter().map(|(_, priority)| *priority).min().unwrap();
+        for (state, priority) in entries {
+            let bucket = self.bucket(priority);
+            self.buckets[bucket].push((state, priority));
+        }
+        true
+    }
+}
+
+impl Frontier for RadixQueue {
+    fn push(&mut self, state: State, priority: u64) {
+        debug_assert!(
+            priority >= self.last,
+            "RadixQueue priorities must be monotone"
+        );
+        let bucket = self.bucket(priority);
+        self.buckets[bucket].push((state, priority));
+    }
+
+    fn pop(&mut self) -> Option<(State, u64)> {
+        if self.settle() {
+            self.buckets[0].pop()
+        } else {
+            None
+        }
+    }
+
+    fn peek(&mut self) -> Option<u64> {
+        if self.settle() {
+            Some(self.last)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum QueueKind {
+    Heap,
+    Radix,
+}
+
+// The factory can be out of reach, say when the grid is too small for the
+// crucible to move as far as it must before stopping.
+pub const NO_PATH: AocError = AocError::Missing("path to the factory");
+
+pub fn search(
+    grid: &[Vec<u64>],
+    rules: CrucibleRules,
+    algorithm: Algorithm,
+) -> Option<SearchResult> {
+    search_with::<RadixQueue>(grid, rules, algorithm)
+}
+
+#[tracing::instrument(level = "trace", skip(grid, rules))]
+fn search_with<Q: Frontier + Default>(
+    grid: &[Vec<u64>],
+    rules: CrucibleRules,
+    algorithm: Algorithm,
+) -> Option<SearchResult> {
+    if algorithm == Algorithm::Bidirectional {
+        return bidirectional_search::<Q>(grid, rules);
+    }
+    let mut queue = Q::default();
+    let height = grid.len();
+    let width = grid[0].len();
+    let estimate = heuristic(grid, rules, algorithm);
+    let mut dist: HashMap<State, u64> = HashMap::new();
+    let mut prevs: HashMap<State, State> = HashMap::new();
+    let mut expanded = 0;
+    let mut pruned = 0;
+    dist.insert(State::start(), 0);
+    queue.push(State::start(), estimate(&State::start()));
+    while let Some((state, priority)) = queue.pop() {
+        let distance = dist[&state];
+        if add_heat(distance, estimate(&state)) != priority {
+            continue;
+        }
+        if state.x == width - 1 && state.y == height - 1 && state.can_stop(rules) {
+            return Some(SearchResult {
+                heat_loss: distance,
+                expanded,
+                pruned,
+                path: reconstruct_path(&prevs, state),
+            });
+        }
+        if is_dominated(&dist, &state, distance, rules) {
+            pruned += 1;
+            continue;
+        }
+        expanded += 1;
+        for next_state in get_neighbors(grid, &state, rules) {
+            let next_distance = add_heat(distance, move_cost(grid, &state, &next_state, rules));
+            if is_dominated(&dist, &next_state, next_distance, rules) {
+                pruned += 1;
+                continue;
+            }
+            if dist
+                .get(&next_state)
+                .is_none_or(|&old_distance| next_distance < old_distance)
+            {
+                dist.insert(next_state, next_distance);
+                prevs.insert(next_state, state);
+                queue.push(next_state, add_heat(next_distance, estimate(&next_state)));
+            }
+        }
+    }
+    None
+}
+
+fn get_predecessors(grid: &[Vec<u64>], state: &State, rules: CrucibleRules) -> Vec<State> {
+    let height = grid.len();
+    let width = grid[0].len();
+    let direction = state.direction.opposite();
+    if state.steps == 0 || !state.in_bounds(width, height, direction) {
+        return Vec::new();
+    }
+    let behind = State {
+        steps: state.steps - 1,
+        ..state.apply_move(direction)
+    };
+    if state.steps > 1 {
+        return vec![State {
+            direction: state.direction,
+            ..behind
+        }];
+    }
+    let mut result = Vec::new();
+    if behind.x == 0 && behind.y == 0 {
+        result.push(State::start());
+    }
+    for &prev_direction in rules.movement.directions() {
+        if prev_direction == state.direction || prev_direction == direction {
+            continue;
+        }
+        for steps in rules.min_straight..=rules.max_straight {
+            result.push(State {
+                steps,
+                direction: prev_direction,
+                ..behind
+            });
+        }
+    }
+    result
+}
+
+fn goal_states(width: usize, height: usize, rules: CrucibleRules) -> Vec<State> {
+    let mut result = Vec::new();
+    for &direction in rules.movement.directions() {
+        for steps in rules.min_straight..=rules.max_straight {
+            result.push(State {
+                x: width - 1,
+                y: height - 1,
+                steps,
+                direction,
+            });
+        }
+    }
+    result
+}
+
+#[tracing::instrument(level = "trace", skip_all)]
+fn distances_to_goal(grid: &[Vec<u64>], rules: CrucibleRules) -> HashMap<State, u64> {
+    let height = grid.len();
+    let width = grid[0].len();
+    let mut queue = RadixQueue::default();
+    let mut dist: HashMap<State, u64> = HashMap::new();
+    for goal in goal_states(width, height, rules) {
+        dist.insert(goal, 0);
+        queue.push(goal, 0);
+    }
+    while let Some((state, distance)) = queue.pop() {
+        if dist[&state] != distance {
+            continue;
+        }
+        for prev_state in get_predecessors(grid, &state, rules) {
+            let prev_distance = add_heat(distance, move_cost(grid, &prev_state, &state, rules));
+            if dist
+                .get(&prev_state)
+                .is_none_or(|&old_distance| prev_distance < old_distance)
+            {
+                dist.insert(prev_state, prev_distance);
+                queue.push(prev_state, prev_distance);
+            }
+        }
+    }
+    dist
+}
+
+fn border_heat_losses(grid: &[Vec<u64>], rules: CrucibleRules) -> Vec<(usize, usize, Option<u64>)> {
+    let height = grid.len();
+    let width = grid[0].len();
+    let dist = distances_to_goal(grid, rules);
+    let mut result = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            if x != 0 && y != 0 && x != width - 1 && y != height - 1 {
+                continue;
+            }
+            let start = State {
+                x,
+                y,
+                ..State::start()
+            };
+            let heat_loss = get_neighbors(grid, &start, rules)
+                .into_iter()
+                .filter_map(|next_state| {
+                    dist.get(&next_state).map(|&distance| {
+                        add_heat(distance, move_cost(grid, &start, &next_state, rules))
+                    })
+                })
+                .min();
+            result.push((x, y, heat_loss));
+        }
+    }
+    result
+}
+
+pub fn border_table(s: &str, presets: &[(String, CrucibleRules)]) -> Result<String, ParseError> {
+    let grid = parse_input(s)?;
+    let mut out = String::new();
+    for (name, rules) in presets {
+        out.push_str(&format!("{}\nx,y,heat_loss\n", name));
+        for (x, y, heat_loss) in border_heat_losses(&grid, *rules) {
+            match heat_loss {
+                Some(heat_loss) => out.push_str(&format!("{},{},{}\n", x, y, heat_loss)),
+                None => out.push_str(&format!("{},{},\n", x, y)),
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[tracing::instrument(level = "trace", skip_all)]
+fn bidirectional_search<Q: Frontier + Default>(
+    grid: &[Vec<u64>],
+    rules: CrucibleRules,
+) -> Option<SearchResult> {
+    let height = grid.len();
+    let width = grid[0].len();
+    let mut forward = Q::default();
+    let mut backward = Q::default();
+    let mut forward_dist: HashMap<State, u64> = HashMap::new();
+    let mut backward_dist: HashMap<State, u64> = HashMap::new();
+    let mut prevs: HashMap<State, State> = HashMap::new();
+    let mut nexts: HashMap<State, State> = HashMap::new();
+    forward_dist.insert(State::start(), 0);
+    forward.push(State::start(), 0);
+    for goal in goal_states(width, height, rules) {
+        backward_dist.insert(goal, 0);
+        backward.push(goal, 0);
+    }
+    let mut best: Option<(u64, State, State)> = None;
+    let mut expanded = 0;
+    while let (Some(forward_top), Some(backward_top)) = (forward.peek(), backward.peek()) {
+        if best.is_some_and(|(cost, _, _)| add_heat(forward_top, backward_top) >= cost) {
+            break;
+        }
+        if forward_top <= backward_top {
+            let (state, distance) = forward.pop().unwrap();
+            if forward_dist[&state] != distance {
+                continue;
+            }
+            expanded += 1;
+            for next_state in get_neighbors(grid, &state, rules) {
+                let next_distance = add_heat(distance, move_cost(grid, &state, &next_state, rules));
+                if forward_dist
+                    .get(&next_state)
+                    .is_none_or(|&old_distance| next_distance < old_distance)
+                {
+                    forward_dist.insert(next_state, next_distance);
+                    prevs.insert(next_state, state);
+                    forward.push(next_state, next_distance);
+                }
+                if let Some(&remaining) = backward_dist.get(&next_state) {
+                    let total = add_heat(next_distance, remaining);
+                    if best.is_none_or(|(cost, _, _)| total < cost) {
+                        best = Some((total, state, next_state));
+                    }
+                }
+            }
+        } else {
+            let (state, distance) = backward.pop().unwrap();
+            if backward_dist[&state] != distance {
+                continue;
+            }
+            expanded += 1;
+            for prev_state in get_predecessors(grid, &state, rules) {
+                let prev_distance = add_heat(distance, move_cost(grid, &prev_state, &state, rules));
+                if backward_dist
+                    .get(&prev_state)
+                    .is_none_or(|&old_distance| prev_distance < old_distance)
+                {
+                    backward_dist.insert(prev_state, prev_distance);
+                    nexts.insert(prev_state, state);
+                    backward.push(prev_state, prev_distance);
+                }
+                if let Some(&travelled) = forward_dist.get(&prev_state) {
+                    let total = add_heat(travelled, prev_distance);
+                    if best.is_none_or(|(cost, _, _)| total < cost) {
+                        best = Some((total, prev_state, state));
+                    }
+                }
+            }
+        }
+    }
+    let (heat_loss, meet_from, meet_to) = best?;
+    let mut path = reconstruct_path(&prevs, meet_from);
+    let mut state = meet_to;
+    path.push(state);
+    while let Some(next_state) = nexts.get(&state) {
+        path.push(*next_state);
+        state = *next_state;
+    }
+    Some(SearchResult {
+        heat_loss,
+        expanded,
+        pruned: 0,
+        path,
+    })
+}
+
+fn render_path(grid: &[Vec<u64>], path: &[State]) -> String {
+    let on_path: HashSet<(usize, usize)> = path.iter().map(|state| (state.x, state.y)).collect();
+    let cell_width = grid
+        .iter()
+        .flatten()
+        .max()
+        .map_or(1, |max| max.to_string().len());
+    let separator = if cell_width > 1 { " " } else { "" };
+    grid.iter()
+        .enumerate()
+        .map(|(y, line)| {
+            line.iter()
+                .enumerate()
+                .map(|(x, weight)| {
+                    if on_path.contains(&(x, y)) {
+                        format!("\x1b[1;30;43m{:>cell_width$}\x1b[0m", weight)
+                    } else {
+                        format!("\x1b[2m{:>cell_width$}\x1b[0m", weight)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(separator)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Heat loss from dark blue to red, with the best path for `rules` in white.
+pub fn render_image(
+    s: &str,
+    rules: CrucibleRules,
+    algorithm: Algorithm,
+) -> Result<RgbImage, ParseError> {
+    let grid = parse_input(s)?;
+    let on_path: HashSet<(usize, usize)> = search(&grid, rules, algorithm)
+        .map(|result| result.positions().collect())
+        .unwrap_or_default();
+    let max = grid.iter().flatten().copied().max().unwrap_or(1).max(1);
+    Ok(render(
+        grid[0].len(),
+        grid.len(),
+        SCALE,
+        |x, y| match on_path.contains(&(x, y)) {
+            true => WHITE,
+            false => gradient([20, 30, 90], [230, 60, 30], grid[y][x] as f64 / max as f64),
+        },
+    ))
+}
+
+pub fn visualize(
+    s: &str,
+    presets: &[(String, CrucibleRules)],
+    algorithm: Algorithm,
+) -> Result<String, ParseError> {
+    let grid = parse_input(s)?;
+    let mut out = String::new();
+    for (name, rules) in presets {
+        match search(&grid, *rules, algorithm) {
+            Some(result) => out.push_str(&format!(
+                "{}\n{}: {}\n",
+                render_path(&grid, &result.path),
+                name,
+                result.heat_loss
+            )),
+            None => out.push_str(&format!("{}: {}\n", name, NO_PATH)),
+        }
+    }
+    Ok(out)
+}
+
+pub fn benchmark(s: &str, presets: &[(String, CrucibleRules)]) -> Result<String, ParseError> {
+    let grid = parse_input(s)?;
+    let mut out = String::new();
+    for (name, rules) in presets {
+        for algorithm in [
+            Algorithm::Dijkstra,
+            Algorithm::AStar,
+            Algorithm::Bidirectional,
+        ] {
+            for queue in [QueueKind::Heap, QueueKind::Radix] {
+                let start = Instant::now();
+                let Some(result) = (match queue {
+                    QueueKind::Heap => {
+                        search_with::<PriorityQueue<State, Reverse<u64>>>(&grid, *rules, algorithm)
+                    }
+                    QueueKind::Radix => search_with::<RadixQueue>(&grid, *rules, algorithm),
+                }) else {
+                    out.push_str(&format!(
+                        "{} {:?} {:?}: {}\n",
+                        name, algorithm, queue, NO_PATH
+                    ));
+                    continue;
+                };
+                out.push_str(&format!(
+                    "{} {:?} {:?}: heat loss {}, {} states expanded, {} pruned in {:?}\n",
+                    name,
+                    algorithm,
+                    queue,
+                    result.heat_loss,
+                    result.expanded,
+                    result.pruned,
+                    start.elapsed()
+                ));
+            }
+        }
+    }
+    Ok(out)
+}
+
+pub fn solve(
+    s: &str,
+    rules: CrucibleRules,
+    algorithm: Algorithm,
+) -> Result<Option<u64>, ParseError> {
+    Ok(search(&parse_input(s)?, rules, algorithm).map(|result| result.heat_loss))
+}
+
+pub fn part1_with(s: &str, algorithm: Algorithm) -> Result<Option<u64>, ParseError> {
+    solve(s, CrucibleRules::PART1, algorithm)
+}
+
+pub fn part2_with(s: &str, algorithm: Algorithm) -> Result<Option<u64>, ParseError> {
+    solve(s, CrucibleRules::PART2, algorithm)
+}
+
+pub fn part1(s: &str) -> Result<Option<u64>, ParseError> {
+    part1_with(s, Algorithm::Dijkstra)
+}
+
+pub fn part2(s: &str) -> Result<Option<u64>, ParseError> {
+    part2_with(s, Algorithm::Dijkstra)
+}
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    type Parsed<'a> = Vec<Vec<u64>>;
+    type Error = ParseError;
+
+    fn day() -> u8 {
+        17
+    }
+
+    fn parse(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(grid: &Self::Parsed<'_>) -> impl Answer {
+        search(grid, CrucibleRules::PART1, Algorithm::Dijkstra)
+            .map(|result| result.heat_loss)
+            .ok_or(NO_PATH)
+    }
+
+    fn part2(grid: &Self::Parsed<'_>) -> impl Answer {
+        search(grid, CrucibleRules::PART2, Algorithm::Dijkstra)
+            .map(|result| result.heat_loss)
+            .ok_or(NO_PATH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use aoc_core::Registration;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day17/example.txt");
+
+    #[test]
+    fn test_algorithm_names() {
+        assert_eq!(
+            Algorithm::try_from("bidirectional"),
+            Ok(Algorithm::Bidirectional)
+        );
+        assert_eq!(
+            Algorithm::try_from("fastest").unwrap_err(),
+            "invalid algorithm \"fastest\", expected dijkstra, astar or bidirectional"
+        );
+    }
+
+    #[test]
+    fn test_astar_expands_fewer_states() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        let dijkstra = search(&grid, CrucibleRules::PART1, Algorithm::Dijkstra).unwrap();
+        let astar = search(&grid, CrucibleRules::PART1, Algorithm::AStar).unwrap();
+        assert!(astar.expanded < dijkstra.expanded);
+    }
+
+    #[test]
+    fn test_part2_can_start_downwards() {
+        let input = "19999
+19999
+19999
+19999
+11111";
+        assert_eq!(part2_with(input, Algorithm::AStar), Ok(Some(8)));
+    }
+
+    #[test]
+    fn test_predecessors_invert_neighbors() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        for rules in [CrucibleRules::PART1, CrucibleRules::PART2] {
+            let mut states = vec![State::start()];
+            let mut seen = HashSet::from([State::start()]);
+            while let Some(state) = states.pop() {
+                for next_state in get_neighbors(&grid, &state, rules) {
+                    assert!(get_predecessors(&grid, &next_state, rules).contains(&state));
+                    if seen.insert(next_state) {
+                        states.push(next_state);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_path_reconstruction() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        for (rules, algorithm) in [
+            (CrucibleRules::PART1, Algorithm::Dijkstra),
+            (CrucibleRules::PART2, Algorithm::Dijkstra),
+            (CrucibleRules::PART1, Algorithm::Bidirectional),
+            (CrucibleRules::PART2, Algorithm::Bidirectional),
+        ] {
+            let result = search(&grid, rules, algorithm).unwrap();
+            assert!(result.path[0] == State::start());
+            let last = result.path.last().unwrap();
+            assert_eq!((last.x, last.y), (12, 12));
+            let cost: u64 = result
+                .path
+                .windows(2)
+                .map(|pair| move_cost(&grid, &pair[0], &pair[1], rules))
+                .sum();
+            assert_eq!(cost, result.heat_loss);
+            for pair in result.path.windows(2) {
+                assert!(get_neighbors(&grid, &pair[0], rules).contains(&pair[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_path() {
+        for algorithm in [
+            Algorithm::Dijkstra,
+            Algorithm::AStar,
+            Algorithm::Bidirectional,
+        ] {
+            assert_eq!(solve("1", CrucibleRules::PART1, algorithm), Ok(None));
+            // Too short for the ultra crucible to stop at the factory.
+            assert_eq!(part2_with("111\n111", algorithm), Ok(None));
+        }
+        assert_eq!(
+            (Registration::of::<Day17>().parts[1])("111\n111"),
+            Err("missing path to the factory".to_string())
+        );
+    }
+
+    #[test]
+    fn test_render_path() {
+        let grid = parse_input("12\n34").unwrap();
+        let result = search(&grid, CrucibleRules::PART1, Algorithm::Dijkstra).unwrap();
+        assert_eq!(
+            render_path(&grid, &result.path),
+            "\x1b[1;30;43m1\x1b[0m\x1b[1;30;43m2\x1b[0m\n\x1b[2m3\x1b[0m\x1b[1;30;43m4\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_reports() {
+        let presets = [("Part 1".to_string(), CrucibleRules::PART1)];
+        let result = search(
+            &parse_input("12\n34").unwrap(),
+            CrucibleRules::PART1,
+            Algorithm::Dijkstra,
+        )
+        .unwrap();
+        assert_eq!(result.heat_loss, 6);
+        assert_eq!(
+            result.positions().collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (1, 1)]
+        );
+        let shown = visualize("12\n34", &presets, Algorithm::Dijkstra).unwrap();
+        assert!(shown.ends_with("\x1b[1;30;43m4\x1b[0m\nPart 1: 6\n"));
+        let bench = benchmark("12\n34", &presets).unwrap();
+        assert_eq!(bench.lines().count(), 6);
+        assert!(bench.starts_with("Part 1 Dijkstra Heap: heat loss 6,"));
+        assert_eq!(
+            visualize("1", &presets, Algorithm::AStar),
+            Ok("Part 1: missing path to the factory\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_render_image() {
+        let image = render_image("12\n34", CrucibleRules::PART1, Algorithm::Dijkstra).unwrap();
+        assert_eq!(image.dimensions(), (8, 8));
+        assert_eq!(image.get_pixel(7, 7).0, WHITE);
+        assert_eq!(
+            image.get_pixel(0, 7).0,
+            gradient([20, 30, 90], [230, 60, 30], 0.75)
+        );
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let input = "11111
+99991";
+        assert_eq!(
+            solve(
+                input,
+                CrucibleRules::try_from("1,4").unwrap(),
+                Algorithm::Dijkstra
+            ),
+            Ok(Some(5))
+        );
+        assert_eq!(
+            solve(
+                input,
+                CrucibleRules::try_from("1,2").unwrap(),
+                Algorithm::Dijkstra
+            ),
+            Ok(Some(21))
+        );
+        assert_eq!(
+            solve(
+                input,
+                CrucibleRules::try_from("1,1").unwrap(),
+                Algorithm::Dijkstra
+            ),
+            Ok(Some(31))
+        );
+        for rules in ["abc", "0,3", "4,2", "1,x"] {
+            assert!(CrucibleRules::try_from(rules).is_err(), "{}", rules);
+        }
+    }
+
+    #[test]
+    fn test_parse_multi_digit_cells() {
+        assert_eq!(
+            parse_input("10,200 3\n4 5 6"),
+            Ok(vec![vec![10, 200, 3], vec![4, 5, 6]])
+        );
+        assert_eq!(parse_input("12\n34"), Ok(vec![vec![1, 2], vec![3, 4]]));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_input(""), Err(ParseError::Empty));
+        assert_eq!(
+            parse_input("123\n4x6"),
+            Err(ParseError::InvalidCell {
+                row: 2,
+                column: 2,
+                token: "x".to_string()
+            })
+        );
+        assert_eq!(
+            parse_input("1 2\n3 -4"),
+            Err(ParseError::InvalidCell {
+                row: 2,
+                column: 2,
+                token: "-4".to_string()
+            })
+        );
+        assert_eq!(
+            parse_input("123\n45\n678"),
+            Err(ParseError::Ragged {
+                row: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            part1_with("12\n3", Algorithm::Dijkstra)
+                .unwrap_err()
+                .to_string(),
+            "row 2 has 1 cells but row 1 has 2"
+        );
+    }
+
+    #[test]
+    fn test_wide_weights() {
+        let input = "1 1000 1000
+1 1000 1000
+1 1 1";
+        assert_eq!(
+            solve(input, CrucibleRules::PART1, Algorithm::Dijkstra),
+            Ok(Some(4))
+        );
+        let input = "1 5000000000
+5000000000 5000000000";
+        assert_eq!(
+            solve(input, CrucibleRules::PART1, Algorithm::AStar),
+            Ok(Some(10000000000))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Heat loss overflowed u64")]
+    fn test_heat_loss_overflow() {
+        let max = u64::MAX;
+        solve(
+            &format!("1 {}\n{} {}", max, max, max),
+            CrucibleRules::PART1,
+            Algorithm::Dijkstra,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_diagonal_movement() {
+        let input = "199
+919
+991";
+        assert_eq!(
+            solve(input, CrucibleRules::PART1, Algorithm::Dijkstra),
+            Ok(Some(20))
+        );
+        for algorithm in [
+            Algorithm::Dijkstra,
+            Algorithm::AStar,
+            Algorithm::Bidirectional,
+        ] {
+            assert_eq!(
+                solve(
+                    input,
+                    CrucibleRules::PART1.with_movement(&Diagonal),
+                    algorithm
+                ),
+                Ok(Some(2))
+            );
+        }
+        let grid = parse_input(TEST_INPUT).unwrap();
+        let rules = CrucibleRules::PART2.with_movement(&Diagonal);
+        let expected = relax_until_stable(&grid, rules);
+        for algorithm in [
+            Algorithm::Dijkstra,
+            Algorithm::AStar,
+            Algorithm::Bidirectional,
+        ] {
+            assert_eq!(search(&grid, rules, algorithm).unwrap().heat_loss, expected);
+        }
+    }
+
+    #[test]
+    fn test_radix_queue_order() {
+        let mut queue = RadixQueue::default();
+        let state = State::start();
+        queue.push(state, 5);
+        queue.push(state, 2);
+        queue.push(state, 9);
+        assert_eq!(queue.pop().map(|(_, p)| p), Some(2));
+        queue.push(state, 3);
+        assert_eq!(queue.pop().map(|(_, p)| p), Some(3));
+        assert_eq!(queue.pop().map(|(_, p)| p), Some(5));
+        queue.push(state, 1 << 40);
+        queue.push(state, 7);
+        assert_eq!(queue.peek(), Some(7));
+        assert_eq!(queue.pop().map(|(_, p)| p), Some(7));
+        assert_eq!(queue.pop().map(|(_, p)| p), Some(9));
+        assert_eq!(queue.pop().map(|(_, p)| p), Some(1 << 40));
+        assert_eq!(queue.pop().map(|(_, p)| p), None);
+    }
+
+    #[test]
+    fn test_dominance_pruning() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        let result = search(&grid, CrucibleRules::PART1, Algorithm::Dijkstra).unwrap();
+        assert!(result.pruned > 0);
+        let mut dist = HashMap::new();
+        let state = State {
+            x: 3,
+            y: 3,
+            steps: 3,
+            direction: Direction::Right,
+        };
+        dist.insert(State { steps: 1, ..state }, 10);
+        assert!(is_dominated(&dist, &state, 10, CrucibleRules::PART1));
+        assert!(!is_dominated(&dist, &state, 9, CrucibleRules::PART1));
+        assert!(!is_dominated(
+            &dist,
+            &state,
+            10,
+            CrucibleRules::try_from("2,3").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_border_heat_losses() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        for rules in [CrucibleRules::PART1, CrucibleRules::PART2] {
+            let results = border_heat_losses(&grid, rules);
+            assert_eq!(results.len(), 48);
+            assert_eq!(
+                results[0],
+                (
+                    0,
+                    0,
+                    Some(search(&grid, rules, Algorithm::Dijkstra).unwrap().heat_loss)
+                )
+            );
+        }
+        let results = border_heat_losses(&grid, CrucibleRules::PART1);
+        assert!(results.contains(&(12, 11, Some(3))));
+        assert!(results.contains(&(11, 12, Some(3))));
+    }
+
+    #[test]
+    fn test_turn_penalty() {
+        let input = "111
+991
+991";
+        let rules = CrucibleRules::try_from("1,3").unwrap();
+        assert_eq!(solve(input, rules, Algorithm::Dijkstra), Ok(Some(4)));
+        assert_eq!(
+            solve(input, rules.with_turn_penalty(2), Algorithm::Dijkstra),
+            Ok(Some(6))
+        );
+        let zigzag = "1111
+1991
+1111";
+        assert_eq!(solve(zigzag, rules, Algorithm::Dijkstra), Ok(Some(5)));
+        for algorithm in [
+            Algorithm::Dijkstra,
+            Algorithm::AStar,
+            Algorithm::Bidirectional,
+        ] {
+            assert_eq!(
+                solve(zigzag, rules.with_turn_penalty(100), algorithm),
+                Ok(Some(105))
+            );
+        }
+    }
+
+    fn relax_until_stable(grid: &[Vec<u64>], rules: CrucibleRules) -> u64 {
+        let height = grid.len();
+        let width = grid[0].len();
+        let mut dist: HashMap<State, u64> = HashMap::from([(State::start(), 0)]);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (state, distance) in dist.clone() {
+                for next_state in get_neighbors(grid, &state, rules) {
+                    let next_distance =
+                        add_heat(distance, move_cost(grid, &state, &next_state, rules));
+                    if dist
+                        .get(&next_state)
+                        .is_none_or(|&old_distance| next_distance < old_distance)
+                    {
+                        dist.insert(next_state, next_distance);
+                        changed = true;
+                    }
+                }
+            }
+        }
+        dist.into_iter()
+            .filter(|(state, _)| {
+                state.x == width - 1 && state.y == height - 1 && state.can_stop(rules)
+            })
+            .map(|(_, distance)| distance)
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_search_matches_exhaustive_relaxation() {
+        let mut seed: u64 = 17;
+        for _ in 0..20 {
+            let grid: Vec<Vec<u64>> = (0..7)
+                .map(|_| {
+                    (0..7)
+                        .map(|_| {
+                            seed = seed
+                                .wrapping_mul(6364136223846793005)
+                                .wrapping_add(1442695040888963407);
+                            [1, 1, 9, 9, 5][(seed >> 33) as usize % 5]
+                        })
+                        .collect()
+                })
+                .collect();
+            for rules in [
+                CrucibleRules::PART1,
+                CrucibleRules::PART2,
+                CrucibleRules::try_from("2,5").unwrap(),
+                CrucibleRules::PART1.with_turn_penalty(3),
+                CrucibleRules::PART2.with_turn_penalty(7),
+            ] {
+                let expected = relax_until_stable(&grid, rules);
+                for algorithm in [
+                    Algorithm::Dijkstra,
+                    Algorithm::AStar,
+                    Algorithm::Bidirectional,
+                ] {
+                    assert_eq!(search(&grid, rules, algorithm).unwrap().heat_loss, expected);
+                    assert_eq!(
+                        search_with::<PriorityQueue<State, Reverse<u64>>>(&grid, rules, algorithm)
+                            .unwrap()
+                            .heat_loss,
+                        expected
+                    );
+                }
+            }
+        }
+    }
+}
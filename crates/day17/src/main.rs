@@ -1,221 +1,130 @@
-use priority_queue::PriorityQueue;
-use std::{cmp::Reverse, collections::HashSet, fs::read_to_string, hash::Hash};
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+use aoc_core::timing::Timings;
+use aoc_input::cli::{Answers, DayArgs};
+use clap::Parser;
+use day17::*;
+use std::{error::Error, path::PathBuf};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    day: DayArgs,
+    /// Solve the selected parts with custom crucible rules
+    #[arg(long, value_name = "MIN,MAX", value_parser = |s: &str| CrucibleRules::try_from(s))]
+    rules: Option<CrucibleRules>,
+    /// Extra heat loss charged for every turn
+    #[arg(long, value_name = "N")]
+    turn_penalty: Option<u64>,
+    /// Let the crucible move diagonally too
+    #[arg(long)]
+    diagonal: bool,
+    /// Print the heat loss along the border for each rule set
+    #[arg(long)]
+    border: bool,
+    /// Compare the search algorithms
+    #[arg(long)]
+    bench: bool,
+    /// Solve with dijkstra, astar or bidirectional
+    #[arg(long, default_value = "dijkstra", value_parser = |s: &str| Algorithm::try_from(s))]
+    algo: Algorithm,
+    /// Draw the best path for each rule set once its search has finished
+    #[arg(long)]
+    visualize: bool,
+    /// Draw the heat map and the best path for the first rule set to a PNG
+    #[arg(long, value_name = "PATH")]
+    render: Option<PathBuf>,
 }
 
-impl Direction {
-    fn opposite(&self) -> Direction {
-        match self {
-            Self::Up => Self::Down,
-            Self::Down => Self::Up,
-            Self::Left => Self::Right,
-            Self::Right => Self::Left,
-        }
+// `rules` with the turn penalty and movement asked for on the command line.
+fn adjust(cli: &Cli, rules: CrucibleRules) -> CrucibleRules {
+    let rules = rules.with_turn_penalty(cli.turn_penalty.unwrap_or(0));
+    match cli.diagonal {
+        true => rules.with_movement(&Diagonal),
+        false => rules,
     }
 }
 
-#[derive(Clone, Copy)]
-struct Move {
-    distance: u32,
-    x: usize,
-    y: usize,
-    steps: u8,
-    direction: Direction,
+fn part_rules(cli: &Cli, part: u8) -> CrucibleRules {
+    let rules = match (cli.rules, part) {
+        (Some(rules), _) => rules,
+        (None, 1) => CrucibleRules::PART1,
+        (None, _) => CrucibleRules::PART2,
+    };
+    adjust(cli, rules)
 }
 
-impl PartialEq for Move {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x
-            && self.y == other.y
-            && self.steps == other.steps
-            && self.direction == other.direction
+fn run(input: &str, cli: &Cli, timings: &mut Timings) -> Result<(), Box<dyn Error>> {
+    let presets: Vec<(String, CrucibleRules)> = match &cli.rules {
+        Some(rules) => vec![("Custom".to_string(), *rules)],
+        None => vec![
+            ("Part 1".to_string(), CrucibleRules::PART1),
+            ("Part 2".to_string(), CrucibleRules::PART2),
+        ],
     }
-}
-
-impl Eq for Move {}
-
-impl Hash for Move {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        state.write_usize(self.x);
-        state.write_usize(self.y);
-        state.write_u8(self.steps);
-        self.direction.hash(state);
+    .into_iter()
+    .map(|(name, rules)| (name, adjust(cli, rules)))
+    .collect();
+    if cli.border {
+        print!("{}", border_table(input, &presets)?);
+        return Ok(());
     }
-}
-
-impl Move {
-    pub fn start() -> Self {
-        Self {
-            distance: 0,
-            x: 0,
-            y: 0,
-            steps: 0,
-            direction: Direction::Right,
-        }
+    if cli.bench {
+        print!("{}", benchmark(input, &presets)?);
+        return Ok(());
     }
-
-    pub fn can_move(&self, direction: Direction, is_part_2: bool) -> bool {
-        if self.direction.opposite() == direction {
-            return false;
-        }
-        if self.direction == direction {
-            if is_part_2 {
-                return self.steps < 10;
-            } else {
-                return self.steps < 3;
-            }
-        }
-        if is_part_2 {
-            self.steps >= 4
-        } else {
-            true
+    let algorithm = cli.algo;
+    if let Some(path) = &cli.render {
+        let image = render_image(input, presets[0].1, algorithm)?;
+        if let Err(err) = aoc_viz::save_png(&image, path) {
+            eprintln!("Could not write {}: {}", path.display(), err);
+            std::process::exit(1);
         }
+        println!("Saved {}", path.display());
+        return Ok(());
     }
-
-    pub fn in_bounds(&self, width: usize, height: usize, direction: Direction) -> bool {
-        match direction {
-            Direction::Left => self.x > 0,
-            Direction::Right => self.x < width - 1,
-            Direction::Up => self.y > 0,
-            Direction::Down => self.y < height - 1,
-        }
-    }
-
-    pub fn apply_move(&self, grid: &[Vec<u32>], direction: Direction) -> Self {
-        let x = match direction {
-            Direction::Left => self.x - 1,
-            Direction::Right => self.x + 1,
-            _ => self.x,
-        };
-        let y = match direction {
-            Direction::Up => self.y - 1,
-            Direction::Down => self.y + 1,
-            _ => self.y,
-        };
-        let steps = if self.direction == direction {
-            self.steps + 1
-        } else {
-            1
-        };
-        let distance = self.distance + grid[y][x];
-        Self {
-            distance,
-            x,
-            y,
-            steps,
-            direction,
-        }
-    }
-}
-
-fn parse_input(s: &str) -> Vec<Vec<u32>> {
-    s.lines()
-        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
-        .collect()
-}
-
-fn initialize_queue() -> PriorityQueue<Move, Reverse<u32>> {
-    let mut queue: PriorityQueue<Move, Reverse<u32>> = PriorityQueue::new();
-    queue.push(Move::start(), Reverse(0));
-    queue
-}
-
-fn initialize_prevs(grid: &[Vec<u32>]) -> Vec<Vec<Option<(usize, usize)>>> {
-    grid.iter().map(|line| vec![None; line.len()]).collect()
-}
-
-fn get_neighbors(grid: &[Vec<u32>], cur_move: &Move, is_part_2: bool) -> Vec<Move> {
-    let height = grid.len();
-    let width = grid[0].len();
-    let mut result = Vec::new();
-    for direction in [
-        Direction::Up,
-        Direction::Down,
-        Direction::Left,
-        Direction::Right,
-    ] {
-        if cur_move.can_move(direction, is_part_2) && cur_move.in_bounds(width, height, direction) {
-            result.push(cur_move.apply_move(grid, direction));
-        }
+    if cli.visualize {
+        print!("{}", visualize(input, &presets, algorithm)?);
+        return Ok(());
     }
-    result
-}
-
-fn find_path(grid: &[Vec<u32>], is_part_2: bool) -> u32 {
-    let height = grid.len();
-    let width = grid[0].len();
-    let mut queue = initialize_queue();
-    let mut prevs = initialize_prevs(grid);
-    let mut seen: HashSet<Move> = HashSet::new();
-    while let Some((cur_move, _)) = queue.pop() {
-        for next_move in get_neighbors(grid, &cur_move, is_part_2) {
-            if next_move.x == width - 1 && next_move.y == height - 1 {
-                return next_move.distance;
-            }
-            if let Some(old_move) = seen.get(&next_move) {
-                if next_move.distance < old_move.distance {
-                    prevs[next_move.y][next_move.x] = Some((cur_move.y, cur_move.x));
-                    queue.remove(&next_move);
-                    queue.push(next_move, Reverse(next_move.distance));
-                }
-            } else {
-                prevs[next_move.y][next_move.x] = Some((cur_move.y, cur_move.x));
-                queue.remove(&next_move);
-                queue.push(next_move, Reverse(next_move.distance));
-            }
-            seen.insert(next_move);
+    let args = &cli.day;
+    let grid = timings.time("parse", || parse_input(input))?;
+    let mut answers = Answers::new(args);
+    for part in [1, 2] {
+        if !args.runs(part) {
+            continue;
         }
+        let rules = part_rules(cli, part);
+        let result = timings
+            .time(&format!("part {}", part), || {
+                search(&grid, rules, algorithm)
+            })
+            .ok_or(NO_PATH)?;
+        tracing::debug!(
+            expanded = result.expanded,
+            pruned = result.pruned,
+            "part {} search",
+            part
+        );
+        answers.report(part, result.heat_loss);
     }
-    panic!("No path found!");
-}
-
-fn part1(s: &str) -> u32 {
-    find_path(&parse_input(s), false)
-}
-
-fn part2(s: &str) -> u32 {
-    find_path(&parse_input(s), true)
+    answers.finish();
+    Ok(())
 }
 
 fn main() {
-    let input = read_to_string("input.txt").unwrap();
-    let answer1 = part1(&input);
-    println!("Part 1: {}", answer1);
-    let answer2 = part2(&input);
-    println!("Part 2: {}", answer2);
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::*;
-
-    const TEST_INPUT: &str = "2413432311323
-3215453535623
-3255245654254
-3446585845452
-4546657867536
-1438598798454
-4457876987766
-3637877979653
-4654967986887
-4564679986453
-1224686865563
-2546548887735
-4322674655533";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 102);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 94);
+    let cli = Cli::parse();
+    let _log = cli.day.init_logging();
+    let mut timings = Timings::new(cli.day.time);
+    let input = timings
+        .time("read", || {
+            cli.day.load(2023, 17, env!("CARGO_MANIFEST_DIR"))
+        })
+        .unwrap_or_else(|err| {
+            eprintln!("Could not load input: {}", err);
+            std::process::exit(1);
+        });
+    if let Err(err) = run(&input, &cli, &mut timings) {
+        eprintln!("Invalid input: {}", err);
+        std::process::exit(1);
     }
+    timings.print();
 }
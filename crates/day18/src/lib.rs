@@ -0,0 +1,1247 @@
+use aoc_core::{interval::coalesce, Answer, Direction, Interval, Point, Solver};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{Debug, Display},
+    ops::Add,
+};
+
+// Plans are case-insensitive unless strict.
+fn parse_direction(value: &str, strict: bool) -> Option<Direction> {
+    let mut chars = value.chars();
+    let c = match (chars.next(), chars.next()) {
+        (Some(c), None) => c,
+        _ => return None,
+    };
+    let c = if strict { c } else { c.to_ascii_uppercase() };
+    Direction::try_from(c).ok()
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+impl Color {
+    fn parse_hex(hex: &str) -> Option<Color> {
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).ok();
+        Some(Self {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        })
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct Instruction {
+    direction: Direction,
+    steps: usize,
+    color: Color,
+}
+
+impl Debug for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} {:?}", self.direction, self.steps))
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseError {
+    MissingColumn {
+        line: usize,
+        column: usize,
+    },
+    ExtraColumns {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    UnknownDirection {
+        line: usize,
+        token: String,
+    },
+    InvalidSteps {
+        line: usize,
+        token: String,
+    },
+    InvalidColor {
+        line: usize,
+        token: String,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingColumn { line, column } => {
+                write!(f, "line {}: missing column {}", line, column + 1)
+            }
+            Self::ExtraColumns {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} columns, found {}",
+                line, expected, found
+            ),
+            Self::UnknownDirection { line, token } => {
+                write!(f, "line {}: unknown direction {:?}", line, token)
+            }
+            Self::InvalidSteps { line, token } => {
+                write!(f, "line {}: invalid step count {:?}", line, token)
+            }
+            Self::InvalidColor { line, token } => {
+                write!(f, "line {}: invalid color {:?}", line, token)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct PlanLine {
+    pub literal: Instruction,
+    pub decoded: Instruction,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct DigPlanParser {
+    pub direction_column: usize,
+    pub steps_column: usize,
+    pub color_column: usize,
+    pub steps_radix: u32,
+    pub color_steps_digits: usize,
+    pub color_directions: [Direction; 4],
+    pub strict: bool,
+}
+
+impl DigPlanParser {
+    pub const PART1: Self = Self {
+        direction_column: 0,
+        steps_column: 1,
+        color_column: 2,
+        steps_radix: 10,
+        color_steps_digits: 5,
+        color_directions: [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ],
+        strict: false,
+    };
+
+    fn columns(&self) -> usize {
+        self.direction_column
+            .max(self.steps_column)
+            .max(self.color_column)
+            + 1
+    }
+
+    fn parse_color<'a>(&self, line: usize, token: &'a str) -> Result<(&'a str, Color), ParseError> {
+        let hex = if self.strict {
+            token.strip_prefix("(#").and_then(|t| t.strip_suffix(')'))
+        } else {
+            Some(
+                token
+                    .trim_start_matches('(')
+                    .trim_start_matches('#')
+                    .trim_end_matches(')'),
+            )
+        };
+        hex.and_then(|hex| Some((hex, Color::parse_hex(hex)?)))
+            .ok_or_else(|| ParseError::InvalidColor {
+                line,
+                token: token.to_string(),
+            })
+    }
+
+    fn parse_line(&self, number: usize, line: &str) -> Result<PlanLine, ParseError> {
+        let segments = line.split_whitespace().collect::<Vec<&str>>();
+        if self.strict && segments.len() > self.columns() {
+            return Err(ParseError::ExtraColumns {
+                line: number,
+                expected: self.columns(),
+                found: segments.len(),
+            });
+        }
+        let column = |column: usize| {
+            segments
+                .get(column)
+                .copied()
+                .ok_or(ParseError::MissingColumn {
+                    line: number,
+                    column,
+                })
+        };
+        let color_segment = column(self.color_column)?;
+        let direction_segment = column(self.direction_column)?;
+        let steps_segment = column(self.steps_column)?;
+        let (hex, color) = self.parse_color(number, color_segment)?;
+        let literal = Instruction {
+            direction: parse_direction(direction_segment, self.strict).ok_or_else(|| {
+                ParseError::UnknownDirection {
+                    line: number,
+                    token: direction_segment.to_string(),
+                }
+            })?,
+            steps: usize::from_str_radix(steps_segment, self.steps_radix).map_err(|_| {
+                ParseError::InvalidSteps {
+                    line: number,
+                    token: steps_segment.to_string(),
+                }
+            })?,
+            color,
+        };
+        let invalid = || ParseError::InvalidColor {
+            line: number,
+            token: color_segment.to_string(),
+        };
+        let (steps, direction) = hex.split_at(self.color_steps_digits.min(hex.len()));
+        let steps = usize::from_str_radix(steps, 16).map_err(|_| invalid())?;
+        let direction = direction
+            .get(..1)
+            .and_then(|d| d.parse::<usize>().ok())
+            .and_then(|idx| self.color_directions.get(idx))
+            .ok_or_else(invalid)?;
+        let decoded = Instruction {
+            direction: *direction,
+            steps,
+            color,
+        };
+        Ok(PlanLine { literal, decoded })
+    }
+
+    pub fn parse(&self, s: &str) -> Result<Vec<PlanLine>, ParseError> {
+        s.lines()
+            .enumerate()
+            .filter(|(_, line)| self.strict || !line.trim().is_empty())
+            .map(|(idx, line)| self.parse_line(idx + 1, line))
+            .collect()
+    }
+}
+
+pub fn split_plan(lines: &[PlanLine]) -> (Vec<Instruction>, Vec<Instruction>) {
+    lines
+        .iter()
+        .map(|line| (line.literal, line.decoded))
+        .unzip()
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Line {
+    pub start_x: i64,
+    pub start_y: i64,
+    pub end_x: i64,
+    pub end_y: i64,
+}
+
+impl Add<&Instruction> for &Line {
+    type Output = Line;
+    fn add(self, rhs: &Instruction) -> Self::Output {
+        let start_x = self.end_x;
+        let start_y = self.end_y;
+        let Point { x: end_x, y: end_y } =
+            Point::new(start_x, start_y).step(rhs.direction, rhs.steps as i64);
+        Self::Output {
+            start_x,
+            start_y,
+            end_x,
+            end_y,
+        }
+    }
+}
+
+impl Line {
+    pub fn contains_y(&self, y: i64) -> bool {
+        (self.start_y <= y && y <= self.end_y) || (self.end_y <= y && y <= self.start_y)
+    }
+
+    pub fn contains_x(&self, x: i64) -> bool {
+        (self.start_x <= x && x <= self.end_x) || (self.end_x <= x && x <= self.start_x)
+    }
+
+    pub fn contains_point(&self, x: i64, y: i64) -> bool {
+        self.contains_x(x) && self.contains_y(y)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EntranceShape {
+    Vert,
+    Down,
+    Up,
+}
+
+fn convert_to_lines(instructions: &[Instruction]) -> Vec<Line> {
+    let mut prev_line = Line::default();
+    let mut lines = Vec::with_capacity(instructions.len());
+    for instruction in instructions {
+        let line = &prev_line + instruction;
+        lines.push(line);
+        prev_line = line;
+    }
+    lines
+}
+
+fn get_bounds(lines: &[Line]) -> Line {
+    let start_x = lines
+        .iter()
+        .map(|line| i64::min(line.start_x, line.end_x))
+        .min()
+        .unwrap();
+    let start_y = lines
+        .iter()
+        .map(|line| i64::min(line.start_y, line.end_y))
+        .min()
+        .unwrap();
+    let end_x = lines
+        .iter()
+        .map(|line| i64::max(line.start_x, line.end_x))
+        .max()
+        .unwrap();
+    let end_y = lines
+        .iter()
+        .map(|line| i64::max(line.start_y, line.end_y))
+        .max()
+        .unwrap();
+    Line {
+        start_x,
+        start_y,
+        end_x,
+        end_y,
+    }
+}
+
+fn get_ranges_for_y(lines: &[Line], y: i64) -> [Vec<Interval<i64>>; 3] {
+    let mut generated_ranges = [Vec::new(), Vec::new(), Vec::new()];
+    for (idx, row) in [y - 1, y, y + 1].into_iter().enumerate() {
+        for line in lines.iter().filter(|line| line.contains_y(row)) {
+            generated_ranges[idx].push(Interval::inclusive(
+                i64::min(line.start_x, line.end_x),
+                i64::max(line.start_x, line.end_x),
+            ));
+        }
+        generated_ranges[idx] = coalesce(generated_ranges[idx].drain(..));
+    }
+    generated_ranges
+}
+
+fn get_shape_from_lines(lines: &[Line], x: i64, y: i64) -> Option<EntranceShape> {
+    if !lines.iter().any(|line| line.contains_point(x, y)) {
+        None
+    } else if lines.iter().any(|line| line.contains_point(x, y - 1)) {
+        if lines.iter().any(|line| line.contains_point(x, y + 1)) {
+            Some(EntranceShape::Vert)
+        } else {
+            Some(EntranceShape::Up)
+        }
+    } else if lines.iter().any(|line| line.contains_point(x, y + 1)) {
+        Some(EntranceShape::Down)
+    } else {
+        None
+    }
+}
+
+fn fill_in_ranges(lines: &[Line], ranges: &[Vec<Interval<i64>>], y: i64) -> usize {
+    let mut filled_in: usize = 0;
+    let mut in_shape = false;
+    let mut prev_range: Option<Interval<i64>> = None;
+    let lines = lines
+        .iter()
+        .filter(|line| line.contains_y(y))
+        .copied()
+        .collect::<Vec<Line>>();
+    for range in ranges[1].iter() {
+        if let Some(prev_range) = prev_range {
+            if in_shape {
+                filled_in += (range.start - prev_range.end) as usize;
+            }
+        }
+        let entrance_shape = get_shape_from_lines(&lines, range.start, y);
+        match entrance_shape {
+            Some(EntranceShape::Down) => match get_shape_from_lines(&lines, range.last(), y) {
+                Some(EntranceShape::Up) => {
+                    in_shape = !in_shape;
+                }
+                Some(EntranceShape::Down) => {}
+                None => {}
+                Some(EntranceShape::Vert) => {
+                    panic!("Should not get vert exit shape: ({}, {})", range.last(), y)
+                }
+            },
+            Some(EntranceShape::Up) => match get_shape_from_lines(&lines, range.last(), y) {
+                Some(EntranceShape::Down) => {
+                    in_shape = !in_shape;
+                }
+                Some(EntranceShape::Up) => {}
+                None => {}
+                Some(EntranceShape::Vert) => {
+                    panic!("Should not get vert exit shape: ({}, {})", range.last(), y)
+                }
+            },
+            None => {
+                if let Some(EntranceShape::Vert) = get_shape_from_lines(&lines, range.last(), y) {
+                    in_shape = !in_shape;
+                }
+            }
+            Some(EntranceShape::Vert) => in_shape = !in_shape,
+        }
+        filled_in += range.len() as usize;
+        prev_range = Some(*range);
+    }
+    filled_in
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Algorithm {
+    Shoelace,
+    Scanline,
+    Sweep,
+}
+
+impl TryFrom<&str> for Algorithm {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "shoelace" => Ok(Self::Shoelace),
+            "scanline" => Ok(Self::Scanline),
+            "sweep" => Ok(Self::Sweep),
+            _ => Err(format!(
+                "invalid algorithm {:?}, expected shoelace, scanline or sweep",
+                value
+            )),
+        }
+    }
+}
+
+fn scanline_area(instructions: &[Instruction]) -> usize {
+    let lines = convert_to_lines(instructions);
+    let bounds = get_bounds(&lines);
+    let mut total = 0;
+    for y in bounds.start_y..=bounds.end_y {
+        let ranges = get_ranges_for_y(&lines, y);
+        total += fill_in_ranges(&lines, &ranges, y);
+    }
+    total
+}
+
+// Merges consecutive moves in the same direction and cancels moves that
+// double straight back, leaving the same corners with fewer edges. A
+// cancelled backtrack's cells are dropped rather than kept as a spur:
+// `R 4, L 1` becomes `R 3` without the fourth cell, and `R 2, L 2` vanishes.
+pub fn simplify(instructions: &[Instruction]) -> Vec<Instruction> {
+    let mut result: Vec<Instruction> = Vec::with_capacity(instructions.len());
+    for instruction in instructions {
+        match result.last_mut() {
+            Some(prev) if prev.direction == instruction.direction => {
+                prev.steps += instruction.steps;
+            }
+            Some(prev) if prev.direction == instruction.direction.opposite() => {
+                if prev.steps > instruction.steps {
+                    prev.steps -= instruction.steps;
+                } else if prev.steps < instruction.steps {
+                    *prev = Instruction {
+                        steps: instruction.steps - prev.steps,
+                        ..*instruction
+                    };
+                } else {
+                    result.pop();
+                }
+            }
+            _ => result.push(*instruction),
+        }
+    }
+    result
+}
+
+fn shoelace_area(instructions: &[Instruction]) -> usize {
+    let instructions = simplify(instructions);
+    let lines = convert_to_lines(&instructions);
+    let twice_area: i64 = lines
+        .iter()
+        .map(|line| line.start_x * line.end_y - line.end_x * line.start_y)
+        .sum();
+    let perimeter: usize = instructions.iter().map(|i| i.steps).sum();
+    twice_area.unsigned_abs() as usize / 2 + perimeter / 2 + 1
+}
+
+fn crossing_ranges(lines: &[Line], y: i64) -> Vec<Interval<i64>> {
+    let mut xs = lines
+        .iter()
+        .filter(|line| line.start_x == line.end_x)
+        .filter(|line| {
+            let (low, high) = (
+                i64::min(line.start_y, line.end_y),
+                i64::max(line.start_y, line.end_y),
+            );
+            low <= y && y < high
+        })
+        .map(|line| line.start_x)
+        .collect::<Vec<i64>>();
+    xs.sort();
+    xs.chunks(2)
+        .filter(|pair| pair.len() == 2)
+        .map(|pair| Interval::inclusive(pair[0], pair[1]))
+        .collect()
+}
+
+fn covered_width(ranges: &[Interval<i64>]) -> usize {
+    ranges.iter().map(|range| range.len() as usize).sum()
+}
+
+fn sweep_area(instructions: &[Instruction]) -> usize {
+    let lines = convert_to_lines(&simplify(instructions));
+    let mut ys = lines.iter().map(|line| line.end_y).collect::<Vec<i64>>();
+    ys.sort();
+    ys.dedup();
+    let mut total = 0;
+    for (idx, &y) in ys.iter().enumerate() {
+        let below = crossing_ranges(&lines, y);
+        let mut row = crossing_ranges(&lines, y - 1);
+        row.extend(below.iter().copied());
+        row.extend(
+            lines
+                .iter()
+                .filter(|line| line.start_y == y && line.end_y == y)
+                .map(|line| {
+                    Interval::inclusive(
+                        i64::min(line.start_x, line.end_x),
+                        i64::max(line.start_x, line.end_x),
+                    )
+                }),
+        );
+        total += covered_width(&coalesce(row));
+        if let Some(&next) = ys.get(idx + 1) {
+            total += covered_width(&below) * (next - y - 1) as usize;
+        }
+    }
+    total
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+enum TrenchPiece {
+    Loop(Vec<Instruction>),
+    Spur(Vec<Instruction>),
+}
+
+fn split_loops(instructions: &[Instruction]) -> Vec<TrenchPiece> {
+    let mut pieces = Vec::new();
+    let mut position = Line::default();
+    let mut vertices = vec![(0, 0)];
+    let mut segment: Vec<Instruction> = Vec::new();
+    for instruction in instructions {
+        position = &position + instruction;
+        segment.push(*instruction);
+        let end = (position.end_x, position.end_y);
+        if let Some(idx) = vertices.iter().position(|vertex| *vertex == end) {
+            let closed = segment.split_off(idx);
+            if !segment.is_empty() {
+                pieces.push(TrenchPiece::Spur(segment));
+            }
+            pieces.push(TrenchPiece::Loop(closed));
+            segment = Vec::new();
+            vertices = vec![end];
+        } else {
+            vertices.push(end);
+        }
+    }
+    if !segment.is_empty() {
+        pieces.push(TrenchPiece::Spur(segment));
+    }
+    pieces
+}
+
+fn loop_area(instructions: &[Instruction], algorithm: Algorithm) -> usize {
+    match algorithm {
+        Algorithm::Shoelace => shoelace_area(instructions),
+        Algorithm::Scanline => scanline_area(instructions),
+        Algorithm::Sweep => sweep_area(instructions),
+    }
+}
+
+fn loop_areas(instructions: &[Instruction], algorithm: Algorithm) -> Vec<usize> {
+    split_loops(instructions)
+        .iter()
+        .filter_map(|piece| match piece {
+            TrenchPiece::Loop(instructions) => Some(loop_area(instructions, algorithm)),
+            TrenchPiece::Spur(_) => None,
+        })
+        .collect()
+}
+
+fn dig_area(instructions: &[Instruction], algorithm: Algorithm) -> usize {
+    let mut total = 1;
+    for piece in split_loops(instructions) {
+        total += match piece {
+            TrenchPiece::Loop(instructions) => loop_area(&instructions, algorithm) - 1,
+            TrenchPiece::Spur(instructions) => instructions.iter().map(|i| i.steps).sum(),
+        };
+    }
+    total
+}
+
+pub fn loop_report(instructions: &[Instruction], algorithm: Algorithm) -> String {
+    let mut out = String::new();
+    for (idx, area) in loop_areas(instructions, algorithm).into_iter().enumerate() {
+        out.push_str(&format!("  Loop {}: {}\n", idx + 1, area));
+    }
+    out.push_str(&format!("  Total: {}\n", dig_area(instructions, algorithm)));
+    out
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Trench {
+    pub vertices: Vec<(i64, i64)>,
+    pub bounds: Line,
+    pub perimeter: usize,
+    pub area: usize,
+}
+
+impl Trench {
+    pub fn new(instructions: &[Instruction], algorithm: Algorithm) -> Self {
+        let lines = convert_to_lines(instructions);
+        let mut vertices = vec![(0, 0)];
+        vertices.extend(lines.iter().map(|line| (line.end_x, line.end_y)));
+        Self {
+            vertices,
+            bounds: get_bounds(&lines),
+            perimeter: instructions.iter().map(|i| i.steps).sum(),
+            area: dig_area(instructions, algorithm),
+        }
+    }
+}
+
+impl Display for Trench {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "  Vertices: {}", self.vertices.len())?;
+        writeln!(
+            f,
+            "  Bounds: ({}, {}) to ({}, {})",
+            self.bounds.start_x, self.bounds.start_y, self.bounds.end_x, self.bounds.end_y
+        )?;
+        writeln!(f, "  Perimeter: {}", self.perimeter)?;
+        writeln!(f, "  Area: {}", self.area)
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct DigPlan {
+    pub part1: Trench,
+    pub part2: Trench,
+}
+
+impl DigPlan {
+    pub fn new(s: &str, algorithm: Algorithm) -> Result<Self, ParseError> {
+        Ok(Self::from_lines(&DigPlanParser::PART1.parse(s)?, algorithm))
+    }
+
+    pub fn from_lines(lines: &[PlanLine], algorithm: Algorithm) -> Self {
+        let (part1, part2) = split_plan(lines);
+        Self {
+            part1: Trench::new(&part1, algorithm),
+            part2: Trench::new(&part2, algorithm),
+        }
+    }
+}
+
+pub fn part1_with(s: &str, algorithm: Algorithm) -> Result<usize, ParseError> {
+    let (instructions, _) = split_plan(&DigPlanParser::PART1.parse(s)?);
+    Ok(dig_area(&instructions, algorithm))
+}
+
+pub fn part2_with(s: &str, algorithm: Algorithm) -> Result<usize, ParseError> {
+    let (_, instructions) = split_plan(&DigPlanParser::PART1.parse(s)?);
+    Ok(dig_area(&instructions, algorithm))
+}
+
+pub fn part1(s: &str) -> Result<usize, ParseError> {
+    part1_with(s, Algorithm::Shoelace)
+}
+
+pub fn part2(s: &str) -> Result<usize, ParseError> {
+    part2_with(s, Algorithm::Shoelace)
+}
+
+pub fn metrics_report(instructions: &[Instruction], algorithm: Algorithm) -> String {
+    let trench = Trench::new(instructions, algorithm);
+    let closed = trench.vertices.first() == trench.vertices.last();
+    let corners = simplify(instructions).len() - usize::from(!closed);
+    let boundary = trench.perimeter + usize::from(!closed);
+    let mut out = String::new();
+    out.push_str(&format!("  Perimeter: {}\n", trench.perimeter));
+    out.push_str(&format!("  Corners: {}\n", corners));
+    out.push_str(&format!(
+        "  Bounding box: {} x {}\n",
+        trench.bounds.end_x - trench.bounds.start_x + 1,
+        trench.bounds.end_y - trench.bounds.start_y + 1
+    ));
+    out.push_str(&format!("  Boundary cells: {}\n", boundary));
+    out.push_str(&format!("  Interior cells: {}\n", trench.area - boundary));
+    for direction in Direction::ALL {
+        let steps: usize = instructions
+            .iter()
+            .filter(|i| i.direction == direction)
+            .map(|i| i.steps)
+            .sum();
+        out.push_str(&format!("  {} steps: {}\n", direction, steps));
+    }
+    out
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Intersection {
+    first: usize,
+    second: usize,
+    first_instruction: Instruction,
+    second_instruction: Instruction,
+    x: i64,
+    y: i64,
+}
+
+impl Display for Intersection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "instruction {} ({:?}) intersects instruction {} ({:?}) at ({}, {})",
+            self.first + 1,
+            self.first_instruction,
+            self.second + 1,
+            self.second_instruction,
+            self.x,
+            self.y
+        )
+    }
+}
+
+impl Error for Intersection {}
+
+fn overlap(a: &Line, b: &Line) -> Option<(Interval<i64>, Interval<i64>)> {
+    let x = Interval::inclusive(
+        i64::max(a.start_x.min(a.end_x), b.start_x.min(b.end_x)),
+        i64::min(a.start_x.max(a.end_x), b.start_x.max(b.end_x)),
+    );
+    let y = Interval::inclusive(
+        i64::max(a.start_y.min(a.end_y), b.start_y.min(b.end_y)),
+        i64::min(a.start_y.max(a.end_y), b.start_y.max(b.end_y)),
+    );
+    if !x.is_empty() && !y.is_empty() {
+        Some((x, y))
+    } else {
+        None
+    }
+}
+
+fn is_endpoint(line: &Line, x: i64, y: i64) -> bool {
+    (line.start_x, line.start_y) == (x, y) || (line.end_x, line.end_y) == (x, y)
+}
+
+// Edges may meet at a corner they share, which is how one loop closes and
+// the next begins. Crossings, T-junctions and overlaps are reported.
+fn check_pair(
+    lines: &[Line],
+    instructions: &[Instruction],
+    i: usize,
+    j: usize,
+) -> Option<Intersection> {
+    let (first, second) = (usize::min(i, j), usize::max(i, j));
+    let (x, y) = overlap(&lines[first], &lines[second])?;
+    let single_point = x.len() == 1 && y.len() == 1;
+    if single_point
+        && is_endpoint(&lines[first], x.start, y.start)
+        && is_endpoint(&lines[second], x.start, y.start)
+    {
+        return None;
+    }
+    Some(Intersection {
+        first,
+        second,
+        first_instruction: instructions[first],
+        second_instruction: instructions[second],
+        x: x.start,
+        y: y.start,
+    })
+}
+
+fn find_self_intersection(instructions: &[Instruction]) -> Option<Intersection> {
+    let lines = convert_to_lines(instructions);
+    let is_horizontal = |line: &Line| line.start_y == line.end_y;
+    let mut events: Vec<(i64, u8, usize)> = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if is_horizontal(line) {
+            events.push((line.start_x.min(line.end_x), 0, idx));
+            events.push((line.start_x.max(line.end_x), 2, idx));
+        } else {
+            events.push((line.start_x, 1, idx));
+        }
+    }
+    events.sort();
+    let mut active: BTreeMap<i64, Vec<usize>> = BTreeMap::new();
+    let mut found: Vec<Intersection> = Vec::new();
+    for (_, kind, idx) in events {
+        let line = lines[idx];
+        match kind {
+            0 => {
+                let same_row = active.entry(line.start_y).or_default();
+                for &other in same_row.iter() {
+                    found.extend(check_pair(&lines, instructions, idx, other));
+                }
+                same_row.push(idx);
+            }
+            1 => {
+                let low = line.start_y.min(line.end_y);
+                let high = line.start_y.max(line.end_y);
+                for (_, horizontals) in active.range(low..=high) {
+                    for &other in horizontals {
+                        found.extend(check_pair(&lines, instructions, idx, other));
+                    }
+                }
+            }
+            _ => {
+                active
+                    .entry(line.start_y)
+                    .or_default()
+                    .retain(|&other| other != idx);
+            }
+        }
+    }
+    let mut verticals: Vec<usize> = (0..lines.len())
+        .filter(|&idx| !is_horizontal(&lines[idx]))
+        .collect();
+    verticals.sort_by_key(|&idx| (lines[idx].start_x, lines[idx].start_y.min(lines[idx].end_y)));
+    for (pos, &idx) in verticals.iter().enumerate() {
+        for &other in verticals[pos + 1..]
+            .iter()
+            .take_while(|&&other| lines[other].start_x == lines[idx].start_x)
+        {
+            found.extend(check_pair(&lines, instructions, idx, other));
+        }
+    }
+    found
+        .into_iter()
+        .min_by_key(|intersection| (intersection.second, intersection.first))
+}
+
+pub fn validate(instructions: &[Instruction]) -> Result<(), Intersection> {
+    match find_self_intersection(instructions) {
+        Some(intersection) => Err(intersection),
+        None => Ok(()),
+    }
+}
+
+pub fn render_trench(instructions: &[Instruction]) -> String {
+    let lines = convert_to_lines(instructions);
+    let bounds = get_bounds(&lines);
+    let width = (bounds.end_x - bounds.start_x + 1) as usize;
+    let height = (bounds.end_y - bounds.start_y + 1) as usize;
+    let mut cells: Vec<Vec<Option<Color>>> = vec![vec![None; width]; height];
+    for (line, instruction) in lines.iter().zip(instructions) {
+        for y in i64::min(line.start_y, line.end_y)..=i64::max(line.start_y, line.end_y) {
+            for x in i64::min(line.start_x, line.end_x)..=i64::max(line.start_x, line.end_x) {
+                cells[(y - bounds.start_y) as usize][(x - bounds.start_x) as usize] =
+                    Some(instruction.color);
+            }
+        }
+    }
+    cells
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| match cell {
+                    Some(Color { r, g, b }) => format!("\x1b[38;2;{};{};{}m#\x1b[0m", r, g, b),
+                    None => ".".to_string(),
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SvgStyle {
+    pub fill: bool,
+    pub colors: bool,
+}
+
+// Longest side of the drawing in pixels.
+const SVG_SIZE: f64 = 800.0;
+
+// The path through the middle of each trench cube, in plan coordinates, so
+// the viewBox does the scaling and both parts fit the same canvas.
+pub fn render_svg(instructions: &[Instruction], style: SvgStyle) -> String {
+    let lines = convert_to_lines(instructions);
+    let bounds = get_bounds(&lines);
+    let width = (bounds.end_x - bounds.start_x + 1) as f64;
+    let height = (bounds.end_y - bounds.start_y + 1) as f64;
+    let scale = SVG_SIZE / width.max(height);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"{} {} {} {}\">\n",
+        width * scale,
+        height * scale,
+        bounds.start_x as f64 - 0.5,
+        bounds.start_y as f64 - 0.5,
+        width,
+        height
+    );
+    let points = lines
+        .iter()
+        .map(|line| format!("{},{}", line.start_x, line.start_y))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let fill = if style.fill { "#f4c27a" } else { "none" };
+    let stroke = if style.colors { "none" } else { "#202020" };
+    svg += &format!(
+        "  <polygon points=\"{}\" fill=\"{}\" stroke=\"{}\" stroke-width=\"1\" vector-effect=\"non-scaling-stroke\"/>\n",
+        points, fill, stroke
+    );
+    if style.colors {
+        for (line, instruction) in lines.iter().zip(instructions) {
+            svg += &format!(
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"2\" vector-effect=\"non-scaling-stroke\"/>\n",
+                line.start_x, line.start_y, line.end_x, line.end_y, instruction.color
+            );
+        }
+    }
+    svg + "</svg>\n"
+}
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Parsed<'a> = Vec<PlanLine>;
+    type Error = ParseError;
+
+    fn day() -> u8 {
+        18
+    }
+
+    fn parse(input: &str) -> Result<Vec<PlanLine>, ParseError> {
+        DigPlanParser::PART1.parse(input)
+    }
+
+    fn part1(plan: &Self::Parsed<'_>) -> impl Answer {
+        dig_area(&split_plan(plan).0, Algorithm::Shoelace)
+    }
+
+    fn part2(plan: &Self::Parsed<'_>) -> impl Answer {
+        dig_area(&split_plan(plan).1, Algorithm::Shoelace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn literal(s: &str) -> Vec<Instruction> {
+        split_plan(&DigPlanParser::PART1.parse(s).unwrap()).0
+    }
+
+    fn decoded(s: &str) -> Vec<Instruction> {
+        split_plan(&DigPlanParser::PART1.parse(s).unwrap()).1
+    }
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day18/example.txt");
+
+    #[test]
+    fn test_algorithm_names() {
+        assert_eq!(Algorithm::try_from("sweep"), Ok(Algorithm::Sweep));
+        assert_eq!(
+            Algorithm::try_from("fastest").unwrap_err(),
+            "invalid algorithm \"fastest\", expected shoelace, scanline or sweep"
+        );
+    }
+
+    #[test]
+    fn test_custom_layout() {
+        let parser = DigPlanParser {
+            direction_column: 2,
+            steps_column: 1,
+            color_column: 0,
+            steps_radix: 16,
+            ..DigPlanParser::PART1
+        };
+        let input = "(#000000) a R\n(#000000) A D\n(#000000) a L\n(#000000) A U";
+        let instructions = split_plan(&parser.parse(input).unwrap()).0;
+        assert_eq!(instructions[0].steps, 10);
+        assert_eq!(instructions[1].direction, Direction::Down);
+        assert_eq!(dig_area(&instructions, Algorithm::Shoelace), 121);
+        let decoder = DigPlanParser {
+            color_steps_digits: 4,
+            color_directions: [
+                Direction::Up,
+                Direction::Right,
+                Direction::Down,
+                Direction::Left,
+            ],
+            ..DigPlanParser::PART1
+        };
+        let instruction = decoder.parse_line(1, "R 1 (#00022f)").unwrap().decoded;
+        assert_eq!(
+            (instruction.direction, instruction.steps),
+            (Direction::Down, 2)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |parser: DigPlanParser, input: &str| parser.parse(input).unwrap_err();
+        assert_eq!(
+            parse(DigPlanParser::PART1, "R 6 (#70c710)\nX 5 (#0dc571)"),
+            ParseError::UnknownDirection {
+                line: 2,
+                token: "X".to_string()
+            }
+        );
+        assert_eq!(
+            parse(DigPlanParser::PART1, "R 6"),
+            ParseError::MissingColumn { line: 1, column: 2 }
+        );
+        assert_eq!(
+            parse(DigPlanParser::PART1, "R 6 (#70c7)"),
+            ParseError::InvalidColor {
+                line: 1,
+                token: "(#70c7)".to_string()
+            }
+        );
+        assert_eq!(
+            parse(DigPlanParser::PART1, "R six (#70c710)").to_string(),
+            "line 1: invalid step count \"six\""
+        );
+        let lenient = "r 6 #70c710\n\nD 5 (#0dc571) extra\n";
+        assert_eq!(literal(lenient).len(), 2);
+        let strict = DigPlanParser {
+            strict: true,
+            ..DigPlanParser::PART1
+        };
+        assert!(matches!(
+            parse(strict, lenient),
+            ParseError::InvalidColor { line: 1, .. }
+        ));
+        assert!(matches!(
+            parse(strict, "r 6 (#70c710)"),
+            ParseError::UnknownDirection { line: 1, .. }
+        ));
+        assert_eq!(
+            parse(strict, "D 5 (#0dc571) extra"),
+            ParseError::ExtraColumns {
+                line: 1,
+                expected: 3,
+                found: 4
+            }
+        );
+        assert!(strict.parse(TEST_INPUT).is_ok());
+    }
+
+    #[test]
+    fn test_dig_plan() {
+        let plan = DigPlan::new(TEST_INPUT, Algorithm::Shoelace).unwrap();
+        assert_eq!(plan.part1.perimeter, 38);
+        assert_eq!(plan.part1.vertices.len(), 15);
+        assert_eq!(plan.part1.vertices.first(), plan.part1.vertices.last());
+        assert_eq!(
+            plan.part1.bounds,
+            Line {
+                start_x: 0,
+                start_y: 0,
+                end_x: 6,
+                end_y: 9
+            }
+        );
+    }
+
+    #[test]
+    fn test_metrics_report() {
+        assert_eq!(
+            metrics_report(&literal(TEST_INPUT), Algorithm::Shoelace),
+            "  Perimeter: 38
+  Corners: 14
+  Bounding box: 7 x 10
+  Boundary cells: 38
+  Interior cells: 24
+  U steps: 9
+  D steps: 9
+  L steps: 10
+  R steps: 10
+"
+        );
+    }
+
+    #[test]
+    fn test_simplify() {
+        let instructions = literal(
+            "R 2 (#000000)
+R 3 (#000000)
+D 4 (#000000)
+U 1 (#000000)
+L 2 (#000000)
+R 2 (#000000)
+L 5 (#000000)
+U 3 (#000000)",
+        );
+        let simplified = simplify(&instructions);
+        assert_eq!(
+            simplified
+                .iter()
+                .map(|i| (i.direction, i.steps))
+                .collect::<Vec<_>>(),
+            vec![
+                (Direction::Right, 5),
+                (Direction::Down, 3),
+                (Direction::Left, 5),
+                (Direction::Up, 3)
+            ]
+        );
+        assert_eq!(shoelace_area(&instructions), 24);
+        let sample = literal(TEST_INPUT);
+        assert_eq!(simplify(&sample), sample);
+    }
+
+    #[test]
+    fn test_colors_preserved() {
+        let instructions = literal(TEST_INPUT);
+        assert_eq!(
+            instructions[0].color,
+            Color {
+                r: 0x70,
+                g: 0xc7,
+                b: 0x10
+            }
+        );
+        let color_instructions = decoded(TEST_INPUT);
+        assert_eq!(color_instructions[1].color, instructions[1].color);
+    }
+
+    #[test]
+    fn test_render_trench() {
+        let instructions = literal("R 2 (#ff0000)\nD 2 (#00ff00)\nL 2 (#0000f3)\nU 2 (#ffff03)");
+        let paint = |r: u8, g: u8, b: u8| format!("\x1b[38;2;{};{};{}m#\x1b[0m", r, g, b);
+        let (red, green, blue, white) = (
+            paint(255, 0, 0),
+            paint(0, 255, 0),
+            paint(0, 0, 243),
+            paint(255, 255, 3),
+        );
+        assert_eq!(
+            render_trench(&instructions),
+            format!("{white}{red}{green}\n{white}.{green}\n{white}{blue}{blue}")
+        );
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let (instructions, _) = split_plan(&DigPlanParser::PART1.parse(TEST_INPUT).unwrap());
+        let svg = render_svg(&instructions, SvgStyle::default());
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"560\" height=\"800\" viewBox=\"-0.5 -0.5 7 10\">"
+        ));
+        assert!(svg.contains("<polygon points=\"0,0 6,0 6,5 4,5 4,7 6,7 6,9 1,9 1,7 0,7 0,5 2,5 2,2 0,2\" fill=\"none\""));
+        assert!(!svg.contains("<line"));
+        let svg = render_svg(
+            &instructions,
+            SvgStyle {
+                fill: true,
+                colors: true,
+            },
+        );
+        assert_eq!(svg.matches("<line").count(), instructions.len());
+        assert!(svg.contains("stroke=\"#70c710\""));
+        assert!(svg.contains("fill=\"#f4c27a\" stroke=\"none\""));
+    }
+
+    #[test]
+    fn test_single_loop() {
+        let instructions = literal(TEST_INPUT);
+        assert_eq!(
+            split_loops(&instructions),
+            vec![TrenchPiece::Loop(instructions.clone())]
+        );
+        assert_eq!(loop_areas(&instructions, Algorithm::Shoelace), vec![62]);
+    }
+
+    #[test]
+    fn test_multiple_loops_with_spur() {
+        let input = "R 2 (#000000)
+D 2 (#000000)
+L 2 (#000000)
+U 2 (#000000)
+L 3 (#000000)
+L 2 (#000000)
+U 1 (#000000)
+R 2 (#000000)
+D 1 (#000000)
+D 4 (#000000)";
+        let instructions = literal(input);
+        let pieces = split_loops(&instructions);
+        assert_eq!(pieces.len(), 4);
+        assert!(matches!(pieces[0], TrenchPiece::Loop(_)));
+        assert!(matches!(pieces[1], TrenchPiece::Spur(_)));
+        assert!(matches!(pieces[2], TrenchPiece::Loop(_)));
+        assert!(matches!(pieces[3], TrenchPiece::Spur(_)));
+        assert_eq!(validate(&instructions), Ok(()));
+        for algorithm in [Algorithm::Shoelace, Algorithm::Scanline] {
+            assert_eq!(loop_areas(&instructions, algorithm), vec![9, 6]);
+            assert_eq!(dig_area(&instructions, algorithm), 9 + 3 + 5 + 4);
+        }
+    }
+
+    #[test]
+    fn test_no_self_intersection() {
+        assert_eq!(validate(&literal(TEST_INPUT)), Ok(()));
+        assert_eq!(validate(&decoded(TEST_INPUT)), Ok(()));
+    }
+
+    #[test]
+    fn test_crossing_detected() {
+        let input = "R 4 (#000000)
+D 2 (#000000)
+L 2 (#000000)
+U 4 (#000000)
+L 2 (#000000)
+D 2 (#000000)";
+        let err = validate(&literal(input)).unwrap_err();
+        assert_eq!((err.first, err.second, err.x, err.y), (0, 3, 2, 0));
+        assert_eq!(
+            err.to_string(),
+            "instruction 1 (R 4) intersects instruction 4 (U 4) at (2, 0)"
+        );
+    }
+
+    #[test]
+    fn test_backtrack_and_collinear_overlap_detected() {
+        let backtrack = literal("R 4 (#000000)\nL 2 (#000000)");
+        let err = validate(&backtrack).unwrap_err();
+        assert_eq!((err.first, err.second), (0, 1));
+        let overlap =
+            literal("D 4 (#000000)\nR 1 (#000000)\nU 2 (#000000)\nL 1 (#000000)\nU 1 (#000000)");
+        let err = validate(&overlap).unwrap_err();
+        assert_eq!((err.first, err.second, err.x, err.y), (0, 3, 0, 2));
+    }
+
+    #[test]
+    fn test_shoelace_matches_scanline() {
+        for input in [
+            "R 2 (#000000)\nD 2 (#000000)\nL 2 (#000000)\nU 2 (#000000)",
+            "R 4 (#000000)\nD 4 (#000000)\nL 2 (#000000)\nU 2 (#000000)\nL 2 (#000000)\nU 2 (#000000)",
+            "L 3 (#000000)\nU 1 (#000000)\nR 1 (#000000)\nU 2 (#000000)\nR 2 (#000000)\nD 3 (#000000)",
+        ] {
+            let instructions = literal(input);
+            assert_eq!(shoelace_area(&instructions), scanline_area(&instructions));
+            assert_eq!(shoelace_area(&instructions), sweep_area(&instructions));
+        }
+    }
+}
@@ -1,356 +1,125 @@
-use std::{fmt::Debug, fs::read_to_string, ops::Add};
-
-#[derive(PartialEq, Eq, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+use aoc_core::timing::Timings;
+use aoc_input::cli::{Answers, DayArgs};
+use clap::Parser;
+use day18::*;
+use std::{fs, path::PathBuf};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    day: DayArgs,
+    /// Solve with shoelace, scanline or sweep
+    #[arg(long, default_value = "shoelace", value_parser = |s: &str| Algorithm::try_from(s))]
+    algo: Algorithm,
+    /// Parse directions and colors strictly
+    #[arg(long)]
+    strict: bool,
+    /// Draw the part 1 trench
+    #[arg(long)]
+    render: bool,
+    /// Report the loops in each dig plan
+    #[arg(long)]
+    loops: bool,
+    /// Report area and perimeter metrics for each dig plan
+    #[arg(long)]
+    metrics: bool,
+    /// Summarize both trenches
+    #[arg(long)]
+    summary: bool,
+    /// Draw the trench as an SVG polygon, for part 2 with `--part 2`
+    #[arg(long, value_name = "PATH")]
+    svg: Option<PathBuf>,
+    /// Fill the inside of the SVG polygon
+    #[arg(long, requires = "svg")]
+    fill: bool,
+    /// Stroke each SVG edge with its instruction's color
+    #[arg(long, requires = "svg")]
+    colors: bool,
 }
 
-impl Debug for Direction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(match self {
-            Direction::Up => "U",
-            Direction::Down => "D",
-            Direction::Left => "L",
-            Direction::Right => "R",
+fn main() {
+    let cli = Cli::parse();
+    let _log = cli.day.init_logging();
+    let args = &cli.day;
+    let mut timings = Timings::new(args.time);
+    let input = timings
+        .time("read", || args.load(2023, 18, env!("CARGO_MANIFEST_DIR")))
+        .unwrap_or_else(|err| {
+            eprintln!("Could not load input: {}", err);
+            std::process::exit(1);
+        });
+    let algorithm = cli.algo;
+    let lines = timings
+        .time("parse", || {
+            DigPlanParser {
+                strict: cli.strict,
+                ..DigPlanParser::PART1
+            }
+            .parse(&input)
         })
-    }
-}
-
-impl From<&str> for Direction {
-    fn from(value: &str) -> Self {
-        match value {
-            "U" => Direction::Up,
-            "D" => Direction::Down,
-            "L" => Direction::Left,
-            "R" => Direction::Right,
-            _ => panic!("Unknown direction!"),
-        }
-    }
-}
-
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
-        match value {
-            '0' => Direction::Right,
-            '1' => Direction::Down,
-            '2' => Direction::Left,
-            '3' => Direction::Up,
-            _ => panic!("Unknown direction!"),
-        }
-    }
-}
-
-#[derive(PartialEq, Eq, Clone, Copy)]
-struct Instruction {
-    direction: Direction,
-    steps: usize,
-}
-
-impl From<&str> for Instruction {
-    fn from(value: &str) -> Self {
-        let segments = value.split_whitespace().collect::<Vec<&str>>();
-        Self {
-            direction: Direction::from(segments[0]),
-            steps: segments[1].parse().unwrap(),
-        }
-    }
-}
-
-impl Debug for Instruction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{:?} {:?}", self.direction, self.steps))
-    }
-}
-
-impl Instruction {
-    fn from_color(value: &str) -> Self {
-        let color = value.split_whitespace().nth(2).unwrap().split_at(2).1;
-        let (steps, direction) = color.split_at(5);
-        let steps = usize::from_str_radix(steps, 16).unwrap();
-        let direction = Direction::from(direction.chars().next().unwrap());
-        Self { direction, steps }
-    }
-}
-
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
-struct Line {
-    start_x: i64,
-    start_y: i64,
-    end_x: i64,
-    end_y: i64,
-}
-
-impl Add<&Instruction> for &Line {
-    type Output = Line;
-    fn add(self, rhs: &Instruction) -> Self::Output {
-        let start_x = self.end_x;
-        let start_y = self.end_y;
-        let (end_x, end_y) = match rhs.direction {
-            Direction::Up => (start_x, start_y - rhs.steps as i64),
-            Direction::Down => (start_x, start_y + rhs.steps as i64),
-            Direction::Left => (start_x - rhs.steps as i64, start_y),
-            Direction::Right => (start_x + rhs.steps as i64, start_y),
-        };
-        Self::Output {
-            start_x,
-            start_y,
-            end_x,
-            end_y,
-        }
-    }
-}
-
-impl Line {
-    pub fn contains_y(&self, y: i64) -> bool {
-        (self.start_y <= y && y <= self.end_y) || (self.end_y <= y && y <= self.start_y)
-    }
-
-    pub fn contains_x(&self, x: i64) -> bool {
-        (self.start_x <= x && x <= self.end_x) || (self.end_x <= x && x <= self.start_x)
-    }
-
-    pub fn contains_point(&self, x: i64, y: i64) -> bool {
-        self.contains_x(x) && self.contains_y(y)
-    }
-}
-
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum EntranceShape {
-    Vert,
-    Down,
-    Up,
-}
-
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
-struct Range {
-    start: i64,
-    end: i64,
-}
-
-impl Range {
-    pub fn new(start: i64, end: i64) -> Self {
-        Self { start, end }
-    }
-
-    pub fn overlaps(&self, other: &Range) -> bool {
-        (self.start <= other.start && other.start <= self.end)
-            || (self.start <= other.end && other.end <= self.end)
-            || (other.start <= self.start && self.start <= other.end)
-            || (other.start <= self.end && self.end <= other.end)
-    }
-}
-
-fn collapse_ranges(ranges: &mut [Range]) -> Vec<Range> {
-    if ranges.is_empty() {
-        return vec![];
-    }
-    let mut result = Vec::with_capacity(ranges.len());
-    ranges.sort();
-    let mut prev = ranges[0];
-    for range in ranges.iter().skip(1) {
-        if prev.overlaps(range) {
-            prev = Range {
-                start: i64::min(range.start, prev.start),
-                end: i64::max(range.end, prev.end),
-            };
+        .unwrap_or_else(|err| {
+            eprintln!("Invalid input: {}", err);
+            std::process::exit(1);
+        });
+    let (instructions1, instructions2) = split_plan(&lines);
+    if cli.render {
+        println!("{}", render_trench(&instructions1));
+        return;
+    }
+    if let Some(path) = &cli.svg {
+        let instructions = if args.runs(1) {
+            &instructions1
         } else {
-            result.push(prev);
-            prev = *range;
-        }
-    }
-    result.push(prev);
-    result
-}
-
-fn parse_instructions(s: &str) -> Vec<Instruction> {
-    s.lines().map(Instruction::from).collect()
-}
-
-fn parse_color_instructions(s: &str) -> Vec<Instruction> {
-    s.lines().map(Instruction::from_color).collect()
-}
-
-fn convert_to_lines(instructions: &[Instruction]) -> Vec<Line> {
-    let mut prev_line = Line::default();
-    let mut lines = Vec::with_capacity(instructions.len());
-    for instruction in instructions {
-        let line = &prev_line + instruction;
-        lines.push(line);
-        prev_line = line;
-    }
-    lines
-}
-
-fn get_bounds(lines: &[Line]) -> Line {
-    let start_x = lines
-        .iter()
-        .map(|line| i64::min(line.start_x, line.end_x))
-        .min()
-        .unwrap();
-    let start_y = lines
-        .iter()
-        .map(|line| i64::min(line.start_y, line.end_y))
-        .min()
-        .unwrap();
-    let end_x = lines
-        .iter()
-        .map(|line| i64::max(line.start_x, line.end_x))
-        .max()
-        .unwrap();
-    let end_y = lines
-        .iter()
-        .map(|line| i64::max(line.start_y, line.end_y))
-        .max()
-        .unwrap();
-    Line {
-        start_x,
-        start_y,
-        end_x,
-        end_y,
-    }
-}
-
-fn get_ranges_for_y(lines: &[Line], y: i64) -> [Vec<Range>; 3] {
-    let mut generated_ranges = [Vec::new(), Vec::new(), Vec::new()];
-    for (idx, row) in [y - 1, y, y + 1].into_iter().enumerate() {
-        for line in lines.iter().filter(|line| line.contains_y(row)) {
-            generated_ranges[idx].push(Range::new(
-                i64::min(line.start_x, line.end_x),
-                i64::max(line.start_x, line.end_x),
-            ));
-        }
-        generated_ranges[idx] = collapse_ranges(&mut generated_ranges[idx]);
-    }
-    generated_ranges
-}
-
-fn get_shape_from_lines(lines: &[Line], x: i64, y: i64) -> Option<EntranceShape> {
-    if !lines.iter().any(|line| line.contains_point(x, y)) {
-        None
-    } else if lines.iter().any(|line| line.contains_point(x, y - 1)) {
-        if lines.iter().any(|line| line.contains_point(x, y + 1)) {
-            Some(EntranceShape::Vert)
-        } else {
-            Some(EntranceShape::Up)
+            &instructions2
+        };
+        let style = SvgStyle {
+            fill: cli.fill,
+            colors: cli.colors,
+        };
+        if let Err(err) = fs::write(path, render_svg(instructions, style)) {
+            eprintln!("Could not write {}: {}", path.display(), err);
+            std::process::exit(1);
         }
-    } else if lines.iter().any(|line| line.contains_point(x, y + 1)) {
-        Some(EntranceShape::Down)
-    } else {
-        None
+        println!("Saved {}", path.display());
+        return;
     }
-}
-
-fn fill_in_ranges(lines: &[Line], ranges: &[Vec<Range>], y: i64) -> usize {
-    let mut filled_in: usize = 0;
-    let mut in_shape = false;
-    let mut prev_range: Option<Range> = None;
-    let lines = lines
-        .iter()
-        .filter(|line| line.contains_y(y))
-        .copied()
-        .collect::<Vec<Line>>();
-    for range in ranges[1].iter() {
-        if let Some(prev_range) = prev_range {
-            if in_shape {
-                filled_in += (range.start - prev_range.end - 1) as usize;
-            }
+    for instructions in [&instructions1, &instructions2] {
+        if let Err(err) = validate(instructions) {
+            eprintln!("Invalid dig plan: {}", err);
+            std::process::exit(1);
         }
-        let entrance_shape = get_shape_from_lines(&lines, range.start, y);
-        match entrance_shape {
-            Some(EntranceShape::Down) => match get_shape_from_lines(&lines, range.end, y) {
-                Some(EntranceShape::Up) => {
-                    in_shape = !in_shape;
-                }
-                Some(EntranceShape::Down) => {}
-                None => {}
-                Some(EntranceShape::Vert) => {
-                    panic!("Should not get vert exit shape: ({}, {})", range.end, y)
-                }
-            },
-            Some(EntranceShape::Up) => match get_shape_from_lines(&lines, range.end, y) {
-                Some(EntranceShape::Down) => {
-                    in_shape = !in_shape;
-                }
-                Some(EntranceShape::Up) => {}
-                None => {}
-                Some(EntranceShape::Vert) => {
-                    panic!("Should not get vert exit shape: ({}, {})", range.end, y)
-                }
-            },
-            None => {
-                if let Some(EntranceShape::Vert) = get_shape_from_lines(&lines, range.end, y) {
-                    in_shape = !in_shape;
-                }
-            }
-            Some(EntranceShape::Vert) => in_shape = !in_shape,
-        }
-        filled_in += (range.end - range.start + 1) as usize;
-        prev_range = Some(*range);
-    }
-    filled_in
-}
-
-fn part1(s: &str) -> usize {
-    let instructions = parse_instructions(s);
-    let lines = convert_to_lines(&instructions);
-    let bounds = get_bounds(&lines);
-    let mut total = 0;
-    for y in bounds.start_y..=bounds.end_y {
-        let ranges = get_ranges_for_y(&lines, y);
-        total += fill_in_ranges(&lines, &ranges, y);
     }
-    total
-}
-
-fn part2(s: &str) -> usize {
-    let instructions = parse_color_instructions(s);
-    let lines = convert_to_lines(&instructions);
-    let bounds = get_bounds(&lines);
-    let mut total = 0;
-    for y in bounds.start_y..=bounds.end_y {
-        let ranges = get_ranges_for_y(&lines, y);
-        total += fill_in_ranges(&lines, &ranges, y);
+    if cli.loops {
+        println!("Part 1:");
+        print!("{}", loop_report(&instructions1, algorithm));
+        println!("Part 2:");
+        print!("{}", loop_report(&instructions2, algorithm));
+        return;
+    }
+    if cli.metrics {
+        println!("Part 1:");
+        print!("{}", metrics_report(&instructions1, algorithm));
+        println!("Part 2:");
+        print!("{}", metrics_report(&instructions2, algorithm));
+        return;
+    }
+    if cli.summary {
+        let plan = DigPlan {
+            part1: Trench::new(&instructions1, algorithm),
+            part2: Trench::new(&instructions2, algorithm),
+        };
+        print!("Part 1:\n{}Part 2:\n{}", plan.part1, plan.part2);
+        return;
     }
-    total
-}
-
-fn main() {
-    let input = read_to_string("input.txt").unwrap();
-    let answer1 = part1(&input);
-    println!("Part 1: {}", answer1);
-    let answer2 = part2(&input);
-    println!("Part 2: {}", answer2);
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::*;
-
-    const TEST_INPUT: &str = "R 6 (#70c710)
-D 5 (#0dc571)
-L 2 (#5713f0)
-D 2 (#d2c081)
-R 2 (#59c680)
-D 2 (#411b91)
-L 5 (#8ceee2)
-U 2 (#caa173)
-L 1 (#1b58a2)
-U 2 (#caa171)
-R 2 (#7807d2)
-U 3 (#a77fa3)
-L 2 (#015232)
-U 2 (#7a21e3)";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 62);
+    let mut answers = Answers::new(args);
+    if args.runs(1) {
+        let trench = timings.time("part 1", || Trench::new(&instructions1, algorithm));
+        answers.report(1, trench.area);
     }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 952408144115);
+    if args.runs(2) {
+        let trench = timings.time("part 2", || Trench::new(&instructions2, algorithm));
+        answers.report(2, trench.area);
     }
+    answers.finish();
+    timings.print();
 }
@@ -0,0 +1,2103 @@
+use aoc_core::{Answer, Interval, Solver};
+use petgraph::{
+    dot::{Config, Dot},
+    graph::{DiGraph, NodeIndex},
+    visit::EdgeRef,
+    Direction,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    error::Error,
+    fmt::{Debug, Display},
+    io::{self, BufRead, Write},
+    rc::Rc,
+    sync::Arc,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseErrorKind {
+    UnknownField(String),
+    UnknownOperator(String),
+    InvalidValue(String),
+    MissingTarget,
+    MalformedWorkflow,
+    MalformedPart,
+    UnknownWorkflow(String),
+    DuplicateWorkflow(String),
+    MissingStart,
+    MissingParts,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownField(token) => write!(f, "unknown field {:?}", token),
+            Self::UnknownOperator(token) => write!(f, "unknown operator {:?}", token),
+            Self::InvalidValue(token) => write!(f, "invalid value {:?}", token),
+            Self::MissingTarget => write!(f, "rule has no target"),
+            Self::MalformedWorkflow => write!(f, "expected name{{rule,...}}"),
+            Self::MalformedPart => write!(f, "expected {{name=value,...}}"),
+            Self::UnknownWorkflow(name) => write!(f, "unknown workflow {:?}", name),
+            Self::DuplicateWorkflow(name) => write!(f, "workflow {:?} defined twice", name),
+            Self::MissingStart => write!(f, "no workflow named \"in\""),
+            Self::MissingParts => write!(f, "missing blank line before parts"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    workflow: Option<Arc<str>>,
+    rule: Option<usize>,
+    span: std::ops::Range<usize>,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, span: std::ops::Range<usize>) -> Self {
+        Self {
+            kind,
+            workflow: None,
+            rule: None,
+            span,
+        }
+    }
+
+    pub fn shifted(self, offset: usize) -> Self {
+        Self {
+            span: self.span.start + offset..self.span.end + offset,
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "bytes {}..{}: {}",
+            self.span.start, self.span.end, self.kind
+        )?;
+        if let Some(rule) = self.rule {
+            write!(f, " in rule {}", rule)?;
+        }
+        if let Some(workflow) = &self.workflow {
+            write!(f, " in workflow {}", workflow)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseErrors(Vec<ParseError>);
+
+impl Display for ParseErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, err) in self.0.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", err)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseErrors {}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+enum Operator {
+    Greater,
+    Less,
+    GreaterEqual,
+    LessEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Operator {
+    const ALL: [Operator; 6] = [
+        Operator::GreaterEqual,
+        Operator::LessEqual,
+        Operator::Equal,
+        Operator::NotEqual,
+        Operator::Greater,
+        Operator::Less,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Operator::Greater => ">",
+            Operator::Less => "<",
+            Operator::GreaterEqual => ">=",
+            Operator::LessEqual => "<=",
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+        }
+    }
+
+    pub fn parse_prefix(value: &str) -> Result<Self, ParseErrorKind> {
+        Self::ALL
+            .into_iter()
+            .find(|operator| value.starts_with(operator.symbol()))
+            .ok_or_else(|| {
+                ParseErrorKind::UnknownOperator(value.chars().take(1).collect::<String>())
+            })
+    }
+}
+
+fn is_category_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub struct Condition<T>
+where
+    T: Clone + PartialEq + Eq + Debug,
+{
+    field: T,
+    operator: Operator,
+    value: u16,
+}
+
+impl TryFrom<&str> for Condition<Arc<str>> {
+    type Error = ParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let op_start = value
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(value.len());
+        let field = &value[..op_start];
+        if !is_category_name(field) {
+            return Err(ParseError::new(
+                ParseErrorKind::UnknownField(field.to_string()),
+                0..op_start,
+            ));
+        }
+        let operator = Operator::parse_prefix(&value[op_start..]).map_err(|kind| {
+            let end = value[op_start..]
+                .chars()
+                .next()
+                .map_or(op_start, |c| op_start + c.len_utf8());
+            ParseError::new(kind, op_start..end)
+        })?;
+        let value_start = op_start + operator.symbol().len();
+        let token = &value[value_start..];
+        let value = token
+            .parse()
+            .ok()
+            .filter(|v| (0..=4001).contains(v))
+            .ok_or_else(|| {
+                ParseError::new(
+                    ParseErrorKind::InvalidValue(token.to_string()),
+                    value_start..value.len(),
+                )
+            })?;
+        Ok(Self {
+            field: field.into(),
+            operator,
+            value,
+        })
+    }
+}
+
+impl Display for Condition<Arc<str>> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.field, self.operator.symbol(), self.value)
+    }
+}
+
+impl Condition<usize> {
+    pub fn matches(&self, part: &Part) -> bool {
+        let field_value = part.ratings[self.field];
+        match self.operator {
+            Operator::Greater => field_value > self.value,
+            Operator::Less => field_value < self.value,
+            Operator::GreaterEqual => field_value >= self.value,
+            Operator::LessEqual => field_value <= self.value,
+            Operator::Equal => field_value == self.value,
+            Operator::NotEqual => field_value != self.value,
+        }
+    }
+
+    pub fn named(&self, categories: &[Arc<str>]) -> Condition<Arc<str>> {
+        Condition {
+            field: categories[self.field].clone(),
+            operator: self.operator,
+            value: self.value,
+        }
+    }
+
+    pub fn invert(&self) -> Self {
+        let (operator, value) = match self.operator {
+            Operator::Greater => (Operator::Less, self.value + 1),
+            Operator::Less => (Operator::Greater, self.value.saturating_sub(1)),
+            Operator::GreaterEqual => (Operator::Less, self.value),
+            Operator::LessEqual => (Operator::Greater, self.value),
+            Operator::Equal => (Operator::NotEqual, self.value),
+            Operator::NotEqual => (Operator::Equal, self.value),
+        };
+        Self {
+            field: self.field,
+            operator,
+            value,
+        }
+    }
+
+    pub fn to_ranges(self) -> Vec<Interval<u16>> {
+        let value = self.value as i32;
+        let bounds = match self.operator {
+            Operator::Greater => vec![(value + 1, 4000)],
+            Operator::Less => vec![(1, value - 1)],
+            Operator::GreaterEqual => vec![(value, 4000)],
+            Operator::LessEqual => vec![(1, value)],
+            Operator::Equal => vec![(value, value)],
+            Operator::NotEqual => vec![(1, value - 1), (value + 1, 4000)],
+        };
+        bounds
+            .into_iter()
+            .map(|(low, high)| (low.max(1), high.min(4000)))
+            .filter(|(low, high)| low <= high)
+            .map(|(low, high)| Interval::inclusive(low as u16, high as u16))
+            .collect()
+    }
+
+    pub fn to_part_ranges(self, dimensions: usize) -> Vec<PartRange> {
+        self.to_ranges()
+            .into_iter()
+            .map(|range| {
+                let mut part_range = PartRange::full(dimensions);
+                part_range.ranges[self.field] = range;
+                part_range
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+struct Rule<T>
+where
+    T: Clone + PartialEq + Eq + Debug,
+{
+    condition: Option<Condition<T>>,
+    target: Stage<T>,
+}
+
+impl TryFrom<&str> for Rule<Arc<str>> {
+    type Error = ParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (condition, target) = if let Some((left, right)) = value.split_once(':') {
+            (Some(Condition::try_from(left)?), right)
+        } else {
+            (None, value)
+        };
+        if target.is_empty() {
+            return Err(ParseError::new(
+                ParseErrorKind::MissingTarget,
+                value.len()..value.len(),
+            ));
+        }
+        Ok(Self {
+            condition,
+            target: target.into(),
+        })
+    }
+}
+
+impl Rule<usize> {
+    pub fn should_apply(&self, part: &Part) -> bool {
+        if let Some(condition) = self.condition {
+            condition.matches(part)
+        } else {
+            true
+        }
+    }
+
+    pub fn get_stage(&self) -> Stage<usize> {
+        self.target.clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Workflow<T>
+where
+    T: Clone + PartialEq + Eq + Debug,
+{
+    name: Arc<str>,
+    rules: Vec<Rule<T>>,
+}
+
+fn rule_spans(value: &str) -> Option<Vec<std::ops::Range<usize>>> {
+    let open = value.find('{')?;
+    if open == 0 || !value.ends_with('}') {
+        return None;
+    }
+    let mut start = open + 1;
+    let mut spans = Vec::new();
+    for rule in value[start..value.len() - 1].split(',') {
+        spans.push(start..start + rule.len());
+        start += rule.len() + 1;
+    }
+    Some(spans)
+}
+
+impl TryFrom<&str> for Workflow<Arc<str>> {
+    type Error = Vec<ParseError>;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let spans = rule_spans(value).ok_or_else(|| {
+            vec![ParseError::new(
+                ParseErrorKind::MalformedWorkflow,
+                0..value.len(),
+            )]
+        })?;
+        let name: Arc<str> = value[..spans[0].start - 1].into();
+        let mut rules = Vec::with_capacity(spans.len());
+        let mut errors = Vec::new();
+        for (rule_idx, span) in spans.into_iter().enumerate() {
+            match Rule::try_from(&value[span.clone()]) {
+                Ok(rule) => rules.push(rule),
+                Err(err) => errors.push(ParseError {
+                    workflow: Some(name.clone()),
+                    rule: Some(rule_idx),
+                    ..err.shifted(span.start)
+                }),
+            }
+        }
+        if errors.is_empty() {
+            Ok(Self { name, rules })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Workflow<usize> {
+    pub fn get_next_stage(&self, part: &Part) -> Stage<usize> {
+        self.rules
+            .iter()
+            .find(|rule| rule.should_apply(part))
+            .unwrap()
+            .get_stage()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct Part {
+    ratings: Vec<u16>,
+}
+
+fn parse_ratings(value: &str) -> Result<Vec<(Arc<str>, u16)>, ParseError> {
+    parse_fields(value, |val| val.parse().ok())
+}
+
+fn parse_range_value(value: &str) -> Option<Interval<u16>> {
+    let (start, end) = value.split_once("..")?;
+    let start: u16 = start.parse().ok()?;
+    let end: u16 = match end.strip_prefix('=') {
+        Some(end) => end.parse::<u16>().ok()?.checked_add(1)?,
+        None => end.parse().ok()?,
+    };
+    (start >= 1 && end > start && end <= 4001).then_some(Interval::new(start, end))
+}
+
+fn parse_fields<V>(
+    value: &str,
+    parse_value: impl Fn(&str) -> Option<V>,
+) -> Result<Vec<(Arc<str>, V)>, ParseError> {
+    let parts = value
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+        .ok_or_else(|| ParseError::new(ParseErrorKind::MalformedPart, 0..value.len()))?;
+    let mut ratings = Vec::new();
+    let mut start = 1;
+    for s in parts.split(',') {
+        let span = start..start + s.len();
+        start += s.len() + 1;
+        let (field, val) = s
+            .split_once('=')
+            .ok_or_else(|| ParseError::new(ParseErrorKind::MalformedPart, span.clone()))?;
+        let val = parse_value(val).ok_or_else(|| {
+            ParseError::new(
+                ParseErrorKind::InvalidValue(val.to_string()),
+                span.start + field.len() + 1..span.end,
+            )
+        })?;
+        if !is_category_name(field) {
+            return Err(ParseError::new(
+                ParseErrorKind::UnknownField(field.to_string()),
+                span.start..span.start + field.len(),
+            ));
+        }
+        ratings.push((field.into(), val));
+    }
+    Ok(ratings)
+}
+
+impl Part {
+    pub fn total(&self) -> u64 {
+        self.ratings.iter().map(|&rating| rating as u64).sum()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum Stage<T>
+where
+    T: Clone + PartialEq + Eq + Debug,
+{
+    Accept,
+    Reject,
+    Workflow(T),
+}
+
+impl From<&str> for Stage<Arc<str>> {
+    fn from(value: &str) -> Self {
+        match value {
+            "A" => Self::Accept,
+            "R" => Self::Reject,
+            _ => Self::Workflow(value.into()),
+        }
+    }
+}
+
+impl Default for Stage<Arc<str>> {
+    fn default() -> Self {
+        Self::Workflow("in".into())
+    }
+}
+
+impl<T> Stage<T>
+where
+    T: Clone + PartialEq + Eq + Debug,
+{
+    pub fn accepted(&self) -> bool {
+        *self == Self::Accept
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Input {
+    categories: Vec<Arc<str>>,
+    pub workflows: Vec<Workflow<usize>>,
+    pub parts: Vec<Part>,
+    starting_workflow: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct Node {
+    workflow_idx: usize,
+    rule_idx: usize,
+}
+
+const FULL: Interval<u16> = Interval::new(1, 4001);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PartRange {
+    ranges: Vec<Interval<u16>>,
+}
+
+impl PartRange {
+    pub fn full(dimensions: usize) -> Self {
+        Self {
+            ranges: vec![FULL; dimensions],
+        }
+    }
+
+    pub fn overlap(&self, other: &Self) -> Self {
+        Self {
+            ranges: self
+                .ranges
+                .iter()
+                .zip(other.ranges.iter())
+                .map(|(a, b)| a.intersection(b))
+                .collect(),
+        }
+    }
+
+    pub fn restrict(&self, condition: &Condition<usize>) -> Vec<PartRange> {
+        condition
+            .to_part_ranges(self.ranges.len())
+            .iter()
+            .map(|piece| self.overlap(piece))
+            .filter(|piece| !piece.is_zero())
+            .collect()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.ranges.iter().any(Interval::is_empty)
+    }
+
+    pub fn size(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|range| range.len() as u128)
+            .product()
+    }
+
+    pub fn subtract(&self, other: &Self) -> Vec<PartRange> {
+        if self.overlap(other).is_zero() {
+            return vec![self.clone()];
+        }
+        let mut pieces = Vec::new();
+        let mut rest = self.clone();
+        for dim in 0..self.ranges.len() {
+            let range = rest.ranges[dim];
+            let cut = range.intersection(&other.ranges[dim]);
+            for piece in range.subtract(&cut) {
+                let mut part_range = rest.clone();
+                part_range.ranges[dim] = piece;
+                pieces.push(part_range);
+            }
+            rest.ranges[dim] = cut;
+        }
+        pieces
+    }
+}
+
+// Boxes cover the same parts as `ranges` minus anything in `removed`, and never
+// overlap each other if `ranges` didn't.
+pub fn difference(ranges: &[PartRange], removed: &[PartRange]) -> Vec<PartRange> {
+    let mut result = ranges.to_vec();
+    for other in removed {
+        result = result
+            .iter()
+            .flat_map(|range| range.subtract(other))
+            .collect();
+    }
+    result
+}
+
+// `ranges` plus the parts of `added` they miss, still without overlaps.
+pub fn union(ranges: &[PartRange], added: &[PartRange]) -> Vec<PartRange> {
+    let mut result = ranges.to_vec();
+    for range in added {
+        let fresh = difference(std::slice::from_ref(range), &result);
+        result.extend(fresh);
+    }
+    result
+}
+
+// Parts covered, counting overlaps twice; pass the result of `union` for
+// a true count.
+pub fn volume(ranges: &[PartRange]) -> u128 {
+    ranges.iter().map(|range| range.size()).sum()
+}
+
+// The first pair of indexes whose boxes share a part.
+pub fn find_overlap(ranges: &[PartRange]) -> Option<(usize, usize)> {
+    for (i, a) in ranges.iter().enumerate() {
+        for (j, b) in ranges.iter().enumerate().skip(i + 1) {
+            if !a.overlap(b).is_zero() {
+                return Some((i, j));
+            }
+        }
+    }
+    None
+}
+
+fn part_ranges(
+    graph_and_map: GraphAndMap,
+    starting_index: usize,
+    dimensions: usize,
+) -> Vec<PartRange> {
+    let graph: DiGraph<Rc<Node>, Option<Condition<usize>>> = graph_and_map.graph;
+    let node_map: HashMap<Rc<Node>, NodeIndex> = graph_and_map.node_to_index;
+    let accept_node: Rc<Node> = graph_and_map.accepted_node;
+    let reject_node: Rc<Node> = graph_and_map.rejected_node;
+    let mut ranges = Vec::new();
+    let mut stack = Vec::new();
+    stack.push((
+        *node_map
+            .get(&Rc::new(Node {
+                workflow_idx: starting_index,
+                rule_idx: 0,
+            }))
+            .unwrap(),
+        PartRange::full(dimensions),
+    ));
+    while let Some((cur_node_index, cur_range)) = stack.pop() {
+        let cur_node_weight = graph.node_weight(cur_node_index).unwrap();
+        if cur_node_weight.clone() == accept_node {
+            ranges.push(cur_range);
+            continue;
+        } else if cur_node_weight.clone() == reject_node {
+            continue;
+        }
+        for edge in graph.edges_directed(cur_node_index, Direction::Outgoing) {
+            let opt_condition = edge.weight();
+            let next_node = edge.target();
+            if let Some(condition) = opt_condition {
+                for next_range in cur_range.restrict(condition) {
+                    stack.push((next_node, next_range));
+                }
+            } else {
+                stack.push((next_node, cur_range.clone()));
+            }
+        }
+    }
+    ranges
+}
+
+#[derive(Debug)]
+pub struct GraphAndMap {
+    graph: DiGraph<Rc<Node>, Option<Condition<usize>>>,
+    node_to_index: HashMap<Rc<Node>, NodeIndex>,
+    accepted_node: Rc<Node>,
+    rejected_node: Rc<Node>,
+}
+
+pub fn make_graph(workflows: &[Workflow<usize>]) -> GraphAndMap {
+    let mut graph = DiGraph::new();
+    let mut node_map = HashMap::new();
+    let accepted_node = Rc::new(Node {
+        workflow_idx: usize::MAX,
+        rule_idx: usize::MAX,
+    });
+    let rejected_node = Rc::new(Node {
+        workflow_idx: usize::MAX,
+        rule_idx: usize::MAX - 1,
+    });
+    node_map.insert(accepted_node.clone(), graph.add_node(accepted_node.clone()));
+    node_map.insert(rejected_node.clone(), graph.add_node(rejected_node.clone()));
+    for (workflow_idx, workflow) in workflows.iter().enumerate() {
+        for (rule_idx, _) in workflow.rules.iter().enumerate() {
+            let node = Node {
+                workflow_idx,
+                rule_idx,
+            };
+            node_map.insert(node.into(), graph.add_node(node.into()));
+        }
+    }
+    for (workflow_idx, workflow) in workflows.iter().enumerate() {
+        for (rule_idx, rule) in workflow.rules.iter().enumerate() {
+            let start_node = Node {
+                workflow_idx,
+                rule_idx,
+            };
+            match rule.target {
+                Stage::Workflow(workflow_idx) => {
+                    let next_node = Node {
+                        workflow_idx,
+                        rule_idx: 0,
+                    };
+                    graph.add_edge(
+                        *node_map.get(&start_node).unwrap(),
+                        *node_map.get(&next_node).unwrap(),
+                        rule.condition,
+                    );
+                }
+                Stage::Accept => {
+                    graph.add_edge(
+                        *node_map.get(&start_node).unwrap(),
+                        *node_map.get(&accepted_node).unwrap(),
+                        rule.condition,
+                    );
+                }
+                Stage::Reject => {
+                    graph.add_edge(
+                        *node_map.get(&start_node).unwrap(),
+                        *node_map.get(&rejected_node).unwrap(),
+                        rule.condition,
+                    );
+                }
+            }
+            if let Some(condition) = rule.condition {
+                if rule_idx + 1 < workflows[workflow_idx].rules.len() {
+                    if let Some(right_node) = node_map.get(&Rc::new(Node {
+                        workflow_idx,
+                        rule_idx: rule_idx + 1,
+                    })) {
+                        graph.add_edge(
+                            *node_map.get(&start_node).unwrap(),
+                            *right_node,
+                            Some(condition.invert()),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    GraphAndMap {
+        graph,
+        node_to_index: node_map,
+        accepted_node,
+        rejected_node,
+    }
+}
+
+pub fn graph_to_dot(graph_and_map: &GraphAndMap, input: &Input) -> String {
+    let graph = &graph_and_map.graph;
+    let workflows = &input.workflows;
+    let edge_attributes =
+        |_, edge: petgraph::graph::EdgeReference<'_, Option<Condition<usize>>>| match edge.weight()
+        {
+            Some(condition) => format!("label = \"{}\"", condition.named(&input.categories)),
+            None => String::new(),
+        };
+    let node_attributes = |_, (_, node): (NodeIndex, &Rc<Node>)| {
+        if *node == graph_and_map.accepted_node {
+            "label = \"A\", style = filled, fillcolor = palegreen".to_string()
+        } else if *node == graph_and_map.rejected_node {
+            "label = \"R\", style = filled, fillcolor = lightcoral".to_string()
+        } else {
+            format!(
+                "label = \"{}:{}\"",
+                workflows[node.workflow_idx].name, node.rule_idx
+            )
+        }
+    };
+    format!(
+        "{:?}",
+        Dot::with_attr_getters(
+            graph,
+            &[Config::EdgeNoLabel, Config::NodeNoLabel],
+            &edge_attributes,
+            &node_attributes,
+        )
+    )
+}
+
+fn convert_to_idx(
+    workflows: Vec<Workflow<Arc<str>>>,
+    name_map: HashMap<Arc<str>, usize>,
+    category_map: &HashMap<Arc<str>, usize>,
+) -> Vec<Workflow<usize>> {
+    workflows
+        .into_iter()
+        .map(|wf| Workflow {
+            name: wf.name,
+            rules: wf
+                .rules
+                .into_iter()
+                .map(|rule| Rule {
+                    condition: rule.condition.map(|condition| Condition {
+                        field: *category_map.get(&condition.field).unwrap(),
+                        operator: condition.operator,
+                        value: condition.value,
+                    }),
+                    target: match rule.target {
+                        Stage::Workflow(name) => Stage::Workflow(*name_map.get(&name).unwrap()),
+                        Stage::Accept => Stage::Accept,
+                        Stage::Reject => Stage::Reject,
+                    },
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn line_offsets(s: &str, base: usize) -> impl Iterator<Item = (usize, &str)> {
+    s.split('\n').scan(base, |offset, line| {
+        let start = *offset;
+        *offset += line.len() + 1;
+        Some((start, line))
+    })
+}
+
+pub fn parse_input(s: &str) -> Result<Input, ParseErrors> {
+    let Some((workflow_text, part_text)) = s.split_once("\n\n") else {
+        return Err(ParseErrors(vec![ParseError::new(
+            ParseErrorKind::MissingParts,
+            s.len()..s.len(),
+        )]));
+    };
+    let mut errors = Vec::new();
+    let mut parsed = Vec::new();
+    for (offset, line) in line_offsets(workflow_text, 0) {
+        match Workflow::try_from(line) {
+            Ok(workflow) => parsed.push((offset, line, workflow)),
+            Err(errs) => errors.extend(errs.into_iter().map(|err| err.shifted(offset))),
+        }
+    }
+    let mut name_map = HashMap::new();
+    for (idx, (offset, line, workflow)) in parsed.iter().enumerate() {
+        if name_map.insert(workflow.name.clone(), idx).is_some() {
+            errors.push(ParseError {
+                workflow: Some(workflow.name.clone()),
+                ..ParseError::new(
+                    ParseErrorKind::DuplicateWorkflow(workflow.name.to_string()),
+                    *offset..offset + line.len(),
+                )
+            });
+        }
+    }
+    for (offset, line, workflow) in parsed.iter() {
+        let spans = rule_spans(line).unwrap();
+        for (rule_idx, rule) in workflow.rules.iter().enumerate() {
+            if let Stage::Workflow(name) = &rule.target {
+                if !name_map.contains_key(name) {
+                    let span = spans[rule_idx].end - name.len()..spans[rule_idx].end;
+                    errors.push(ParseError {
+                        workflow: Some(workflow.name.clone()),
+                        rule: Some(rule_idx),
+                        ..ParseError::new(ParseErrorKind::UnknownWorkflow(name.to_string()), span)
+                            .shifted(*offset)
+                    });
+                }
+            }
+        }
+    }
+    let starting_workflow = name_map.get("in").copied();
+    if starting_workflow.is_none() && errors.is_empty() {
+        errors.push(ParseError::new(
+            ParseErrorKind::MissingStart,
+            0..workflow_text.len(),
+        ));
+    }
+    let mut rated = Vec::new();
+    for (offset, line) in line_offsets(part_text, workflow_text.len() + 2) {
+        if line.is_empty() {
+            continue;
+        }
+        match parse_ratings(line) {
+            Ok(ratings) => rated.push(ratings),
+            Err(err) => errors.push(err.shifted(offset)),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(ParseErrors(errors));
+    }
+    let mut categories: Vec<Arc<str>> = Vec::new();
+    let mut category_map = HashMap::new();
+    let condition_fields = parsed.iter().flat_map(|(_, _, workflow)| {
+        workflow
+            .rules
+            .iter()
+            .filter_map(|rule| rule.condition.as_ref().map(|c| &c.field))
+    });
+    for name in rated
+        .iter()
+        .flatten()
+        .map(|(name, _)| name)
+        .chain(condition_fields)
+    {
+        if !category_map.contains_key(name) {
+            category_map.insert(name.clone(), categories.len());
+            categories.push(name.clone());
+        }
+    }
+    let parts = rated
+        .into_iter()
+        .map(|ratings| {
+            let mut part = Part {
+                ratings: vec![0; categories.len()],
+            };
+            for (name, val) in ratings {
+                part.ratings[category_map[&name]] = val;
+            }
+            part
+        })
+        .collect();
+    let workflows = parsed
+        .into_iter()
+        .map(|(_, _, workflow)| workflow)
+        .collect();
+    Ok(Input {
+        workflows: convert_to_idx(workflows, name_map, &category_map),
+        parts,
+        starting_workflow: starting_workflow.unwrap(),
+        categories,
+    })
+}
+
+fn accept_part(workflows: &[Workflow<usize>], starting_index: usize, part: &Part) -> bool {
+    let mut stage = Stage::Workflow(starting_index);
+    while let Stage::Workflow(idx) = stage {
+        let workflow = &workflows[idx];
+        stage = workflow.get_next_stage(part);
+    }
+    stage.accepted()
+}
+
+pub fn accepted_total(input: &Input) -> u64 {
+    input
+        .parts
+        .iter()
+        .filter(|part| accept_part(&input.workflows, input.starting_workflow, part))
+        .map(|part| part.total())
+        .sum()
+}
+
+pub fn part1(s: &str) -> Result<u64, ParseErrors> {
+    Ok(accepted_total(&parse_input(s)?))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Algorithm {
+    Graph,
+    Split,
+}
+
+impl TryFrom<&str> for Algorithm {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "graph" => Ok(Self::Graph),
+            "split" => Ok(Self::Split),
+            _ => Err(format!(
+                "invalid algorithm {:?}, expected graph or split",
+                value
+            )),
+        }
+    }
+}
+
+fn count_split(workflows: &[Workflow<usize>], stage: &Stage<usize>, range: PartRange) -> u128 {
+    let idx = match stage {
+        Stage::Accept => return range.size(),
+        Stage::Reject => return 0,
+        Stage::Workflow(idx) => *idx,
+    };
+    let mut total = 0;
+    let mut remaining = vec![range];
+    for rule in workflows[idx].rules.iter() {
+        let Some(condition) = rule.condition else {
+            return total
+                + remaining
+                    .into_iter()
+                    .map(|range| count_split(workflows, &rule.target, range))
+                    .sum::<u128>();
+        };
+        for range in remaining.iter() {
+            for matching in range.restrict(&condition) {
+                total += count_split(workflows, &rule.target, matching);
+            }
+        }
+        let inverse = condition.invert();
+        remaining = remaining
+            .iter()
+            .flat_map(|range| range.restrict(&inverse))
+            .collect();
+        if remaining.is_empty() {
+            break;
+        }
+    }
+    total
+}
+
+impl Input {
+    pub fn full_range(&self) -> PartRange {
+        PartRange::full(self.categories.len())
+    }
+
+    // Narrows one category of `range`, by name, to the half-open `ratings`.
+    pub fn set_range(
+        &self,
+        range: &mut PartRange,
+        category: &str,
+        ratings: Interval<u16>,
+    ) -> Result<(), ParseErrorKind> {
+        let idx = self
+            .categories
+            .iter()
+            .position(|name| name.as_ref() == category)
+            .ok_or_else(|| ParseErrorKind::UnknownField(category.to_string()))?;
+        range.ranges[idx] = ratings;
+        Ok(())
+    }
+
+    pub fn count_accepted(&self, range: &PartRange) -> u128 {
+        let start = Stage::Workflow(self.starting_workflow);
+        count_split(&self.workflows, &start, range.clone())
+    }
+}
+
+pub fn accepted_combinations(input: &Input, algorithm: Algorithm) -> u128 {
+    match algorithm {
+        Algorithm::Graph => {
+            let graph = make_graph(&input.workflows);
+            let ranges = part_ranges(graph, input.starting_workflow, input.categories.len());
+            let total = volume(&ranges);
+            debug_assert_eq!(find_overlap(&ranges), None);
+            debug_assert_eq!(volume(&union(&[], &ranges)), total);
+            total
+        }
+        Algorithm::Split => input.count_accepted(&input.full_range()),
+    }
+}
+
+pub fn part2_with(s: &str, algorithm: Algorithm) -> Result<u128, ParseErrors> {
+    Ok(accepted_combinations(&parse_input(s)?, algorithm))
+}
+
+pub fn part2(s: &str) -> Result<u128, ParseErrors> {
+    part2_with(s, Algorithm::Graph)
+}
+
+fn prune_rules(workflow: &Workflow<usize>, dimensions: usize) -> (Vec<Rule<usize>>, Vec<usize>) {
+    let mut remaining = vec![PartRange::full(dimensions)];
+    let mut kept = Vec::new();
+    let mut dead = Vec::new();
+    for (rule_idx, rule) in workflow.rules.iter().enumerate() {
+        if remaining.is_empty() {
+            dead.push(rule_idx);
+            continue;
+        }
+        let Some(condition) = rule.condition else {
+            kept.push(rule.clone());
+            remaining.clear();
+            continue;
+        };
+        if remaining
+            .iter()
+            .all(|range| range.restrict(&condition).is_empty())
+        {
+            dead.push(rule_idx);
+            continue;
+        }
+        let inverse = condition.invert();
+        remaining = remaining
+            .iter()
+            .flat_map(|range| range.restrict(&inverse))
+            .collect();
+        kept.push(Rule {
+            condition: if remaining.is_empty() {
+                None
+            } else {
+                Some(condition)
+            },
+            target: rule.target.clone(),
+        });
+    }
+    (kept, dead)
+}
+
+fn constant_stage(
+    workflows: &[Workflow<usize>],
+    idx: usize,
+    memo: &mut Vec<Option<Option<Stage<usize>>>>,
+) -> Option<Stage<usize>> {
+    if let Some(known) = &memo[idx] {
+        return known.clone();
+    }
+    memo[idx] = Some(None);
+    let mut stages = workflows[idx].rules.iter().map(|rule| match rule.target {
+        Stage::Workflow(next) => constant_stage(workflows, next, memo),
+        ref terminal => Some(terminal.clone()),
+    });
+    let first = stages.next().flatten();
+    let result = if stages.all(|stage| stage == first) {
+        first
+    } else {
+        None
+    };
+    memo[idx] = Some(result.clone());
+    result
+}
+
+fn reachable(workflows: &[Workflow<usize>], start: usize) -> Vec<bool> {
+    let mut seen = vec![false; workflows.len()];
+    let mut stack = vec![start];
+    while let Some(idx) = stack.pop() {
+        if seen[idx] {
+            continue;
+        }
+        seen[idx] = true;
+        for rule in workflows[idx].rules.iter() {
+            if let Stage::Workflow(next) = rule.target {
+                stack.push(next);
+            }
+        }
+    }
+    seen
+}
+
+#[derive(Debug, PartialEq, Eq, Default)]
+struct Analysis {
+    dead_rules: Vec<(usize, usize)>,
+    unreachable: Vec<usize>,
+    constant: Vec<(usize, Stage<usize>)>,
+    simplified: Vec<Workflow<usize>>,
+}
+
+fn analyze(input: &Input) -> Analysis {
+    let mut analysis = Analysis::default();
+    let mut workflows = input.workflows.clone();
+    for (idx, workflow) in workflows.iter_mut().enumerate() {
+        let (kept, dead) = prune_rules(workflow, input.categories.len());
+        analysis
+            .dead_rules
+            .extend(dead.into_iter().map(|rule_idx| (idx, rule_idx)));
+        workflow.rules = kept;
+    }
+    let seen = reachable(&workflows, input.starting_workflow);
+    analysis.unreachable = (0..workflows.len()).filter(|idx| !seen[*idx]).collect();
+    let mut memo = vec![None; workflows.len()];
+    let constants = (0..workflows.len())
+        .map(|idx| constant_stage(&workflows, idx, &mut memo))
+        .collect::<Vec<_>>();
+    analysis.constant = constants
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, stage)| stage.clone().map(|stage| (idx, stage)))
+        .collect();
+    for (idx, workflow) in workflows.iter_mut().enumerate() {
+        if let Some(stage) = &constants[idx] {
+            workflow.rules = vec![Rule {
+                condition: None,
+                target: stage.clone(),
+            }];
+            continue;
+        }
+        for rule in workflow.rules.iter_mut() {
+            if let Stage::Workflow(next) = rule.target {
+                if let Some(stage) = &constants[next] {
+                    rule.target = stage.clone();
+                }
+            }
+        }
+        while workflow.rules.len() >= 2
+            && workflow.rules[workflow.rules.len() - 2].target
+                == workflow.rules[workflow.rules.len() - 1].target
+        {
+            workflow.rules.remove(workflow.rules.len() - 2);
+        }
+    }
+    let seen = reachable(&workflows, input.starting_workflow);
+    analysis.simplified = workflows
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, _)| seen[*idx])
+        .map(|(_, workflow)| workflow)
+        .collect();
+    analysis
+}
+
+fn format_stage(stage: &Stage<usize>, input: &Input) -> String {
+    match stage {
+        Stage::Accept => "A".to_string(),
+        Stage::Reject => "R".to_string(),
+        Stage::Workflow(idx) => input.workflows[*idx].name.to_string(),
+    }
+}
+
+fn format_rule(rule: &Rule<usize>, input: &Input) -> String {
+    let target = format_stage(&rule.target, input);
+    match rule.condition {
+        Some(condition) => format!("{}:{}", condition.named(&input.categories), target),
+        None => target,
+    }
+}
+
+fn format_workflow(workflow: &Workflow<usize>, input: &Input) -> String {
+    let rules = workflow
+        .rules
+        .iter()
+        .map(|rule| format_rule(rule, input))
+        .collect::<Vec<_>>();
+    format!("{}{{{}}}", workflow.name, rules.join(","))
+}
+
+fn format_part(part: &Part, categories: &[Arc<str>]) -> String {
+    let ratings = categories
+        .iter()
+        .zip(part.ratings.iter())
+        .map(|(name, rating)| format!("{}={}", name, rating))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", ratings.join(","))
+}
+
+// Back to puzzle text, with ratings in the order the categories were first
+// seen rather than the order they were written in.
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for workflow in self.workflows.iter() {
+            writeln!(f, "{}", format_workflow(workflow, self))?;
+        }
+        writeln!(f)?;
+        for part in self.parts.iter() {
+            writeln!(f, "{}", format_part(part, &self.categories))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Hop {
+    workflow_idx: usize,
+    rule_idx: usize,
+}
+
+fn trace_part(
+    workflows: &[Workflow<usize>],
+    starting_index: usize,
+    part: &Part,
+) -> (Vec<Hop>, Stage<usize>) {
+    let mut hops = Vec::new();
+    let mut stage = Stage::Workflow(starting_index);
+    while let Stage::Workflow(workflow_idx) = stage {
+        let rule_idx = workflows[workflow_idx]
+            .rules
+            .iter()
+            .position(|rule| rule.should_apply(part))
+            .unwrap();
+        hops.push(Hop {
+            workflow_idx,
+            rule_idx,
+        });
+        stage = workflows[workflow_idx].rules[rule_idx].get_stage();
+    }
+    (hops, stage)
+}
+
+pub fn format_trace(input: &Input, part: &Part) -> String {
+    let (hops, stage) = trace_part(&input.workflows, input.starting_workflow, part);
+    let mut steps = hops
+        .iter()
+        .map(|hop| {
+            let workflow = &input.workflows[hop.workflow_idx];
+            format!(
+                "{}[{}] {}",
+                workflow.name,
+                hop.rule_idx,
+                format_rule(&workflow.rules[hop.rule_idx], input)
+            )
+        })
+        .collect::<Vec<_>>();
+    steps.push(format_stage(&stage, input));
+    format!(
+        "{}: {}",
+        format_part(part, &input.categories),
+        steps.join(" -> ")
+    )
+}
+
+// Rewritten workflows, one per line, with what was found while rewriting
+// them for the caller to report.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Listing {
+    pub workflows: String,
+    pub notes: Vec<String>,
+}
+
+pub fn simplify(s: &str) -> Result<Listing, ParseErrors> {
+    let input = parse_input(s)?;
+    let analysis = analyze(&input);
+    let workflows = &input.workflows;
+    let mut notes = Vec::new();
+    for (idx, rule_idx) in analysis.dead_rules.iter() {
+        notes.push(format!(
+            "dead rule {} in workflow {}",
+            rule_idx, workflows[*idx].name
+        ));
+    }
+    for idx in analysis.unreachable.iter() {
+        notes.push(format!("unreachable workflow {}", workflows[*idx].name));
+    }
+    for (idx, stage) in analysis.constant.iter() {
+        notes.push(format!(
+            "workflow {} always ends in {:?}",
+            workflows[*idx].name, stage
+        ));
+    }
+    Ok(Listing {
+        workflows: analysis
+            .simplified
+            .iter()
+            .map(|workflow| format_workflow(workflow, &input) + "\n")
+            .collect(),
+        notes,
+    })
+}
+
+fn retarget(workflows: &mut [Workflow<usize>], from: usize, to: &Stage<usize>) {
+    for rule in workflows
+        .iter_mut()
+        .flat_map(|workflow| workflow.rules.iter_mut())
+    {
+        if rule.target == Stage::Workflow(from) {
+            rule.target = to.clone();
+        }
+    }
+}
+
+fn collapse_jumps(workflows: &mut [Workflow<usize>], removed: &mut [bool], start: usize) -> bool {
+    let mut changed = false;
+    for idx in 0..workflows.len() {
+        if removed[idx] || idx == start {
+            continue;
+        }
+        if let [Rule {
+            condition: None,
+            target,
+        }] = workflows[idx].rules.as_slice()
+        {
+            if *target != Stage::Workflow(idx) {
+                let target = target.clone();
+                retarget(workflows, idx, &target);
+                workflows[idx].rules.clear();
+                removed[idx] = true;
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+fn merge_identical(workflows: &mut [Workflow<usize>], removed: &mut [bool], start: usize) -> bool {
+    let mut first: HashMap<Vec<Rule<usize>>, usize> = HashMap::new();
+    let mut duplicates = vec![];
+    let order = std::iter::once(start).chain((0..workflows.len()).filter(|idx| *idx != start));
+    for idx in order {
+        if removed[idx] {
+            continue;
+        }
+        match first.entry(workflows[idx].rules.clone()) {
+            Entry::Occupied(entry) => duplicates.push((idx, *entry.get())),
+            Entry::Vacant(entry) => {
+                entry.insert(idx);
+            }
+        }
+    }
+    for (duplicate, kept) in duplicates.iter() {
+        retarget(workflows, *duplicate, &Stage::Workflow(*kept));
+        workflows[*duplicate].rules.clear();
+        removed[*duplicate] = true;
+    }
+    !duplicates.is_empty()
+}
+
+fn inline_single_use(
+    workflows: &mut [Workflow<usize>],
+    removed: &mut [bool],
+    start: usize,
+) -> bool {
+    let mut references = vec![vec![]; workflows.len()];
+    for (idx, workflow) in workflows.iter().enumerate() {
+        for (rule_idx, rule) in workflow.rules.iter().enumerate() {
+            if let Stage::Workflow(next) = rule.target {
+                references[next].push((idx, rule_idx));
+            }
+        }
+    }
+    for idx in 0..workflows.len() {
+        if removed[idx] || idx == start {
+            continue;
+        }
+        if let [(parent, rule_idx)] = references[idx].as_slice() {
+            let fallback = &workflows[*parent].rules[*rule_idx];
+            if *parent != idx
+                && fallback.condition.is_none()
+                && *rule_idx + 1 == workflows[*parent].rules.len()
+            {
+                let rules = std::mem::take(&mut workflows[idx].rules);
+                let parent = &mut workflows[*parent].rules;
+                parent.pop();
+                parent.extend(rules);
+                removed[idx] = true;
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn minimize(input: &Input) -> Input {
+    let start = input.starting_workflow;
+    let mut workflows = input.workflows.clone();
+    let mut removed = vec![false; workflows.len()];
+    while collapse_jumps(&mut workflows, &mut removed, start)
+        | merge_identical(&mut workflows, &mut removed, start)
+        | inline_single_use(&mut workflows, &mut removed, start)
+    {}
+    let seen = reachable(&workflows, start);
+    let mut new_idx = vec![usize::MAX; workflows.len()];
+    let mut kept = vec![];
+    for (idx, workflow) in workflows.into_iter().enumerate() {
+        if seen[idx] {
+            new_idx[idx] = kept.len();
+            kept.push(workflow);
+        }
+    }
+    for rule in kept
+        .iter_mut()
+        .flat_map(|workflow| workflow.rules.iter_mut())
+    {
+        if let Stage::Workflow(next) = rule.target {
+            rule.target = Stage::Workflow(new_idx[next]);
+        }
+    }
+    Input {
+        categories: input.categories.clone(),
+        workflows: kept,
+        parts: input.parts.clone(),
+        starting_workflow: new_idx[start],
+    }
+}
+
+pub fn minimize_listing(s: &str) -> Result<Listing, ParseErrors> {
+    let input = parse_input(s)?;
+    let minimized = minimize(&input);
+    Ok(Listing {
+        workflows: minimized
+            .workflows
+            .iter()
+            .map(|workflow| format_workflow(workflow, &minimized) + "\n")
+            .collect(),
+        notes: vec![format!(
+            "{} workflows minimized to {}",
+            input.workflows.len(),
+            minimized.workflows.len()
+        )],
+    })
+}
+
+pub fn same_answers(input: &str, listing: &str) -> Result<bool, ParseErrors> {
+    let parts = input.split_once("\n\n").map_or("", |(_, parts)| parts);
+    let rewritten = format!("{}\n{}", listing, parts);
+    Ok(part1(&rewritten)? == part1(input)?
+        && part2_with(&rewritten, Algorithm::Split)? == part2_with(input, Algorithm::Split)?)
+}
+
+fn lookup_category(input: &Input, line: &str, name: &Arc<str>) -> Result<usize, ParseError> {
+    input
+        .categories
+        .iter()
+        .position(|category| category == name)
+        .ok_or_else(|| {
+            let start = line.find(&format!("{}=", name)).unwrap_or(0);
+            ParseError::new(
+                ParseErrorKind::UnknownField(name.to_string()),
+                start..start + name.len(),
+            )
+        })
+}
+
+fn format_part_range(range: &PartRange, categories: &[Arc<str>]) -> String {
+    let ranges = categories
+        .iter()
+        .zip(range.ranges.iter())
+        .map(|(name, range)| format!("{}={}..={}", name, range.start, range.end - 1))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", ranges.join(","))
+}
+
+fn evaluate(input: &Input, line: &str) -> Result<String, ParseError> {
+    if line.contains("..") {
+        let mut range = input.full_range();
+        for (name, field_range) in parse_fields(line, parse_range_value)? {
+            range.ranges[lookup_category(input, line, &name)?] = field_range;
+        }
+        return Ok(format!(
+            "{}: {} accepted",
+            format_part_range(&range, &input.categories),
+            input.count_accepted(&range)
+        ));
+    }
+    let mut part = Part {
+        ratings: vec![0; input.categories.len()],
+    };
+    for (name, rating) in parse_ratings(line)? {
+        part.ratings[lookup_category(input, line, &name)?] = rating;
+    }
+    Ok(format_trace(input, &part))
+}
+
+pub fn interactive(input: &Input, reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+    write!(writer, "> ")?;
+    writer.flush()?;
+    for line in reader.lines() {
+        let line = line?;
+        match line.trim() {
+            "" => {}
+            "quit" | "exit" => break,
+            query => match evaluate(input, query) {
+                Ok(result) => writeln!(writer, "{}", result)?,
+                Err(err) => writeln!(writer, "error: {}", err)?,
+            },
+        }
+        write!(writer, "> ")?;
+        writer.flush()?;
+    }
+    writeln!(writer)
+}
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    type Parsed<'a> = Input;
+    type Error = ParseErrors;
+
+    fn day() -> u8 {
+        19
+    }
+
+    fn parse(input: &str) -> Result<Input, ParseErrors> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> impl Answer {
+        accepted_total(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> impl Answer {
+        accepted_combinations(input, Algorithm::Graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use proptest::prelude::*;
+
+    const NAMES: [&str; 6] = ["in", "aa", "bb", "cc", "dd", "ee"];
+
+    fn condition() -> impl Strategy<Value = String> {
+        (
+            prop::sample::select(vec!["x", "m", "a", "s"]),
+            prop::sample::select(Operator::ALL.map(|operator| operator.symbol()).to_vec()),
+            0..=4001u16,
+        )
+            .prop_map(|(field, operator, value)| format!("{}{}{}", field, operator, value))
+    }
+
+    // Workflows only send parts on to later workflows, so nothing loops.
+    fn workflow(idx: usize, count: usize) -> impl Strategy<Value = String> {
+        let target = prop::sample::select([&NAMES[idx + 1..count], &["A", "R"]].concat());
+        let rule = (condition(), target.clone())
+            .prop_map(|(condition, target)| format!("{}:{}", condition, target));
+        (prop::collection::vec(rule, 0..4), target).prop_map(move |(mut rules, fallback)| {
+            rules.push(fallback.to_string());
+            format!("{}{{{}}}\n", NAMES[idx], rules.join(","))
+        })
+    }
+
+    fn input_text() -> impl Strategy<Value = String> {
+        (1..=NAMES.len()).prop_flat_map(|count| {
+            let workflows: Vec<_> = (0..count).map(|idx| workflow(idx, count)).collect();
+            let part = prop::array::uniform4(1..=4000u16);
+            (workflows, prop::collection::vec(part, 1..4)).prop_map(move |(workflows, parts)| {
+                let mut text = workflows.concat();
+                text += "\n";
+                for [x, m, a, s] in parts {
+                    text += &format!("{{x={},m={},a={},s={}}}\n", x, m, a, s);
+                }
+                text
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_input_round_trip(text in input_text()) {
+            let formatted = parse_input(&text).unwrap().to_string();
+            prop_assert_eq!(parse_input(&formatted).unwrap().to_string(), formatted.clone());
+            prop_assert_eq!(part1(&formatted), part1(&text));
+            prop_assert_eq!(part2(&formatted), part2(&text));
+        }
+
+        #[test]
+        fn prop_invert(
+            field in 0..4usize,
+            operator in prop::sample::select(Operator::ALL.to_vec()),
+            value in 0..=4001u16,
+            ratings in prop::collection::vec(1..=4000u16, 4),
+        ) {
+            let condition = Condition { field, operator, value };
+            let part = Part { ratings };
+            prop_assert_eq!(condition.invert().matches(&part), !condition.matches(&part));
+            prop_assert_eq!(condition.invert().invert().matches(&part), condition.matches(&part));
+        }
+    }
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day19/example.txt");
+
+    #[test]
+    fn test_algorithm_names() {
+        assert_eq!(Algorithm::try_from("split"), Ok(Algorithm::Split));
+        assert_eq!(
+            Algorithm::try_from("fastest").unwrap_err(),
+            "invalid algorithm \"fastest\", expected graph or split"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let errors = parse_input(
+            "in{q!5:A,x>abc:px,R}
+px{a<2006:nope,A}
+px{R
+
+{x=1,m=2,a=3,s=4}
+{x=1,=2}",
+        )
+        .unwrap_err();
+        assert_eq!(
+            errors.0,
+            vec![
+                ParseError {
+                    workflow: Some("in".into()),
+                    rule: Some(0),
+                    ..ParseError::new(ParseErrorKind::UnknownOperator("!".into()), 4..5)
+                },
+                ParseError {
+                    workflow: Some("in".into()),
+                    rule: Some(1),
+                    ..ParseError::new(ParseErrorKind::InvalidValue("abc".into()), 11..14)
+                },
+                ParseError::new(ParseErrorKind::MalformedWorkflow, 39..43),
+                ParseError {
+                    workflow: Some("px".into()),
+                    rule: Some(0),
+                    ..ParseError::new(ParseErrorKind::UnknownWorkflow("nope".into()), 31..35)
+                },
+                ParseError::new(ParseErrorKind::UnknownField("".into()), 68..68),
+            ]
+        );
+        assert_eq!(
+            errors.0[0].to_string(),
+            "bytes 4..5: unknown operator \"!\" in rule 0 in workflow in"
+        );
+        assert_eq!(
+            parse_input("px{A}\n\n").unwrap_err().0,
+            vec![ParseError::new(ParseErrorKind::MissingStart, 0..5)]
+        );
+    }
+
+    #[test]
+    fn test_part2_basic() {
+        let cases = [
+            (
+                "in{x<2001:A,R}
+
+{x=1,m=1,a=1,s=1}",
+                2000 * 4000 * 4000 * 4000,
+            ),
+            (
+                "in{x<2001:A,A}
+
+{x=1,m=1,a=1,s=1}",
+                4000 * 4000 * 4000 * 4000,
+            ),
+            (
+                "in{x<2001:A,b}
+b{m>2000:A,R}
+
+{x=1,m=1,a=1,s=1}",
+                2000 * 4000 * 4000 * 4000 + 2000 * 2000 * 4000 * 4000,
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(part2_with(input, Algorithm::Graph), Ok(expected));
+            assert_eq!(part2_with(input, Algorithm::Split), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn test_part2_basic2() {}
+
+    #[test]
+    fn test_make_graph() {
+        let start_node = Rc::new(Node {
+            workflow_idx: 0,
+            rule_idx: 0,
+        });
+        let accepted_node = Rc::new(Node {
+            workflow_idx: usize::MAX,
+            rule_idx: usize::MAX,
+        });
+        let rejected_node = Rc::new(Node {
+            workflow_idx: usize::MAX,
+            rule_idx: usize::MAX - 1,
+        });
+        let mut expected = DiGraph::new();
+        let accepted_node = expected.add_node(accepted_node.clone());
+        expected.add_node(rejected_node.clone());
+        let start_node = expected.add_node(start_node.clone());
+        let other_node = expected.add_node(Rc::new(Node {
+            workflow_idx: 0,
+            rule_idx: 1,
+        }));
+        expected.add_edge(
+            start_node,
+            accepted_node,
+            Some(Condition {
+                field: 0,
+                operator: Operator::Less,
+                value: 2001,
+            }),
+        );
+        expected.add_edge(
+            start_node,
+            other_node,
+            Some(Condition {
+                field: 0,
+                operator: Operator::Greater,
+                value: 2000,
+            }),
+        );
+        expected.add_edge(other_node, accepted_node, None);
+
+        let input = parse_input(
+            "in{x<2001:A,A}
+
+{x=1,m=1,a=1,s=1}",
+        )
+        .unwrap();
+        let graph = make_graph(&input.workflows);
+        assert_eq!(
+            graph.graph.node_weights().cloned().collect::<Vec<_>>(),
+            expected.node_weights().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            graph.graph.edge_weights().cloned().collect::<Vec<_>>(),
+            expected.edge_weights().cloned().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_graph_to_dot() {
+        let input = parse_input(
+            "in{x<2001:A,R}
+
+{x=1,m=1,a=1,s=1}",
+        )
+        .unwrap();
+        let dot = graph_to_dot(&make_graph(&input.workflows), &input);
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("label = \"A\", style = filled, fillcolor = palegreen"));
+        assert!(dot.contains("label = \"R\", style = filled, fillcolor = lightcoral"));
+        assert!(dot.contains("2 [ label = \"in:0\"]"));
+        assert!(dot.contains("2 -> 0 [ label = \"x<2001\"]"));
+        assert!(dot.contains("2 -> 3 [ label = \"x>2000\"]"));
+        assert!(dot.contains("3 -> 1 [ ]"));
+    }
+
+    #[test]
+    fn test_analyze() {
+        let input = parse_input(
+            "in{x<2001:a,x<1000:R,b}
+a{m>10:A,A}
+b{x>2000:c,R}
+c{s<5:R,s>4:d,A}
+d{m<100:R,m<50:A,a>0:A,R}
+e{R}
+
+{x=1,m=1,a=1,s=1}",
+        )
+        .unwrap();
+        let analysis = analyze(&input);
+        assert_eq!(analysis.dead_rules, vec![(0, 1), (3, 2), (4, 1), (4, 3)]);
+        assert_eq!(analysis.unreachable, vec![5]);
+        assert_eq!(
+            analysis.constant,
+            vec![(1, Stage::Accept), (5, Stage::Reject)]
+        );
+        let listing = analysis
+            .simplified
+            .iter()
+            .map(|workflow| format_workflow(workflow, &input))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            listing,
+            vec![
+                "in{x<2001:A,b}",
+                "b{x>2000:c,R}",
+                "c{s<5:R,d}",
+                "d{m<100:R,A}"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simplify_preserves_answers() {
+        let listing = simplify(TEST_INPUT).unwrap().workflows;
+        let parts = TEST_INPUT.split_once("\n\n").unwrap().1;
+        let simplified = format!("{}\n{}", listing, parts);
+        assert_eq!(part1(&simplified), part1(TEST_INPUT));
+        assert_eq!(
+            part2_with(&simplified, Algorithm::Split),
+            part2_with(TEST_INPUT, Algorithm::Split)
+        );
+        assert!(listing.lines().count() < TEST_INPUT.split_once("\n\n").unwrap().0.lines().count());
+    }
+
+    #[test]
+    fn test_minimize() {
+        let input = "in{x<10:a,j1}
+a{m>5:R,A}
+b{m>5:R,A}
+j1{j2}
+j2{c}
+c{s<7:b,d}
+d{a>3:A,R}
+
+{x=1,m=2,a=3,s=4}
+{x=20,m=2,a=3,s=4}
+{x=20,m=9,a=3,s=9}
+{x=20,m=9,a=5,s=9}
+";
+        let Listing {
+            workflows: listing,
+            notes,
+        } = minimize_listing(input).unwrap();
+        assert_eq!(notes, vec!["7 workflows minimized to 2"]);
+        assert_eq!(
+            listing.lines().collect::<Vec<_>>(),
+            vec!["in{x<10:a,s<7:a,a>3:A,R}", "a{m>5:R,A}"]
+        );
+        assert!(same_answers(input, &listing).unwrap());
+        let minimized = minimize_listing(TEST_INPUT).unwrap().workflows;
+        assert!(same_answers(TEST_INPUT, &minimized).unwrap());
+        let parsed = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(
+            minimize(&parsed).count_accepted(&parsed.full_range()),
+            167409079868000
+        );
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(
+            evaluate(&input, "{x=787,m=2655,a=1222,s=2876}").unwrap(),
+            "{x=787,m=2655,a=1222,s=2876}: in[1] qqz -> qqz[0] s>2770:qs -> qs[1] lnx -> lnx[0] m>1548:A -> A"
+        );
+        assert_eq!(
+            evaluate(&input, "{x=1..=4000,m=1..4001,a=1..=4000,s=1..=4000}").unwrap(),
+            "{x=1..=4000,m=1..=4000,a=1..=4000,s=1..=4000}: 167409079868000 accepted"
+        );
+        assert!(evaluate(&input, "{s=1..=1350,x=1..=4000}")
+            .unwrap()
+            .starts_with("{x=1..=4000,m=1..=4000,a=1..=4000,s=1..=1350}: "));
+        assert_eq!(
+            evaluate(&input, "{x=1,q=1}").unwrap_err(),
+            ParseError::new(ParseErrorKind::UnknownField("q".to_string()), 5..6)
+        );
+        assert!(evaluate(&input, "{x=5..5}").is_err());
+        let mut output = Vec::new();
+        interactive(
+            &input,
+            "{x=1,m=1,a=1,s=1}\nbogus\nquit\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("-> A\n> error: bytes 0..5"));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let json = serde_json::to_string(&input).unwrap();
+        let loaded: Input = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, input);
+        assert_eq!(loaded.count_accepted(&loaded.full_range()), 167409079868000);
+        let loaded: Input = bincode::deserialize(&bincode::serialize(&input).unwrap()).unwrap();
+        assert_eq!(loaded, input);
+        let rule: Rule<usize> = serde_json::from_str(
+            r#"{"condition":{"field":0,"operator":"Less","value":2001},"target":"Accept"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            rule,
+            Rule {
+                condition: Some(Condition {
+                    field: 0,
+                    operator: Operator::Less,
+                    value: 2001
+                }),
+                target: Stage::Accept
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&input.parts[0]).unwrap(),
+            r#"{"ratings":[787,2655,1222,2876]}"#
+        );
+    }
+
+    #[test]
+    fn test_count_accepted() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(input.count_accepted(&input.full_range()), 167409079868000);
+        let mut range = input.full_range();
+        input
+            .set_range(&mut range, "s", Interval::new(3449, 4001))
+            .unwrap();
+        input
+            .set_range(&mut range, "m", Interval::new(1, 1549))
+            .unwrap();
+        assert_eq!(input.count_accepted(&range), 552 * 1548 * 4000 * 4000);
+        input
+            .set_range(&mut range, "x", Interval::new(1, 1))
+            .unwrap();
+        assert_eq!(input.count_accepted(&range), 0);
+        assert_eq!(
+            input.set_range(&mut range, "q", FULL),
+            Err(ParseErrorKind::UnknownField("q".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(
+            format_trace(&input, &input.parts[0]),
+            "{x=787,m=2655,a=1222,s=2876}: in[1] qqz -> qqz[0] s>2770:qs -> qs[1] lnx -> lnx[0] m>1548:A -> A"
+        );
+        let (hops, stage) = trace_part(&input.workflows, input.starting_workflow, &input.parts[1]);
+        assert_eq!(hops.len(), 4);
+        assert_eq!(stage, Stage::Reject);
+    }
+
+    #[test]
+    fn test_arbitrary_categories() {
+        let input = "in{speed>10:fast,R}
+fast{grip<3:R,cost>100:R,A}
+
+{speed=20,grip=5,cost=50,weight=7}
+{speed=5,grip=5,cost=50,weight=7}
+{speed=20,grip=1,cost=50,weight=7}";
+        let parsed = parse_input(input).unwrap();
+        let names = parsed
+            .categories
+            .iter()
+            .map(|c| c.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["speed", "grip", "cost", "weight"]);
+        assert_eq!(parsed.parts[0].ratings, vec![20, 5, 50, 7]);
+        assert_eq!(part1(input), Ok(82));
+        let expected = 3990 * 3998 * 100 * 4000;
+        assert_eq!(part2_with(input, Algorithm::Graph), Ok(expected));
+        assert_eq!(part2_with(input, Algorithm::Split), Ok(expected));
+        assert_eq!(
+            part2_with("in{q<11:A,R}\n\n{q=1}", Algorithm::Split),
+            Ok(10)
+        );
+    }
+
+    #[test]
+    fn test_part_ranges() {
+        let input = parse_input(
+            "in{x<2001:A,A}
+
+{x=1,m=1,a=1,s=1}",
+        )
+        .unwrap();
+        let graph = make_graph(&input.workflows);
+        let ranges = part_ranges(graph, input.starting_workflow, 4);
+        let range = |start: u16, size: u16| {
+            let mut part_range = PartRange::full(4);
+            part_range.ranges[0] = Interval::new(start, start + size);
+            part_range
+        };
+        let expected_ranges = vec![range(1, 2000), range(2001, 2000)];
+        assert_eq!(ranges, expected_ranges);
+    }
+
+    #[test]
+    fn test_invert_condition() {
+        let condition = Condition {
+            field: 0,
+            operator: Operator::Greater,
+            value: 2000,
+        };
+        let expected = Condition {
+            field: 0,
+            operator: Operator::Less,
+            value: 2001,
+        };
+        assert_eq!(condition.invert(), expected);
+        assert_eq!(expected.invert(), condition);
+    }
+
+    #[test]
+    fn test_range_conversion() {
+        let condition = Condition {
+            field: 0,
+            operator: Operator::Greater,
+            value: 50,
+        };
+        let expected_range = PartRange {
+            ranges: vec![Interval::new(51, 4001), FULL, FULL, FULL],
+        };
+        assert_eq!(condition.to_part_ranges(4), vec![expected_range]);
+        let condition = Condition {
+            field: 0,
+            operator: Operator::Less,
+            value: 150,
+        };
+        let expected_range = PartRange {
+            ranges: vec![Interval::new(1, 150), FULL, FULL, FULL],
+        };
+        assert_eq!(condition.to_part_ranges(4), vec![expected_range]);
+    }
+
+    #[test]
+    fn test_extended_operators() {
+        let input = "in{x>=2000:a,m<=10:A,s==5:A,R}
+a{x==2000:R,a!=7:A,R}
+
+{x=2000,m=1,a=1,s=1}
+{x=2001,m=4000,a=7,s=1}
+{x=1,m=4000,a=1,s=5}";
+        assert_eq!(part1(input), Ok(4007));
+        let expected = 2000 * 4000 * 3999 * 4000 + 1999 * 10 * 4000 * 4000 + 1999 * 3990 * 4000;
+        assert_eq!(part2_with(input, Algorithm::Split), Ok(expected));
+        assert_eq!(part2_with(input, Algorithm::Graph), Ok(expected));
+        let condition = Condition {
+            field: 0,
+            operator: Operator::Equal,
+            value: 10,
+        };
+        assert_eq!(
+            condition.invert().to_ranges(),
+            vec![Interval::new(1, 10), Interval::new(11, 4001)]
+        );
+        assert_eq!(condition.invert().invert(), condition);
+        let parsed = parse_input(input).unwrap();
+        let listing = format_workflow(&parsed.workflows[1], &parsed);
+        assert_eq!(listing, "a{x==2000:R,a!=7:A,R}");
+    }
+
+    #[test]
+    fn test_part_range_set_algebra() {
+        let boxed = |x: (u16, u16), m: (u16, u16)| PartRange {
+            ranges: vec![Interval::new(x.0, x.0 + x.1), Interval::new(m.0, m.0 + m.1)],
+        };
+        let outer = boxed((1, 10), (1, 10));
+        let inner = boxed((4, 3), (4, 3));
+        let pieces = outer.subtract(&inner);
+        assert_eq!(pieces.len(), 4);
+        assert_eq!(find_overlap(&pieces), None);
+        assert_eq!(volume(&pieces), 100 - 9);
+        assert_eq!(
+            outer.subtract(&boxed((20, 5), (1, 10))),
+            vec![outer.clone()]
+        );
+        assert!(outer.subtract(&outer).is_empty());
+
+        let shifted = boxed((6, 10), (1, 10));
+        let combined = union(std::slice::from_ref(&outer), std::slice::from_ref(&shifted));
+        assert_eq!(find_overlap(&combined), None);
+        assert_eq!(volume(&combined), 150);
+        let remaining = difference(&combined, &[inner]);
+        assert_eq!(volume(&remaining), 141);
+        assert_eq!(find_overlap(&[outer, shifted]), Some((0, 1)));
+
+        let input = parse_input(TEST_INPUT).unwrap();
+        let graph = make_graph(&input.workflows);
+        let ranges = part_ranges(graph, input.starting_workflow, 4);
+        assert_eq!(find_overlap(&ranges), None);
+    }
+
+    #[test]
+    fn test_range_overlap() {
+        let a = Interval::new(50, 101);
+        let b = Interval::new(100, 110);
+        let expected = Interval::new(100, 101);
+        assert_eq!(a.intersection(&b), expected);
+
+        let a = Interval::new(50, 101);
+        let b = Interval::new(150, 160);
+        let expected = Interval::new(150, 150);
+        assert_eq!(a.intersection(&b), expected);
+
+        let a = FULL;
+        let b = Interval::new(150, 160);
+        assert_eq!(a.intersection(&b), b);
+    }
+}
@@ -0,0 +1,200 @@
+use aoc_core::{
+    error::{parse_lines, parse_number, split_once},
+    Answer, AocError, Solver,
+};
+use std::fmt::Display;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Pull {
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+impl TryFrom<&str> for Pull {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut pull = Self::default();
+        for sub in value.split(", ") {
+            let (num, color) = split_once(sub, " ", "cube count")?;
+            let num: u32 = parse_number("cube count", num)?;
+            match color {
+                "red" => pull.red += num,
+                "blue" => pull.blue += num,
+                "green" => pull.green += num,
+                _ => return Err(AocError::invalid("color", color)),
+            }
+        }
+        Ok(pull)
+    }
+}
+
+impl Pull {
+    pub fn is_possible_with(&self, red: u32, green: u32, blue: u32) -> bool {
+        red >= self.red && green >= self.green && blue >= self.blue
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            red: u32::max(self.red, other.red),
+            green: u32::max(self.green, other.green),
+            blue: u32::max(self.blue, other.blue),
+        }
+    }
+
+    pub fn power(&self) -> u64 {
+        self.red as u64 * self.green as u64 * self.blue as u64
+    }
+}
+
+// Colors come out in a fixed order with empty ones left out, except that an
+// empty pull is written as `0 red` so it still parses.
+impl Display for Pull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counts: Vec<String> = [
+            (self.red, "red"),
+            (self.green, "green"),
+            (self.blue, "blue"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, color)| format!("{} {}", count, color))
+        .collect();
+        match counts.is_empty() {
+            true => f.write_str("0 red"),
+            false => f.write_str(&counts.join(", ")),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    id: u32,
+    pulls: Vec<Pull>,
+}
+
+impl TryFrom<&str> for Game {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut game = Self::default();
+        let (game_str, pulls_str) = split_once(value, ": ", "game")?;
+        let id = game_str
+            .strip_prefix("Game ")
+            .ok_or_else(|| AocError::invalid("game", game_str))?;
+        game.id = parse_number("game id", id)?;
+        for pull_str in pulls_str.split("; ") {
+            game.pulls.push(Pull::try_from(pull_str)?);
+        }
+        Ok(game)
+    }
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pulls: Vec<String> = self.pulls.iter().map(Pull::to_string).collect();
+        write!(f, "Game {}: {}", self.id, pulls.join("; "))
+    }
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game>, AocError> {
+    parse_lines(input, Game::try_from)
+}
+
+impl Game {
+    pub fn is_possible_with(&self, red: u32, green: u32, blue: u32) -> bool {
+        self.pulls
+            .iter()
+            .all(|pull| pull.is_possible_with(red, green, blue))
+    }
+
+    pub fn min_pull(&self) -> Pull {
+        self.pulls
+            .iter()
+            .fold(Pull::default(), |acc: Pull, e| acc.max(e))
+    }
+}
+
+pub fn possible_id_sum(games: &[Game]) -> u64 {
+    games
+        .iter()
+        .filter(|game| game.is_possible_with(12, 13, 14))
+        .map(|game| game.id as u64)
+        .sum()
+}
+
+pub fn power_sum(games: &[Game]) -> u64 {
+    games
+        .iter()
+        .map(Game::min_pull)
+        .map(|pull| pull.power())
+        .sum()
+}
+
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    Ok(possible_id_sum(&parse_games(input)?))
+}
+
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    Ok(power_sum(&parse_games(input)?))
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Parsed<'a> = Vec<Game>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        2
+    }
+
+    fn parse(input: &str) -> Result<Vec<Game>, AocError> {
+        parse_games(input)
+    }
+
+    fn part1(games: &Self::Parsed<'_>) -> impl Answer {
+        possible_id_sum(games)
+    }
+
+    fn part2(games: &Self::Parsed<'_>) -> impl Answer {
+        power_sum(games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use proptest::prelude::*;
+
+    fn game() -> impl Strategy<Value = Game> {
+        let pull =
+            (0..30u32, 0..30u32, 0..30u32).prop_map(|(red, green, blue)| Pull { red, green, blue });
+        (0..1000u32, prop::collection::vec(pull, 1..6)).prop_map(|(id, pulls)| Game { id, pulls })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_game_round_trip(game in game()) {
+            let text = game.to_string();
+            let parsed = Game::try_from(text.as_str()).unwrap();
+            prop_assert_eq!(&parsed, &game);
+            prop_assert_eq!(parsed.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part1("Game 1: 3 blue\nGame 2: 4 purple")
+                .unwrap_err()
+                .to_string(),
+            "line 2: invalid color `purple`"
+        );
+        assert_eq!(
+            part2("Game x: 3 blue").unwrap_err().to_string(),
+            "line 1: invalid game id `x`"
+        );
+    }
+}
@@ -0,0 +1,1671 @@
+use aoc_core::{math::crt, Answer, Solver};
+use aoc_viz::OnFrame;
+#[cfg(feature = "debugger")]
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    widgets::{Block, List, ListItem, Paragraph},
+    DefaultTerminal, Frame,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum PulseState {
+    #[default]
+    Low,
+    High,
+}
+
+impl TryFrom<&str> for PulseState {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "high" => Ok(Self::High),
+            _ => Err(format!("unknown pulse state {:?}", value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Pulse {
+    pub state: PulseState,
+    pub source: usize,
+    pub destination: usize,
+}
+
+pub trait Module: Debug {
+    fn kind(&self) -> &'static str;
+    fn connect(&mut self, _source: usize) {}
+    fn inputs(&self) -> Vec<usize> {
+        Vec::new()
+    }
+    fn receive(&mut self, pulse: &Pulse) -> Option<PulseState>;
+    fn snapshot(&self, _bits: &mut Vec<bool>) {}
+    fn describe(&self, _names: &[&str]) -> String {
+        String::new()
+    }
+    fn received(&self) -> Option<(usize, usize)> {
+        None
+    }
+    fn boxed_clone(&self) -> Box<dyn Module>;
+}
+
+impl Clone for Box<dyn Module> {
+    fn clone(&self) -> Self {
+        self.boxed_clone()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Button;
+
+impl Module for Button {
+    fn kind(&self) -> &'static str {
+        "button"
+    }
+
+    fn receive(&mut self, _pulse: &Pulse) -> Option<PulseState> {
+        panic!("Button can't receive pulses!")
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Broadcaster;
+
+impl Module for Broadcaster {
+    fn kind(&self) -> &'static str {
+        "broadcaster"
+    }
+
+    fn receive(&mut self, pulse: &Pulse) -> Option<PulseState> {
+        Some(pulse.state)
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct FlipFlop {
+    on: bool,
+}
+
+impl Module for FlipFlop {
+    fn kind(&self) -> &'static str {
+        "flip-flop"
+    }
+
+    fn receive(&mut self, pulse: &Pulse) -> Option<PulseState> {
+        match pulse.state {
+            PulseState::High => None,
+            PulseState::Low => {
+                self.on = !self.on;
+                Some(if self.on {
+                    PulseState::High
+                } else {
+                    PulseState::Low
+                })
+            }
+        }
+    }
+
+    fn snapshot(&self, bits: &mut Vec<bool>) {
+        bits.push(self.on);
+    }
+
+    fn describe(&self, _names: &[&str]) -> String {
+        if self.on { "on" } else { "off" }.to_string()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Conjunction {
+    input_state: Vec<(usize, PulseState)>,
+}
+
+impl Module for Conjunction {
+    fn kind(&self) -> &'static str {
+        "conjunction"
+    }
+
+    fn connect(&mut self, source: usize) {
+        self.input_state.push((source, PulseState::Low));
+    }
+
+    fn inputs(&self) -> Vec<usize> {
+        self.input_state.iter().map(|(source, _)| *source).collect()
+    }
+
+    fn receive(&mut self, pulse: &Pulse) -> Option<PulseState> {
+        self.input_state
+            .iter_mut()
+            .find(|(source, _)| *source == pulse.source)
+            .unwrap()
+            .1 = pulse.state;
+        Some(
+            if self
+                .input_state
+                .iter()
+                .all(|(_, state)| *state == PulseState::High)
+            {
+                PulseState::Low
+            } else {
+                PulseState::High
+            },
+        )
+    }
+
+    fn snapshot(&self, bits: &mut Vec<bool>) {
+        bits.extend(
+            self.input_state
+                .iter()
+                .map(|(_, state)| *state == PulseState::High),
+        );
+    }
+
+    fn describe(&self, names: &[&str]) -> String {
+        self.input_state
+            .iter()
+            .map(|(source, state)| format!("{}={:?}", names[*source], state))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Sink {
+    sources: Vec<usize>,
+    low: usize,
+    high: usize,
+}
+
+impl Module for Sink {
+    fn kind(&self) -> &'static str {
+        "sink"
+    }
+
+    fn connect(&mut self, source: usize) {
+        self.sources.push(source);
+    }
+
+    fn inputs(&self) -> Vec<usize> {
+        self.sources.clone()
+    }
+
+    fn receive(&mut self, pulse: &Pulse) -> Option<PulseState> {
+        match pulse.state {
+            PulseState::Low => self.low += 1,
+            PulseState::High => self.high += 1,
+        }
+        None
+    }
+
+    fn describe(&self, _names: &[&str]) -> String {
+        format!("low={} high={}", self.low, self.high)
+    }
+
+    fn received(&self) -> Option<(usize, usize)> {
+        Some((self.low, self.high))
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+}
+
+type ModuleFactory = fn() -> Box<dyn Module>;
+
+#[derive(Clone, Debug)]
+pub struct Registry {
+    kinds: HashMap<char, ModuleFactory>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Self {
+            kinds: HashMap::new(),
+        };
+        registry.register('%', || Box::new(FlipFlop::default()));
+        registry.register('&', || Box::new(Conjunction::default()));
+        registry
+    }
+}
+
+impl Registry {
+    pub fn register(&mut self, prefix: char, factory: ModuleFactory) {
+        self.kinds.insert(prefix, factory);
+    }
+
+    fn prefix(&self, kind: &str) -> Option<char> {
+        self.kinds
+            .iter()
+            .find(|(_, factory)| factory().kind() == kind)
+            .map(|(prefix, _)| *prefix)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Part {
+    module: Box<dyn Module>,
+    destinations: Vec<usize>,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseError {
+    MissingArrow {
+        line: usize,
+    },
+    UnknownKind {
+        line: usize,
+        prefix: char,
+    },
+    EmptyName {
+        line: usize,
+    },
+    DuplicateModule {
+        line: usize,
+        id: String,
+        first: usize,
+    },
+    DanglingDestination {
+        line: usize,
+        id: String,
+    },
+    MissingBroadcaster,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingArrow { line } => write!(f, "line {}: missing \" -> \"", line),
+            Self::UnknownKind { line, prefix } => {
+                write!(f, "line {}: unknown module kind {:?}", line, prefix)
+            }
+            Self::EmptyName { line } => write!(f, "line {}: empty module name", line),
+            Self::DuplicateModule { line, id, first } => write!(
+                f,
+                "line {}: module {} already defined on line {}",
+                line, id, first
+            ),
+            Self::DanglingDestination { line, id } => {
+                write!(f, "line {}: destination {} is never defined", line, id)
+            }
+            Self::MissingBroadcaster => write!(f, "no broadcaster module"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+type Definition<'a> = (&'a str, Box<dyn Module>, Vec<&'a str>);
+
+fn parse_line<'a>(
+    line: usize,
+    value: &'a str,
+    registry: &Registry,
+) -> Result<Definition<'a>, ParseError> {
+    let (kind_and_name, destinations) = value
+        .split_once(" -> ")
+        .ok_or(ParseError::MissingArrow { line })?;
+    let (id, module): (&str, Box<dyn Module>) = match kind_and_name {
+        BROADCASTER => (BROADCASTER, Box::new(Broadcaster)),
+        _ => {
+            let prefix = kind_and_name
+                .chars()
+                .next()
+                .ok_or(ParseError::EmptyName { line })?;
+            let factory = registry
+                .kinds
+                .get(&prefix)
+                .ok_or(ParseError::UnknownKind { line, prefix })?;
+            (&kind_and_name[prefix.len_utf8()..], factory())
+        }
+    };
+    let destinations: Vec<&str> = destinations.split(", ").collect();
+    if id.is_empty() || destinations.contains(&"") {
+        return Err(ParseError::EmptyName { line });
+    }
+    Ok((id, module, destinations))
+}
+
+#[derive(Clone, Debug)]
+pub struct State<'a> {
+    pub names: Vec<&'a str>,
+    index: HashMap<&'a str, usize>,
+    parts: Vec<Part>,
+    pulses: VecDeque<Pulse>,
+    button: usize,
+    pub warnings: Vec<ParseError>,
+}
+
+const BROADCASTER: &str = "broadcaster";
+const BUTTON: &str = "button";
+const RX: &str = "rx";
+// Destinations the puzzle leaves undefined on purpose; they become sinks
+// without a warning.
+const KNOWN_SINKS: [&str; 2] = [RX, "output"];
+pub const MAX_PRESSES: usize = 1_000_000;
+
+impl<'a, 'b> TryFrom<&'b str> for State<'a>
+where
+    'b: 'a,
+{
+    type Error = ParseError;
+    fn try_from(value: &'b str) -> Result<Self, Self::Error> {
+        Self::parse(value, &Registry::default(), false)
+    }
+}
+
+impl<'a> State<'a> {
+    pub fn parse(value: &'a str, registry: &Registry, strict: bool) -> Result<Self, ParseError> {
+        let mut definitions: Vec<Definition<'a>> = Vec::new();
+        let mut lines: Vec<usize> = Vec::new();
+        let mut index: HashMap<&'a str, usize> = HashMap::new();
+        for (number, line) in value.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let definition = parse_line(number + 1, line, registry)?;
+            if let Some(&first) = index.get(definition.0) {
+                return Err(ParseError::DuplicateModule {
+                    line: number + 1,
+                    id: definition.0.to_string(),
+                    first: lines[first],
+                });
+            }
+            index.insert(definition.0, definitions.len());
+            definitions.push(definition);
+            lines.push(number + 1);
+        }
+        if !index.contains_key(BROADCASTER) {
+            return Err(ParseError::MissingBroadcaster);
+        }
+        index.insert(BUTTON, definitions.len());
+        definitions.push((BUTTON, Box::new(Button), vec![BROADCASTER]));
+        let mut warnings = Vec::new();
+        let mut sinks: Vec<&'a str> = Vec::new();
+        for (idx, (_, _, destinations)) in definitions.iter().enumerate() {
+            for destination in destinations.iter() {
+                if !index.contains_key(destination) {
+                    let dangling = ParseError::DanglingDestination {
+                        line: lines[idx],
+                        id: destination.to_string(),
+                    };
+                    if strict {
+                        return Err(dangling);
+                    }
+                    if !KNOWN_SINKS.contains(destination) {
+                        warnings.push(dangling);
+                    }
+                    sinks.push(destination);
+                }
+            }
+        }
+        sinks.sort();
+        sinks.dedup();
+        for id in sinks {
+            index.insert(id, definitions.len());
+            definitions.push((id, Box::new(Sink::default()), vec![]));
+        }
+        let names = definitions.iter().map(|(id, _, _)| *id).collect();
+        let mut parts: Vec<Part> = definitions
+            .into_iter()
+            .map(|(_, module, destinations)| Part {
+                module,
+                destinations: destinations.iter().map(|id| index[id]).collect(),
+            })
+            .collect();
+        let edges: Vec<(usize, usize)> = parts
+            .iter()
+            .enumerate()
+            .flat_map(|(source, part)| part.destinations.iter().map(move |d| (source, *d)))
+            .collect();
+        for (source, destination) in edges {
+            parts[destination].module.connect(source);
+        }
+
+        Ok(Self {
+            names,
+            button: index[BUTTON],
+            index,
+            parts,
+            pulses: VecDeque::default(),
+            warnings,
+        })
+    }
+
+    // The modules as written, in their original order. Undefined destinations
+    // stay undefined rather than being listed as sinks.
+    pub fn listing(&self, registry: &Registry) -> String {
+        (0..self.button)
+            .map(|idx| {
+                let part = &self.parts[idx];
+                let prefix = registry
+                    .prefix(part.module.kind())
+                    .map_or(String::new(), String::from);
+                let destinations: Vec<&str> = part
+                    .destinations
+                    .iter()
+                    .map(|&destination| self.names[destination])
+                    .collect();
+                format!(
+                    "{}{} -> {}\n",
+                    prefix,
+                    self.names[idx],
+                    destinations.join(", ")
+                )
+            })
+            .collect()
+    }
+
+    fn part(&self, id: &str) -> Option<&Part> {
+        self.index.get(id).map(|idx| &self.parts[*idx])
+    }
+
+    pub fn input_names(&self, id: &str) -> Vec<&'a str> {
+        self.part(id).map_or(vec![], |part| {
+            part.module
+                .inputs()
+                .into_iter()
+                .map(|source| self.names[source])
+                .collect()
+        })
+    }
+
+    fn sorted_ids(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = (0..self.parts.len()).collect();
+        ids.sort_by_key(|idx| self.names[*idx]);
+        ids
+    }
+
+    // Every module by name, with its kind and current state.
+    fn module_lines(&self) -> Vec<String> {
+        self.sorted_ids()
+            .into_iter()
+            .map(|idx| {
+                let module = &self.parts[idx].module;
+                format!(
+                    "{:<12} {:<12} {}",
+                    self.names[idx],
+                    module.kind(),
+                    module.describe(&self.names)
+                )
+            })
+            .collect()
+    }
+
+    pub fn sinks(&self) -> Vec<(&'a str, usize, usize)> {
+        let mut sinks: Vec<(&'a str, usize, usize)> = self
+            .parts
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, part)| {
+                let (low, high) = part.module.received()?;
+                Some((self.names[idx], low, high))
+            })
+            .collect();
+        sinks.sort();
+        sinks
+    }
+
+    fn step(&mut self) -> Option<Pulse> {
+        let pulse = self.pulses.pop_front()?;
+        let part = &mut self.parts[pulse.destination];
+        if let Some(state) = part.module.receive(&pulse) {
+            self.pulses
+                .extend(part.destinations.iter().map(|&destination| Pulse {
+                    state,
+                    source: pulse.destination,
+                    destination,
+                }));
+        }
+        Some(pulse)
+    }
+
+    fn process_pulses_with(&mut self, mut watch: impl FnMut(&Pulse)) {
+        while let Some(pulse) = self.step() {
+            watch(&pulse);
+        }
+    }
+
+    pub fn process_pulses(&mut self) -> (usize, usize) {
+        let mut low = 0;
+        let mut high = 0;
+        self.process_pulses_with(|pulse| match pulse.state {
+            PulseState::Low => low += 1,
+            PulseState::High => high += 1,
+        });
+        (low, high)
+    }
+
+    fn process_pulses_part2(&mut self, rx: usize) -> bool {
+        let mut rx_low_pulses: usize = 0;
+        self.process_pulses_with(|pulse| {
+            if pulse.state == PulseState::Low && pulse.destination == rx {
+                rx_low_pulses += 1;
+            }
+        });
+        rx_low_pulses != 0
+    }
+
+    pub fn push_button(&mut self) {
+        self.pulses.push_back(Pulse {
+            state: PulseState::Low,
+            source: self.button,
+            destination: self.parts[self.button].destinations[0],
+        });
+    }
+}
+
+// Low and high pulse totals over `presses` presses. Presses past the first
+// repeat of the modules' state are extrapolated, so this finishes quickly
+// whenever the state cycles within MAX_PRESSES presses, as it does for the
+// examples. Real inputs count presses in 12-bit binary counters that only
+// line up again after ~10^14 presses; for those, None once `presses` is
+// over MAX_PRESSES and no cycle has turned up.
+pub fn pulses_after(state: &State, presses: u64) -> Option<(u64, u64)> {
+    let mut state = state.clone();
+    // The whole state, packed 64 modules' bits to a word so that up to
+    // MAX_PRESSES of them still fit in memory.
+    let snapshot = |state: &State| {
+        let mut bits = Vec::new();
+        for part in state.parts.iter() {
+            part.module.snapshot(&mut bits);
+        }
+        bits.chunks(64)
+            .map(|chunk| chunk.iter().fold(0, |word, &bit| word << 1 | bit as u64))
+            .collect::<Vec<u64>>()
+    };
+    let mut totals: Vec<(u64, u64)> = vec![(0, 0)];
+    let mut seen: HashMap<Vec<u64>, u64> = HashMap::from([(snapshot(&state), 0)]);
+    for press in 1..=presses {
+        if press > MAX_PRESSES as u64 {
+            return None;
+        }
+        state.push_button();
+        let (lows, highs) = state.process_pulses();
+        let (low, high) = totals[totals.len() - 1];
+        totals.push((low + lows as u64, high + highs as u64));
+        let bits = snapshot(&state);
+        if let Some(&start) = seen.get(&bits) {
+            let length = press - start;
+            let cycles = (presses - start) / length;
+            let remainder = (presses - start) % length;
+            let at = |press: u64| totals[press as usize];
+            let (low_start, high_start) = at(start);
+            let (low_cycle, high_cycle) = at(press);
+            let (low_rest, high_rest) = at(start + remainder);
+            return Some((
+                low_rest + cycles * (low_cycle - low_start),
+                high_rest + cycles * (high_cycle - high_start),
+            ));
+        }
+        seen.insert(bits, press);
+    }
+    Some(totals[totals.len() - 1])
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PressStats {
+    low: usize,
+    high: usize,
+    sent: Vec<(usize, usize)>,
+}
+
+pub fn press_stats(state: &State, presses: usize) -> Vec<PressStats> {
+    let mut state = state.clone();
+    let mut stats = Vec::with_capacity(presses);
+    for _ in 0..presses {
+        let mut press = PressStats {
+            low: 0,
+            high: 0,
+            sent: vec![(0, 0); state.parts.len()],
+        };
+        state.push_button();
+        state.process_pulses_with(|pulse| {
+            let sent = &mut press.sent[pulse.source];
+            match pulse.state {
+                PulseState::Low => {
+                    press.low += 1;
+                    sent.0 += 1;
+                }
+                PulseState::High => {
+                    press.high += 1;
+                    sent.1 += 1;
+                }
+            }
+        });
+        stats.push(press);
+    }
+    stats
+}
+
+pub fn stats_csv(state: &State, stats: &[PressStats]) -> String {
+    let ids = state.sorted_ids();
+    let mut header = vec!["press".to_string(), "low".to_string(), "high".to_string()];
+    for &idx in ids.iter() {
+        header.push(format!("{}_low", state.names[idx]));
+        header.push(format!("{}_high", state.names[idx]));
+    }
+    let mut csv = header.join(",") + "\n";
+    for (press, stat) in stats.iter().enumerate() {
+        let mut row = vec![
+            (press + 1).to_string(),
+            stat.low.to_string(),
+            stat.high.to_string(),
+        ];
+        for &idx in ids.iter() {
+            row.push(stat.sent[idx].0.to_string());
+            row.push(stat.sent[idx].1.to_string());
+        }
+        csv += &(row.join(",") + "\n");
+    }
+    csv
+}
+
+fn vcd_id(mut idx: usize) -> String {
+    let mut id = String::new();
+    loop {
+        id.push((b'!' + (idx % 94) as u8) as char);
+        idx /= 94;
+        if idx == 0 {
+            return id;
+        }
+        idx -= 1;
+    }
+}
+
+pub fn write_vcd(state: &State, presses: usize, out: &mut impl Write) -> io::Result<()> {
+    let mut state = state.clone();
+    let signals: Vec<usize> = state
+        .sorted_ids()
+        .into_iter()
+        .filter(|idx| !state.parts[*idx].destinations.is_empty())
+        .collect();
+    let mut ids = vec![String::new(); state.parts.len()];
+    writeln!(out, "$timescale 1ns $end")?;
+    writeln!(out, "$scope module day20 $end")?;
+    for (n, &idx) in signals.iter().enumerate() {
+        ids[idx] = vcd_id(n + 1);
+        writeln!(out, "$var wire 1 {} {} $end", ids[idx], state.names[idx])?;
+    }
+    let press_id = vcd_id(0);
+    writeln!(out, "$var integer 32 {} press $end", press_id)?;
+    writeln!(out, "$upscope $end")?;
+    writeln!(out, "$enddefinitions $end")?;
+    writeln!(out, "#0")?;
+    writeln!(out, "$dumpvars")?;
+    for &idx in signals.iter() {
+        writeln!(out, "0{}", ids[idx])?;
+    }
+    writeln!(out, "b0 {}", press_id)?;
+    writeln!(out, "$end")?;
+    let mut levels = vec![PulseState::Low; state.parts.len()];
+    let mut time = 0;
+    for press in 1..=presses {
+        time += 1;
+        writeln!(out, "#{}", time)?;
+        writeln!(out, "b{:b} {}", press, press_id)?;
+        state.push_button();
+        while let Some(pulse) = state.step() {
+            time += 1;
+            if levels[pulse.source] != pulse.state && !ids[pulse.source].is_empty() {
+                levels[pulse.source] = pulse.state;
+                let bit = if pulse.state == PulseState::High {
+                    1
+                } else {
+                    0
+                };
+                writeln!(out, "#{}", time)?;
+                writeln!(out, "{}{}", bit, ids[pulse.source])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn benchmark(state: &State, presses: usize) -> (usize, Duration) {
+    let mut state = state.clone();
+    let start = Instant::now();
+    let mut pulses = 0;
+    for _ in 0..presses {
+        state.push_button();
+        let (low, high) = state.process_pulses();
+        pulses += low + high;
+    }
+    (pulses, start.elapsed())
+}
+
+pub fn pulse_product(state: &State) -> usize {
+    let (low, high) = pulses_after(state, 1000).unwrap();
+    (low * high) as usize
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Algorithm {
+    Cycles,
+    Simulate,
+}
+
+impl TryFrom<&str> for Algorithm {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "cycles" => Ok(Self::Cycles),
+            "simulate" => Ok(Self::Simulate),
+            _ => Err(format!(
+                "invalid algorithm {:?}, expected cycles or simulate",
+                value
+            )),
+        }
+    }
+}
+
+fn part2_simulate(state: &State) -> Option<usize> {
+    let mut state = state.clone();
+    let rx = *state.index.get(RX)?;
+    let mut count: usize = 1;
+    state.push_button();
+    while !state.process_pulses_part2(rx) {
+        count += 1;
+        if count.is_multiple_of(1_000_000) {
+            tracing::debug!(presses = count, "rx still not low");
+        }
+        state.push_button();
+    }
+    Some(count)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct PulseEvent {
+    source: Option<usize>,
+    destination: usize,
+    state: PulseState,
+}
+
+impl PulseEvent {
+    fn matches(&self, pulse: &Pulse) -> bool {
+        pulse.destination == self.destination
+            && pulse.state == self.state
+            && self.source.is_none_or(|source| source == pulse.source)
+    }
+}
+
+fn decompose(state: &State, event: &PulseEvent) -> Option<Vec<PulseEvent>> {
+    if event.source.is_some() || event.state != PulseState::Low {
+        return None;
+    }
+    let [feeder] = state.parts[event.destination].module.inputs()[..] else {
+        return None;
+    };
+    let module = &state.parts[feeder].module;
+    if module.kind() != "conjunction" {
+        return None;
+    }
+    Some(
+        module
+            .inputs()
+            .into_iter()
+            .map(|source| PulseEvent {
+                source: Some(source),
+                destination: feeder,
+                state: PulseState::High,
+            })
+            .collect(),
+    )
+}
+
+fn first_common_press(state: &State, events: &[PulseEvent]) -> Option<u64> {
+    let mut state = state.clone();
+    let mut fired: Vec<Vec<usize>> = vec![vec![]; events.len()];
+    for press in 1..=MAX_PRESSES {
+        let mut this_press = vec![false; events.len()];
+        state.push_button();
+        state.process_pulses_with(|pulse| {
+            for (idx, event) in events.iter().enumerate() {
+                this_press[idx] |= event.matches(pulse);
+            }
+        });
+        if this_press.iter().all(|fired| *fired) {
+            return Some(press as u64);
+        }
+        for (presses, _) in fired.iter_mut().zip(this_press).filter(|(_, now)| *now) {
+            if presses.len() < 2 {
+                presses.push(press);
+            }
+        }
+        if fired.iter().all(|presses| presses.len() == 2) {
+            let congruences: Vec<(usize, usize)> = fired
+                .iter()
+                .map(|presses| (presses[0], presses[1] - presses[0]))
+                .collect();
+            let (residue, modulus) = crt(&congruences)?;
+            let first = congruences.iter().map(|(first, _)| *first).max().unwrap();
+            return Some(if residue >= first {
+                residue as u64
+            } else {
+                (residue + (first - residue).div_ceil(modulus) * modulus) as u64
+            });
+        }
+    }
+    None
+}
+
+// The first press on which every target module has received its pulse, or
+// None if that doesn't happen within MAX_PRESSES presses.
+pub fn presses_until_all(
+    state: &State,
+    targets: &[(&str, PulseState)],
+) -> Result<Option<u64>, String> {
+    let mut events = targets
+        .iter()
+        .map(|(id, pulse_state)| {
+            Ok(PulseEvent {
+                source: None,
+                destination: *state
+                    .index
+                    .get(id)
+                    .ok_or_else(|| format!("unknown module {:?}", id))?,
+                state: *pulse_state,
+            })
+        })
+        .collect::<Result<Vec<PulseEvent>, String>>()?;
+    if let [event] = events[..] {
+        if let Some(inputs) = decompose(state, &event) {
+            events = inputs;
+        }
+    }
+    Ok(first_common_press(state, &events))
+}
+
+pub fn presses_until(
+    state: &State,
+    id: &str,
+    pulse_state: PulseState,
+) -> Result<Option<u64>, String> {
+    presses_until_all(state, &[(id, pulse_state)])
+}
+
+fn part2_cycles(state: &State) -> Option<usize> {
+    let presses = presses_until(state, RX, PulseState::Low).ok()??;
+    Some(presses as usize)
+}
+
+pub fn part2_with(state: &State, algorithm: Algorithm) -> Option<usize> {
+    match algorithm {
+        Algorithm::Cycles => part2_cycles(state),
+        Algorithm::Simulate => part2_simulate(state),
+    }
+}
+
+pub fn part1(s: &str) -> Result<usize, ParseError> {
+    Ok(pulse_product(&State::try_from(s)?))
+}
+
+pub fn part2(s: &str) -> Result<Option<usize>, ParseError> {
+    Ok(part2_with(&State::try_from(s)?, Algorithm::Cycles))
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct PulseFilter {
+    source: Option<String>,
+    destination: Option<String>,
+    state: Option<PulseState>,
+}
+
+impl TryFrom<&str> for PulseFilter {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut filter = Self::default();
+        for term in value.split(',') {
+            match term.split_once('=') {
+                Some(("source", name)) => filter.source = Some(name.to_string()),
+                Some(("dest", name)) => filter.destination = Some(name.to_string()),
+                Some(("state", state)) => filter.state = Some(PulseState::try_from(state)?),
+                _ => return Err(format!("unknown watch term {:?}", term)),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+impl PulseFilter {
+    fn matches(&self, pulse: &Pulse, names: &[&str]) -> bool {
+        self.source
+            .as_ref()
+            .is_none_or(|source| source == names[pulse.source])
+            && self
+                .destination
+                .as_ref()
+                .is_none_or(|destination| destination == names[pulse.destination])
+            && self.state.is_none_or(|state| state == pulse.state)
+    }
+}
+
+pub fn watch_presses(
+    state: &mut State,
+    presses: usize,
+    filters: &[PulseFilter],
+    mut on_match: impl FnMut(usize, &Pulse),
+) {
+    let names = state.names.clone();
+    for press in 1..=presses {
+        let _press = tracing::info_span!("press", n = press).entered();
+        state.push_button();
+        state.process_pulses_with(|pulse| {
+            if filters.is_empty() || filters.iter().any(|filter| filter.matches(pulse, &names)) {
+                on_match(press, pulse);
+            }
+        });
+    }
+}
+
+// Button presses one pulse at a time, listing every module after each.
+pub fn simulate(state: &State, on_frame: OnFrame) {
+    let mut state = state.clone();
+    let mut presses = 0;
+    let text = |state: &State| state.module_lines().join("\n");
+    if on_frame(aoc_viz::Frame::new(text(&state), "Before the first press")).is_break() {
+        return;
+    }
+    while presses < MAX_PRESSES || !state.pulses.is_empty() {
+        if state.pulses.is_empty() {
+            state.push_button();
+            presses += 1;
+        }
+        let Some(pulse) = state.step() else {
+            return;
+        };
+        let caption = format!(
+            "Press {}: {} -{:?}-> {}",
+            presses, state.names[pulse.source], pulse.state, state.names[pulse.destination]
+        );
+        if on_frame(aoc_viz::Frame::new(text(&state), caption)).is_break() {
+            return;
+        }
+    }
+}
+
+#[cfg(feature = "debugger")]
+const HISTORY: usize = 200;
+
+#[cfg(feature = "debugger")]
+pub struct Debugger<'a> {
+    state: State<'a>,
+    presses: usize,
+    history: VecDeque<(usize, Pulse)>,
+    breakpoints: Vec<PulseFilter>,
+    status: String,
+}
+
+#[cfg(feature = "debugger")]
+impl<'a> Debugger<'a> {
+    pub fn new(state: State<'a>, breakpoints: Vec<PulseFilter>) -> Self {
+        Self {
+            state,
+            presses: 0,
+            history: VecDeque::new(),
+            breakpoints,
+            status: String::new(),
+        }
+    }
+
+    fn hits_breakpoint(&self, pulse: &Pulse) -> bool {
+        self.breakpoints
+            .iter()
+            .any(|filter| filter.matches(pulse, &self.state.names))
+    }
+
+    fn step_pulse(&mut self) -> Pulse {
+        if self.state.pulses.is_empty() {
+            self.state.push_button();
+            self.presses += 1;
+        }
+        let pulse = self.state.step().unwrap();
+        if self.history.len() == HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back((self.presses, pulse));
+        self.status = if self.hits_breakpoint(&pulse) {
+            format!(
+                "breakpoint: {} -{:?}-> {}",
+                self.state.names[pulse.source], pulse.state, self.state.names[pulse.destination]
+            )
+        } else {
+            String::new()
+        };
+        pulse
+    }
+
+    fn step_press(&mut self) -> bool {
+        loop {
+            let pulse = self.step_pulse();
+            if self.hits_breakpoint(&pulse) {
+                return true;
+            }
+            if self.state.pulses.is_empty() {
+                return false;
+            }
+        }
+    }
+
+    fn run(&mut self, limit: usize) -> bool {
+        for _ in 0..limit {
+            if self.step_press() {
+                return true;
+            }
+        }
+        self.status = format!("no breakpoint hit in {} presses", limit);
+        false
+    }
+}
+
+#[cfg(feature = "debugger")]
+fn draw_debugger(frame: &mut Frame, debugger: &Debugger) {
+    let [header, body] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(frame.area());
+    let [modules, pulses] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(body);
+    frame.render_widget(
+        Paragraph::new(format!(
+            "presses: {}  queued: {}  {}",
+            debugger.presses,
+            debugger.state.pulses.len(),
+            debugger.status
+        ))
+        .block(
+            Block::bordered()
+                .title("day20 debugger: [s]tep pulse, [p]ress, [r]un to breakpoint, [q]uit"),
+        ),
+        header,
+    );
+    let items = debugger.state.module_lines().into_iter().map(ListItem::new);
+    frame.render_widget(
+        List::new(items).block(Block::bordered().title("modules")),
+        modules,
+    );
+    let names = &debugger.state.names;
+    let visible = pulses.height.saturating_sub(2) as usize;
+    let items = debugger
+        .history
+        .iter()
+        .skip(debugger.history.len().saturating_sub(visible))
+        .map(|(press, pulse)| {
+            ListItem::new(format!(
+                "#{} {} -{:?}-> {}",
+                press, names[pulse.source], pulse.state, names[pulse.destination]
+            ))
+        });
+    frame.render_widget(
+        List::new(items).block(Block::bordered().title("pulses")),
+        pulses,
+    );
+}
+
+#[cfg(feature = "debugger")]
+pub fn debug_ui(terminal: &mut DefaultTerminal, debugger: &mut Debugger) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| draw_debugger(frame, debugger))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('s') | KeyCode::Char(' ') => {
+                    debugger.step_pulse();
+                }
+                KeyCode::Char('p') | KeyCode::Enter => {
+                    debugger.step_press();
+                }
+                KeyCode::Char('r') => {
+                    debugger.run(MAX_PRESSES);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// The modules reachable from each broadcaster output without passing through
+// the conjunction feeding rx. On real inputs these are the counters part 2
+// times; when they overlap there is no such structure and none are returned.
+pub fn counter_chains(state: &State) -> Vec<Vec<usize>> {
+    let (Some(&broadcaster), Some(&rx)) = (state.index.get(BROADCASTER), state.index.get(RX))
+    else {
+        return vec![];
+    };
+    let mut hub = state.parts[rx].module.inputs();
+    hub.push(rx);
+    let mut owner: Vec<Option<usize>> = vec![None; state.parts.len()];
+    let starts = &state.parts[broadcaster].destinations;
+    for (chain, &start) in starts.iter().enumerate() {
+        let mut queue = VecDeque::from([start]);
+        while let Some(idx) = queue.pop_front() {
+            if hub.contains(&idx) || owner[idx] == Some(chain) {
+                continue;
+            }
+            if owner[idx].is_some() {
+                return vec![];
+            }
+            owner[idx] = Some(chain);
+            queue.extend(state.parts[idx].destinations.iter().copied());
+        }
+    }
+    let mut chains = vec![vec![]; starts.len()];
+    for idx in state.sorted_ids() {
+        if let Some(chain) = owner[idx] {
+            chains[chain].push(idx);
+        }
+    }
+    chains
+}
+
+pub fn to_dot(state: &State) -> String {
+    let ids = state.sorted_ids();
+    let chains = counter_chains(state);
+    let mut dot = String::from("digraph {\n");
+    for &idx in ids.iter() {
+        if chains.iter().any(|chain| chain.contains(&idx)) {
+            continue;
+        }
+        dot += &format!(
+            "    {} [shape={}]\n",
+            state.names[idx],
+            dot_shape(state, idx)
+        );
+    }
+    for (chain, start) in chains
+        .iter()
+        .zip(&state.parts[state.index[BROADCASTER]].destinations)
+    {
+        dot += &format!(
+            "    subgraph cluster_{} {{\n        label=\"{}\"\n",
+            state.names[*start], state.names[*start]
+        );
+        for &idx in chain {
+            dot += &format!(
+                "        {} [shape={}]\n",
+                state.names[idx],
+                dot_shape(state, idx)
+            );
+        }
+        dot += "    }\n";
+    }
+    for &idx in ids.iter() {
+        for &destination in state.parts[idx].destinations.iter() {
+            dot += &format!("    {} -> {}\n", state.names[idx], state.names[destination]);
+        }
+    }
+    dot + "}\n"
+}
+
+fn dot_shape(state: &State, idx: usize) -> &'static str {
+    match state.parts[idx].module.kind() {
+        "button" => "plaintext",
+        "broadcaster" => "doublecircle",
+        "flip-flop" => "box",
+        "conjunction" => "invtrapezium",
+        "sink" if state.names[idx] == RX => "doubleoctagon, style=filled, fillcolor=red",
+        "sink" => "octagon",
+        _ => "ellipse",
+    }
+}
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Parsed<'a> = State<'a>;
+    type Error = ParseError;
+
+    fn day() -> u8 {
+        20
+    }
+
+    fn parse(input: &str) -> Result<State<'_>, ParseError> {
+        State::parse(input, &Registry::default(), false)
+    }
+
+    fn part1(state: &Self::Parsed<'_>) -> impl Answer {
+        pulse_product(state)
+    }
+
+    fn part2(state: &Self::Parsed<'_>) -> impl Answer {
+        part2_with(state, Algorithm::Cycles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use proptest::prelude::*;
+
+    const NAMES: [&str; 6] = ["a", "b", "c", "d", "e", "f"];
+
+    fn module_text() -> impl Strategy<Value = String> {
+        let destinations = prop::collection::vec(
+            prop::sample::select([&NAMES[..], &["rx", "output"]].concat()),
+            1..4,
+        )
+        .prop_map(|destinations| destinations.join(", "));
+        (
+            prop::collection::vec(
+                (prop::sample::select(vec!["%", "&"]), destinations.clone()),
+                NAMES.len(),
+            ),
+            destinations,
+            0..=NAMES.len(),
+        )
+            .prop_map(|(modules, broadcast, position)| {
+                let mut lines: Vec<String> = NAMES
+                    .iter()
+                    .zip(modules)
+                    .map(|(name, (prefix, destinations))| {
+                        format!("{}{} -> {}\n", prefix, name, destinations)
+                    })
+                    .collect();
+                lines.insert(position, format!("broadcaster -> {}\n", broadcast));
+                lines.concat()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_listing_round_trip(text in module_text()) {
+            let registry = Registry::default();
+            let state = State::try_from(text.as_str()).unwrap();
+            let listing = state.listing(&registry);
+            prop_assert_eq!(&listing, &text);
+            let reparsed = State::try_from(listing.as_str()).unwrap();
+            prop_assert_eq!(reparsed.listing(&registry), listing.clone());
+            // Not simulated: random wiring can pulse around a loop forever.
+            prop_assert_eq!(reparsed.names, state.names);
+        }
+    }
+
+    pub fn parse(s: &str) -> State<'_> {
+        State::try_from(s).unwrap()
+    }
+
+    const TEST_SIMPLE_INPUT: &str = include_str!("../../../tests/data/day20/example2.txt");
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day20/example.txt");
+
+    const TEST_RX_INPUT: &str = include_str!("../../../tests/data/day20/example3.txt");
+
+    #[test]
+    fn test_algorithm_names() {
+        assert_eq!(Algorithm::try_from("simulate"), Ok(Algorithm::Simulate));
+        assert_eq!(
+            Algorithm::try_from("fastest").unwrap_err(),
+            "invalid algorithm \"fastest\", expected cycles or simulate"
+        );
+    }
+
+    #[test]
+    fn test_simulate() {
+        let state = parse(TEST_SIMPLE_INPUT);
+        let frames = aoc_viz::take_frames(6, |on_frame| simulate(&state, on_frame));
+        assert_eq!(frames[0].caption, "Before the first press");
+        assert_eq!(frames[1].caption, "Press 1: button -Low-> broadcaster");
+        assert_eq!(frames[5].caption, "Press 1: a -High-> b");
+        assert_eq!(frames[5].text.lines().count(), state.names.len());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            part2_with(&parse(TEST_RX_INPUT), Algorithm::Simulate),
+            Some(3)
+        );
+        assert_eq!(
+            part2_with(&parse(TEST_RX_INPUT), Algorithm::Cycles),
+            Some(3)
+        );
+        assert_eq!(part2_with(&parse(TEST_INPUT), Algorithm::Cycles), None);
+        assert_eq!(part2_with(&parse(TEST_INPUT), Algorithm::Simulate), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let registry = Registry::default();
+        let errors = [
+            ("broadcaster -> a\n%a", ParseError::MissingArrow { line: 2 }),
+            (
+                "broadcaster -> a\n\n$a -> b",
+                ParseError::UnknownKind {
+                    line: 3,
+                    prefix: '$',
+                },
+            ),
+            (
+                "broadcaster -> a\n% -> b",
+                ParseError::EmptyName { line: 2 },
+            ),
+            ("broadcaster -> a, ", ParseError::EmptyName { line: 1 }),
+            (
+                "broadcaster -> a\n%a -> b\n&a -> c",
+                ParseError::DuplicateModule {
+                    line: 3,
+                    id: "a".to_string(),
+                    first: 2,
+                },
+            ),
+            ("%a -> b", ParseError::MissingBroadcaster),
+        ];
+        for (input, error) in errors {
+            assert_eq!(State::parse(input, &registry, false).unwrap_err(), error);
+        }
+        let dangling = ParseError::DanglingDestination {
+            line: 5,
+            id: "output".to_string(),
+        };
+        assert!(parse(TEST_INPUT).warnings.is_empty());
+        assert_eq!(
+            parse(&TEST_INPUT.replace("output", "nowhere")).warnings,
+            vec![ParseError::DanglingDestination {
+                line: 5,
+                id: "nowhere".to_string(),
+            }]
+        );
+        assert_eq!(
+            State::parse(TEST_INPUT, &registry, true).unwrap_err(),
+            dangling
+        );
+        assert!(State::parse(TEST_SIMPLE_INPUT, &registry, true)
+            .unwrap()
+            .warnings
+            .is_empty());
+        assert_eq!(
+            dangling.to_string(),
+            "line 5: destination output is never defined"
+        );
+    }
+
+    #[test]
+    fn test_conjunction_inputs() {
+        let state = parse(TEST_INPUT);
+        assert_eq!(state.part("con").unwrap().module.kind(), "conjunction");
+        assert_eq!(state.input_names("con"), vec!["a", "b"]);
+        assert_eq!(state.input_names("inv"), vec!["a"]);
+        assert_eq!(state.input_names("b"), Vec::<&str>::new());
+        assert_eq!(
+            state.part("a").unwrap().destinations,
+            vec![state.index["inv"], state.index["con"]]
+        );
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct Divider {
+        lows: usize,
+    }
+
+    impl Module for Divider {
+        fn kind(&self) -> &'static str {
+            "divider"
+        }
+
+        fn receive(&mut self, pulse: &Pulse) -> Option<PulseState> {
+            if pulse.state == PulseState::High {
+                return None;
+            }
+            self.lows += 1;
+            self.lows.is_multiple_of(2).then_some(PulseState::High)
+        }
+
+        fn boxed_clone(&self) -> Box<dyn Module> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_custom_module() {
+        let mut registry = Registry::default();
+        registry.register('#', || Box::new(Divider::default()));
+        let mut state = State::parse("broadcaster -> d\n#d -> out", &registry, false).unwrap();
+        assert_eq!(state.part("d").unwrap().module.kind(), "divider");
+        state.push_button();
+        assert_eq!(state.process_pulses(), (2, 0));
+        state.push_button();
+        assert_eq!(state.process_pulses(), (2, 1));
+    }
+
+    #[test]
+    fn test_pulses_after() {
+        assert_eq!(pulses_after(&parse(TEST_SIMPLE_INPUT), 1), Some((8, 4)));
+        assert_eq!(pulses_after(&parse(TEST_INPUT), 4), Some((17, 11)));
+        assert_eq!(pulses_after(&parse(TEST_INPUT), 1000), Some((4250, 2750)));
+        assert_eq!(
+            pulses_after(&parse(TEST_INPUT), 1_000_000_000_000),
+            Some((4_250_000_000_000, 2_750_000_000_000))
+        );
+        assert_eq!(
+            pulses_after(&parse(TEST_INPUT), 1003).unwrap().0,
+            4250 + 4 + 4 + 5
+        );
+        // A 21-bit ripple counter only repeats after 2^21 presses.
+        let counter = (0..21)
+            .map(|bit| format!("%f{} -> f{}", bit, bit + 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let counter = format!("broadcaster -> f0\n{}\n", counter);
+        let counter = parse(&counter);
+        assert!(pulses_after(&counter, 1000).is_some());
+        assert_eq!(pulses_after(&counter, 1 << 21), None);
+    }
+
+    #[test]
+    fn test_press_stats() {
+        let state = parse(TEST_INPUT);
+        let stats = press_stats(&state, 4);
+        assert_eq!(
+            stats
+                .iter()
+                .map(|stat| (stat.low, stat.high))
+                .collect::<Vec<_>>(),
+            vec![(4, 4), (4, 2), (5, 3), (4, 2)]
+        );
+        assert_eq!(
+            stats.iter().map(|stat| stat.low + stat.high).sum::<usize>() as u64,
+            {
+                let (low, high) = pulses_after(&state, 4).unwrap();
+                low + high
+            }
+        );
+        let csv = stats_csv(&state, &stats);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("press,low,high,a_low,a_high,b_low,b_high,broadcaster_low,broadcaster_high,button_low,button_high,con_low,con_high,inv_low,inv_high,output_low,output_high")
+        );
+        assert_eq!(lines.next(), Some("1,4,4,0,2,0,1,1,0,1,0,1,1,1,0,0,0"));
+        assert_eq!(csv.lines().count(), 5);
+    }
+
+    #[test]
+    fn test_presses_until() {
+        let state = parse(TEST_RX_INPUT);
+        assert_eq!(presses_until(&state, "rx", PulseState::Low), Ok(Some(3)));
+        assert_eq!(presses_until(&state, "feed", PulseState::High), Ok(Some(1)));
+        assert_eq!(presses_until(&state, "jc", PulseState::Low), Ok(Some(1)));
+        assert_eq!(
+            presses_until_all(&state, &[("ja", PulseState::Low), ("jc", PulseState::Low)]),
+            Ok(Some(3))
+        );
+        assert_eq!(presses_until(&state, "rx", PulseState::High), Ok(Some(1)));
+        assert_eq!(
+            presses_until(&state, "missing", PulseState::Low),
+            Err("unknown module \"missing\"".to_string())
+        );
+        let state = parse(TEST_INPUT);
+        assert_eq!(
+            presses_until(&state, "output", PulseState::Low),
+            Ok(Some(1))
+        );
+    }
+
+    #[test]
+    fn test_vcd() {
+        assert_eq!(vcd_id(0), "!");
+        assert_eq!(vcd_id(93), "~");
+        assert_eq!(vcd_id(94), "!!");
+        let mut out = Vec::new();
+        write_vcd(&parse(TEST_INPUT), 1, &mut out).unwrap();
+        let vcd = String::from_utf8(out).unwrap();
+        assert!(vcd.contains("$var wire 1 \" a $end\n$var wire 1 # b $end\n"));
+        assert!(vcd.contains("$var integer 32 ! press $end\n"));
+        assert!(vcd.ends_with("#1\nb1 !\n#4\n1\"\n#7\n1&\n#8\n1#\n#9\n0&\n"));
+    }
+
+    #[test]
+    fn test_sinks() {
+        let mut state = parse(TEST_INPUT);
+        assert_eq!(state.part("output").unwrap().module.kind(), "sink");
+        assert_eq!(state.input_names("output"), vec!["con"]);
+        for _ in 0..4 {
+            state.push_button();
+            state.process_pulses();
+        }
+        assert_eq!(state.sinks(), vec![("output", 2, 4)]);
+        let state = parse(TEST_RX_INPUT);
+        assert_eq!(state.sinks(), vec![("rx", 0, 0)]);
+        assert_eq!(state.input_names("rx"), vec!["feed"]);
+    }
+
+    #[cfg(feature = "debugger")]
+    #[test]
+    fn test_debugger() {
+        let breakpoints = vec![PulseFilter::try_from("source=con,state=low").unwrap()];
+        let mut debugger = Debugger::new(parse(TEST_INPUT), breakpoints);
+        let pulse = debugger.step_pulse();
+        let names = debugger.state.names.clone();
+        assert_eq!(
+            (names[pulse.source], names[pulse.destination]),
+            (BUTTON, BROADCASTER)
+        );
+        assert_eq!(debugger.presses, 1);
+        assert_eq!(
+            debugger.state.part("a").unwrap().module.describe(&names),
+            "off"
+        );
+        debugger.step_pulse();
+        assert_eq!(
+            debugger.state.part("a").unwrap().module.describe(&names),
+            "on"
+        );
+        assert!(debugger.step_press());
+        assert_eq!(debugger.status, "breakpoint: con -Low-> output");
+        assert_eq!(
+            debugger.state.part("con").unwrap().module.describe(&names),
+            "a=High b=High"
+        );
+        let hit_at = debugger.presses;
+        assert!(debugger.run(10));
+        assert_eq!(debugger.presses, hit_at + 2);
+        let (press, pulse) = debugger.history.back().unwrap();
+        assert_eq!(
+            (*press, names[pulse.source], pulse.state),
+            (hit_at + 2, "con", PulseState::Low)
+        );
+        let mut debugger = Debugger::new(parse(TEST_INPUT), vec![]);
+        assert!(!debugger.run(HISTORY));
+        assert_eq!(debugger.history.len(), HISTORY);
+        assert_eq!(
+            debugger.status,
+            format!("no breakpoint hit in {} presses", HISTORY)
+        );
+    }
+
+    #[test]
+    fn test_watch_filters() {
+        let filter = PulseFilter::try_from("dest=rx,state=High").unwrap();
+        assert_eq!(
+            filter,
+            PulseFilter {
+                source: None,
+                destination: Some("rx".to_string()),
+                state: Some(PulseState::High),
+            }
+        );
+        assert!(PulseFilter::try_from("state=medium").is_err());
+        assert!(PulseFilter::try_from("colour=red").is_err());
+        let filters = vec![
+            PulseFilter::try_from("source=inv").unwrap(),
+            PulseFilter::try_from("dest=output,state=low").unwrap(),
+        ];
+        let mut state = parse(TEST_INPUT);
+        let names = state.names.clone();
+        let mut seen = vec![];
+        watch_presses(&mut state, 2, &filters, |press, pulse| {
+            seen.push((
+                press,
+                names[pulse.source],
+                names[pulse.destination],
+                pulse.state,
+            ));
+        });
+        assert_eq!(
+            seen,
+            vec![
+                (1, "inv", "b", PulseState::Low),
+                (1, "con", "output", PulseState::Low),
+                (2, "inv", "b", PulseState::High),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_counter_chains() {
+        let state = parse(TEST_RX_INPUT);
+        let names = |chain: &Vec<usize>| {
+            chain
+                .iter()
+                .map(|&idx| state.names[idx])
+                .collect::<Vec<_>>()
+        };
+        let chains = counter_chains(&state);
+        assert_eq!(
+            chains.iter().map(names).collect::<Vec<_>>(),
+            [vec!["a", "b", "ia", "ja"], vec!["c", "ic", "jc"]]
+        );
+        assert!(counter_chains(&parse(TEST_INPUT)).is_empty());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(&parse(TEST_INPUT));
+        assert!(dot.starts_with("digraph {\n    a [shape=box]\n"));
+        assert!(dot.contains("    broadcaster [shape=doublecircle]\n"));
+        assert!(dot.contains("    con [shape=invtrapezium]\n"));
+        assert!(dot.contains("    output [shape=octagon]\n"));
+        assert!(dot.contains("    a -> inv\n    a -> con\n"));
+        let dot = to_dot(&parse(TEST_RX_INPUT));
+        assert!(dot.contains("    rx [shape=doubleoctagon, style=filled, fillcolor=red]\n"));
+        assert!(dot.contains("    feed -> rx\n"));
+        assert!(dot.contains(
+            "    subgraph cluster_c {\n        label=\"c\"\n        c [shape=box]\n        ic [shape=invtrapezium]\n        jc [shape=invtrapezium]\n    }\n"
+        ));
+        assert!(dot.contains("    feed [shape=invtrapezium]\n"));
+    }
+}
@@ -1,275 +1,198 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    fs::read_to_string,
-    rc::Rc,
-};
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-enum PulseState {
-    Low,
-    High,
-}
-
-impl Default for PulseState {
-    fn default() -> Self {
-        Self::Low
-    }
+use aoc_core::timing::Timings;
+use aoc_input::cli::{Answers, DayArgs};
+use clap::Parser;
+use day20::*;
+use std::io;
+
+fn parse_target(value: &str) -> Result<(String, PulseState), String> {
+    let (id, state) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected MODULE=STATE, got `{}`", value))?;
+    Ok((id.to_string(), PulseState::try_from(state)?))
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-struct Pulse<'a> {
-    state: PulseState,
-    source: &'a str,
-    destination: &'a str,
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    day: DayArgs,
+    /// Reject destinations that are never defined instead of treating them as sinks
+    #[arg(long)]
+    strict: bool,
+    /// Print the module graph in Graphviz format, with each counter chain feeding rx boxed
+    #[arg(long)]
+    dot: bool,
+    /// Step through button presses in an interactive debugger
+    #[cfg(feature = "debugger")]
+    #[arg(long)]
+    debug: bool,
+    /// Pause the debugger on pulses matching FILTER
+    #[cfg(feature = "debugger")]
+    #[arg(long = "break", value_name = "FILTER", value_parser = |s: &str| PulseFilter::try_from(s))]
+    breakpoints: Vec<PulseFilter>,
+    /// Log every pulse sent during the presses
+    #[arg(long)]
+    trace: bool,
+    /// Only trace pulses matching FILTER
+    #[arg(long, value_name = "FILTER", value_parser = |s: &str| PulseFilter::try_from(s))]
+    watch: Vec<PulseFilter>,
+    /// Number of button presses to trace
+    #[arg(long, default_value_t = 1)]
+    presses: usize,
+    /// Time PRESSES button presses
+    #[arg(long, value_name = "PRESSES")]
+    bench: Option<usize>,
+    /// Write PRESSES button presses as a VCD waveform
+    #[arg(long, value_name = "PRESSES")]
+    vcd: Option<usize>,
+    /// Print per-module pulse counts over PRESSES button presses as CSV
+    #[arg(long, value_name = "PRESSES")]
+    csv: Option<usize>,
+    /// Count presses until every MODULE=STATE target is seen
+    #[arg(long, value_name = "MODULE=STATE", value_parser = parse_target)]
+    until: Vec<(String, PulseState)>,
+    /// Print the pulses received by undefined destination modules after 1000 presses
+    #[arg(long)]
+    sinks: bool,
+    /// Count the low and high pulses sent over PRESSES button presses
+    #[arg(long, value_name = "PRESSES")]
+    pulses_after: Option<u64>,
+    /// Solve with cycles or simulate
+    #[arg(long, default_value = "cycles", value_parser = |s: &str| Algorithm::try_from(s))]
+    algo: Algorithm,
 }
 
-#[derive(Clone, PartialEq, Eq, Debug)]
-enum PartKind<'a> {
-    Button,
-    Broadcaster,
-    FlipFlop {
-        on: bool,
-    },
-    Conjunction {
-        input_state: Vec<(&'a str, PulseState)>,
-    },
-}
-
-#[derive(Clone, PartialEq, Eq, Debug)]
-struct Part<'a> {
-    kind: PartKind<'a>,
-    id: &'a str,
-    destinations: Rc<[&'a str]>,
-}
-
-impl<'a, 'b> From<&'b str> for Part<'a>
-where
-    'b: 'a,
-{
-    fn from(value: &'b str) -> Part<'a> {
-        let (kind_and_name, destinations) = value.split_once(" -> ").unwrap();
-        let (kind, id) = match kind_and_name {
-            BROADCASTER => (PartKind::Broadcaster, BROADCASTER),
-            _ => match kind_and_name.split_at(1) {
-                ("%", name) => (PartKind::FlipFlop { on: false }, name),
-                ("&", name) => (
-                    PartKind::Conjunction {
-                        input_state: Vec::default(),
-                    },
-                    name,
-                ),
-                _ => panic!("Unknown part type!"),
-            },
-        };
-        let destinations = destinations.split(", ").collect();
-        Self {
-            kind,
-            id,
-            destinations,
+fn main() {
+    let cli = Cli::parse();
+    // The pulses --trace logs are info events, which -v would also show.
+    let _log = aoc_input::log::init(
+        cli.day.verbose.max(cli.trace as u8),
+        cli.day.profile.as_deref(),
+    );
+    let args = &cli.day;
+    let mut timings = Timings::new(args.time);
+    let input = timings
+        .time("read", || args.load(2023, 20, env!("CARGO_MANIFEST_DIR")))
+        .unwrap_or_else(|err| {
+            eprintln!("Could not load input: {}", err);
+            std::process::exit(1);
+        });
+    let state = match timings.time("parse", || {
+        State::parse(&input, &Registry::default(), cli.strict)
+    }) {
+        Ok(state) => state,
+        Err(err) => {
+            eprintln!("Invalid input: {}", err);
+            std::process::exit(1);
         }
+    };
+    for warning in state.warnings.iter() {
+        eprintln!("warning: {}", warning);
     }
-}
-
-impl<'a> Part<'a> {
-    fn process_pulse(&mut self, pulse: Pulse<'a>) -> Vec<Pulse<'a>> {
-        match &mut self.kind {
-            PartKind::Broadcaster => self
-                .destinations
-                .iter()
-                .map(|d| Pulse {
-                    source: self.id,
-                    destination: d,
-                    state: pulse.state,
-                })
-                .collect(),
-            PartKind::FlipFlop { on } => match pulse.state {
-                PulseState::High => vec![],
-                PulseState::Low => {
-                    *on = !*on;
-                    let state = if *on {
-                        PulseState::High
-                    } else {
-                        PulseState::Low
-                    };
-                    self.destinations
-                        .iter()
-                        .map(|d| Pulse {
-                            source: self.id,
-                            destination: d,
-                            state,
-                        })
-                        .collect()
-                }
-            },
-            PartKind::Conjunction { input_state } => {
-                input_state
-                    .iter_mut()
-                    .find(|(name, _)| *name == pulse.source)
-                    .unwrap()
-                    .1 = pulse.state;
-                let state = if input_state
-                    .iter()
-                    .all(|(_, state)| *state == PulseState::High)
-                {
-                    PulseState::Low
-                } else {
-                    PulseState::High
-                };
-                self.destinations
-                    .iter()
-                    .map(|d| Pulse {
-                        source: self.id,
-                        destination: d,
-                        state,
-                    })
-                    .collect()
-            }
-            PartKind::Button => panic!("Button can't receive pulses!"),
-        }
+    if cli.dot {
+        print!("{}", to_dot(&state));
+        return;
     }
-}
-
-#[derive(Clone, PartialEq, Eq, Debug)]
-struct State<'a> {
-    parts: HashMap<&'a str, Part<'a>>,
-    pulses: VecDeque<Pulse<'a>>,
-}
-
-const BROADCASTER: &str = "broadcaster";
-const BUTTON: &str = "button";
-
-impl<'a, 'b> From<&'b str> for State<'a>
-where
-    'b: 'a,
-{
-    fn from(value: &'b str) -> Self {
-        let mut parts: HashMap<&'a str, Part<'a>> =
-            value.lines().map(Part::from).map(|p| (p.id, p)).collect();
-        parts.insert(
-            BUTTON,
-            Part {
-                kind: PartKind::Button,
-                id: BUTTON,
-                destinations: vec![BROADCASTER].into(),
-            },
+    #[cfg(feature = "debugger")]
+    if cli.debug {
+        let mut debugger = Debugger::new(state, cli.breakpoints.clone());
+        let mut terminal = ratatui::init();
+        let result = debug_ui(&mut terminal, &mut debugger);
+        ratatui::restore();
+        result.unwrap();
+        return;
+    }
+    if cli.trace {
+        let mut state = state;
+        let names = state.names.clone();
+        watch_presses(&mut state, cli.presses, &cli.watch, |_, pulse| {
+            tracing::info!(
+                source = names[pulse.source],
+                destination = names[pulse.destination],
+                state = ?pulse.state,
+                "pulse"
+            );
+        });
+        return;
+    }
+    if let Some(presses) = cli.bench {
+        let (pulses, elapsed) = benchmark(&state, presses);
+        println!(
+            "{} presses, {} pulses in {:?} ({:.0} pulses/s)",
+            presses,
+            pulses,
+            elapsed,
+            pulses as f64 / elapsed.as_secs_f64()
         );
-        for part_id in parts.clone().into_keys() {
-            for part in parts.clone().into_values() {
-                if part.destinations.contains(&part_id) {
-                    if let PartKind::Conjunction { input_state } =
-                        &mut parts.get_mut(&part_id).unwrap().kind
-                    {
-                        input_state.push((part.id, PulseState::Low));
-                    }
-                }
+        return;
+    }
+    if let Some(presses) = cli.vcd {
+        write_vcd(
+            &state,
+            presses,
+            &mut io::BufWriter::new(io::stdout().lock()),
+        )
+        .unwrap();
+        return;
+    }
+    if let Some(presses) = cli.csv {
+        let stats = press_stats(&state, presses);
+        print!("{}", stats_csv(&state, &stats));
+        return;
+    }
+    if !cli.until.is_empty() {
+        let targets: Vec<(&str, PulseState)> = cli
+            .until
+            .iter()
+            .map(|(id, pulse_state)| (id.as_str(), *pulse_state))
+            .collect();
+        match presses_until_all(&state, &targets) {
+            Ok(Some(presses)) => println!("{}", presses),
+            Ok(None) => println!("not reached within {} presses", MAX_PRESSES),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
             }
         }
-
-        Self {
-            parts,
-            pulses: VecDeque::default(),
-        }
+        return;
     }
-}
-
-impl<'a> State<'a> {
-    fn process_pulses(&mut self) -> (usize, usize) {
-        let mut low = 0;
-        let mut high = 0;
-        while let Some(pulse) = self.pulses.pop_front() {
-            match pulse.state {
-                PulseState::Low => low += 1,
-                PulseState::High => high += 1,
-            };
-            if let Some(destination_part) = self.parts.get_mut(&pulse.destination) {
-                self.pulses.extend(destination_part.process_pulse(pulse));
-            }
+    if cli.sinks {
+        let mut state = state;
+        for _ in 0..1000 {
+            state.push_button();
+            state.process_pulses();
+        }
+        for (id, low, high) in state.sinks() {
+            let sources = state.input_names(id).join(", ");
+            println!("{} <- {}: low={} high={}", id, sources, low, high);
         }
-        (low, high)
+        return;
     }
-
-    fn process_pulses_part2(&mut self) -> bool {
-        let mut rx_low_pulses: usize = 0;
-        while let Some(pulse) = self.pulses.pop_front() {
-            if pulse.state == PulseState::Low && pulse.destination == "rx" {
-                rx_low_pulses += 1;
-            }
-            if let Some(destination_part) = self.parts.get_mut(&pulse.destination) {
-                self.pulses.extend(destination_part.process_pulse(pulse));
+    if let Some(presses) = cli.pulses_after {
+        match pulses_after(&state, presses) {
+            Some((low, high)) => println!(
+                "low: {}, high: {}, product: {}",
+                low,
+                high,
+                low as u128 * high as u128
+            ),
+            None => {
+                eprintln!("no cycle found within {} presses", MAX_PRESSES);
+                std::process::exit(1);
             }
         }
-        rx_low_pulses != 0
-    }
-
-    fn push_button(&mut self) {
-        self.pulses.push_back(Pulse {
-            state: PulseState::Low,
-            source: BUTTON,
-            destination: BROADCASTER,
-        });
+        return;
     }
-}
-
-fn part1(s: &str) -> usize {
-    let mut state = State::from(s);
-    let mut low = 0;
-    let mut high = 0;
-    for _ in 0..1000 {
-        state.push_button();
-        let (lows, highs) = state.process_pulses();
-        low += lows;
-        high += highs;
+    let mut answers = Answers::new(args);
+    if args.runs(1) {
+        answers.report(1, timings.time("part 1", || pulse_product(&state)));
     }
-    low * high
-}
-
-fn part2(s: &str) -> usize {
-    let mut state = State::from(s);
-    let mut count: usize = 1;
-    state.push_button();
-    while !state.process_pulses_part2() {
-        count += 1;
-        if count % 1_000_000 == 0 {
-            dbg!(count);
+    let algorithm = cli.algo;
+    if args.runs(2) {
+        match timings.time("part 2", || part2_with(&state, algorithm)) {
+            Some(answer2) => answers.report(2, answer2),
+            None => eprintln!("Part 2: rx is not driven by counter cycles, try --algo simulate"),
         }
-        state.push_button();
-    }
-    count
-}
-
-fn main() {
-    let input = read_to_string("input.txt").unwrap();
-    let answer1 = part1(&input);
-    println!("Part 1: {}", answer1);
-    let answer2 = part2(&input);
-    println!("Part 2: {}", answer2);
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::*;
-
-    const TEST_SIMPLE_INPUT: &str = "broadcaster -> a, b, c
-%a -> b
-%b -> c
-%c -> inv
-&inv -> a";
-    const TEST_INPUT: &str = "broadcaster -> a
-%a -> inv, con
-&inv -> b
-%b -> con
-&con -> output";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_SIMPLE_INPUT), 8_000 * 4_000);
-        assert_eq!(part1(TEST_INPUT), 4250 * 2750);
-    }
-
-    /*
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 167409079868000);
     }
-    */
+    answers.finish();
+    timings.print();
 }
@@ -0,0 +1,196 @@
+use aoc_core::{Answer, AocError, Grid, Solver};
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Tile {
+    Plot,
+    Rock,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' | 'S' => Ok(Self::Plot),
+            '#' => Ok(Self::Rock),
+            _ => Err(AocError::invalid("tile", &value.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Garden {
+    pub map: Grid<Tile>,
+    pub start: (usize, usize),
+}
+
+impl TryFrom<&str> for Garden {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let chars = Grid::<char>::try_from(value)?;
+        let start = chars
+            .find(|c| *c == 'S')
+            .ok_or(AocError::Missing("start position"))?;
+        // The extrapolation in part 2 steps whole tiles at a time.
+        if chars.width() != chars.height() {
+            let size = format!("{}x{}", chars.width(), chars.height());
+            return Err(AocError::invalid("garden size", &size));
+        }
+        Ok(Self {
+            map: chars.try_map(|c| Tile::try_from(*c))?,
+            start,
+        })
+    }
+}
+
+impl Garden {
+    fn is_plot(&self, (x, y): (isize, isize), infinite: bool) -> bool {
+        let (width, height) = (self.map.width() as isize, self.map.height() as isize);
+        let inside = (0..width).contains(&x) && (0..height).contains(&y);
+        if !(infinite || inside) {
+            return false;
+        }
+        let pos = (x.rem_euclid(width) as usize, y.rem_euclid(height) as usize);
+        self.map[pos] == Tile::Plot
+    }
+
+    // The number of plots first reached after exactly 0, 1, 2, ... steps.
+    fn ring_sizes(&self, max_steps: usize, infinite: bool) -> Vec<usize> {
+        let start = (self.start.0 as isize, self.start.1 as isize);
+        let mut seen = HashSet::from([start]);
+        let mut frontier = vec![start];
+        let mut sizes = Vec::with_capacity(max_steps + 1);
+        for _ in 0..=max_steps {
+            sizes.push(frontier.len());
+            let mut next = Vec::new();
+            for (x, y) in frontier {
+                for pos in [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)] {
+                    if self.is_plot(pos, infinite) && seen.insert(pos) {
+                        next.push(pos);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        sizes
+    }
+
+    // A plot reached in fewer steps can still be ended on by stepping back
+    // and forth, as long as the remaining steps are even.
+    fn count(sizes: &[usize], steps: usize) -> usize {
+        sizes[..=steps].iter().skip(steps % 2).step_by(2).sum()
+    }
+
+    pub fn reachable(&self, steps: usize, infinite: bool) -> usize {
+        Self::count(&self.ring_sizes(steps, infinite), steps)
+    }
+
+    // The real input has a clear start row and column, so the reachable area
+    // grows quadratically in the number of whole tiles crossed. Fit the
+    // quadratic on the first three tile boundaries and evaluate it.
+    pub fn reachable_extrapolated(&self, steps: usize) -> usize {
+        let size = self.map.width();
+        let offset = steps % size;
+        let sizes = self.ring_sizes(offset + 2 * size, true);
+        let [a0, a1, a2] = [0, 1, 2].map(|tiles| Self::count(&sizes, offset + tiles * size));
+        let x = steps / size;
+        a0 + x * (a1 - a0) + x * x.saturating_sub(1) / 2 * (a2 + a0 - 2 * a1)
+    }
+}
+
+const STEPS: usize = 64;
+const INFINITE_STEPS: usize = 26_501_365;
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    Ok(Garden::try_from(s)?.reachable(STEPS, false))
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    Ok(Garden::try_from(s)?.reachable_extrapolated(INFINITE_STEPS))
+}
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    type Parsed<'a> = Garden;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        21
+    }
+
+    fn parse(input: &str) -> Result<Garden, AocError> {
+        Garden::try_from(input)
+    }
+
+    fn part1(garden: &Self::Parsed<'_>) -> impl Answer {
+        garden.reachable(STEPS, false)
+    }
+
+    fn part2(garden: &Self::Parsed<'_>) -> impl Answer {
+        garden.reachable_extrapolated(INFINITE_STEPS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day21/example.txt");
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part1("..\n.#").unwrap_err().to_string(),
+            "missing start position"
+        );
+        assert_eq!(part1("S.\n.x").unwrap_err().to_string(), "invalid tile `x`");
+        assert_eq!(
+            part2("S..\n.#.").unwrap_err().to_string(),
+            "invalid garden size `3x2`"
+        );
+        assert_eq!(
+            part2("S..\n.#").unwrap_err().to_string(),
+            "line 2: expected 3 columns, found 2"
+        );
+    }
+
+    #[test]
+    fn test_reachable() {
+        let garden = Garden::try_from(TEST_INPUT).unwrap();
+        assert_eq!(garden.start, (5, 5));
+        assert_eq!(garden.reachable(6, false), 16);
+    }
+
+    #[test]
+    fn test_reachable_infinite() {
+        let garden = Garden::try_from(TEST_INPUT).unwrap();
+        assert_eq!(garden.reachable(6, true), 16);
+        assert_eq!(garden.reachable(10, true), 50);
+        assert_eq!(garden.reachable(50, true), 1594);
+        assert_eq!(garden.reachable(100, true), 6536);
+        assert_eq!(garden.reachable(500, true), 167004);
+    }
+
+    #[test]
+    fn test_reachable_extrapolated() {
+        let garden = Garden::try_from(
+            ".......
+.#...#.
+..#.#..
+...S...
+..#.#..
+.#...#.
+.......",
+        )
+        .unwrap();
+        for steps in [3, 10, 45, 101] {
+            assert_eq!(
+                garden.reachable_extrapolated(steps),
+                garden.reachable(steps, true)
+            );
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use aoc_core::{
+    error::{parse_lines, parse_number, split_once},
+    Answer, AocError, Solver,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+pub struct Brick {
+    pub start: (usize, usize, usize),
+    pub end: (usize, usize, usize),
+}
+
+fn parse_point(s: &str) -> Result<(usize, usize, usize), AocError> {
+    let coords = s
+        .split(',')
+        .map(|c| parse_number("coordinate", c))
+        .collect::<Result<Vec<usize>, _>>()?;
+    match coords[..] {
+        [x, y, z] => Ok((x, y, z)),
+        _ => Err(AocError::invalid("point", s)),
+    }
+}
+
+impl TryFrom<&str> for Brick {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (start, end) = split_once(value, "~", "brick")?;
+        let (start, end) = (parse_point(start)?, parse_point(end)?);
+        Ok(Self {
+            start: (start.0.min(end.0), start.1.min(end.1), start.2.min(end.2)),
+            end: (start.0.max(end.0), start.1.max(end.1), start.2.max(end.2)),
+        })
+    }
+}
+
+impl Brick {
+    fn footprint(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.start.0..=self.end.0)
+            .flat_map(move |x| (self.start.1..=self.end.1).map(move |y| (x, y)))
+    }
+
+    fn height(&self) -> usize {
+        self.end.2 - self.start.2
+    }
+}
+
+// Bricks are kept in settled order, so a brick's supporters always come
+// before it.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Stack {
+    pub bricks: Vec<Brick>,
+    pub supported_by: Vec<Vec<usize>>,
+    pub supports: Vec<Vec<usize>>,
+}
+
+impl TryFrom<&str> for Stack {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut bricks = parse_lines(value, Brick::try_from)?;
+        bricks.sort_by_key(|brick| brick.start.2);
+        Ok(Self::settle(bricks))
+    }
+}
+
+impl Stack {
+    fn settle(mut bricks: Vec<Brick>) -> Self {
+        let mut tops: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut supported_by = vec![Vec::new(); bricks.len()];
+        let mut supports = vec![Vec::new(); bricks.len()];
+        for (idx, brick) in bricks.iter_mut().enumerate() {
+            let below: Vec<(usize, usize)> = brick
+                .footprint()
+                .filter_map(|pos| tops.get(&pos).copied())
+                .collect();
+            let rest = below.iter().map(|(z, _)| *z).max().unwrap_or(0);
+            let supporters: HashSet<usize> = below
+                .into_iter()
+                .filter(|(z, _)| *z == rest && rest > 0)
+                .map(|(_, id)| id)
+                .collect();
+            for &id in &supporters {
+                supports[id].push(idx);
+            }
+            supported_by[idx] = supporters.into_iter().collect();
+            let height = brick.height();
+            brick.start.2 = rest + 1;
+            brick.end.2 = rest + 1 + height;
+            for pos in brick.footprint() {
+                tops.insert(pos, (brick.end.2, idx));
+            }
+        }
+        Self {
+            bricks,
+            supported_by,
+            supports,
+        }
+    }
+
+    pub fn is_safe(&self, idx: usize) -> bool {
+        self.supports[idx]
+            .iter()
+            .all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    pub fn chain_reaction(&self, idx: usize) -> usize {
+        let mut falling = vec![false; self.bricks.len()];
+        falling[idx] = true;
+        let mut count = 0;
+        for above in idx + 1..self.bricks.len() {
+            let supporters = &self.supported_by[above];
+            if !supporters.is_empty() && supporters.iter().all(|&id| falling[id]) {
+                falling[above] = true;
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub fn safe_count(&self) -> usize {
+        (0..self.bricks.len())
+            .filter(|&idx| self.is_safe(idx))
+            .count()
+    }
+
+    pub fn total_falls(&self) -> usize {
+        (0..self.bricks.len())
+            .map(|idx| self.chain_reaction(idx))
+            .sum()
+    }
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    Ok(Stack::try_from(s)?.safe_count())
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    Ok(Stack::try_from(s)?.total_falls())
+}
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Parsed<'a> = Stack;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        22
+    }
+
+    fn parse(input: &str) -> Result<Stack, AocError> {
+        Stack::try_from(input)
+    }
+
+    fn part1(stack: &Self::Parsed<'_>) -> impl Answer {
+        stack.safe_count()
+    }
+
+    fn part2(stack: &Self::Parsed<'_>) -> impl Answer {
+        stack.total_falls()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day22/example.txt");
+
+    #[test]
+    fn test_settle() {
+        let stack = Stack::try_from(TEST_INPUT).unwrap();
+        assert_eq!(stack.bricks[2].start.2, 2);
+        assert_eq!(stack.bricks[6].start, (1, 1, 5));
+        assert_eq!(stack.bricks[6].end, (1, 1, 6));
+        assert_eq!(stack.supports[0].len(), 2);
+        assert_eq!(stack.supported_by[0], Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part1("1,0,1~1,2,1\n0,0~2,0,2").unwrap_err().to_string(),
+            "line 2: invalid point `0,0`"
+        );
+    }
+
+    #[test]
+    fn test_chain_reaction() {
+        let stack = Stack::try_from(TEST_INPUT).unwrap();
+        assert_eq!(stack.chain_reaction(0), 6);
+        assert_eq!(stack.chain_reaction(5), 1);
+    }
+}
@@ -0,0 +1,26 @@
+use aoc_core::{error::OrExit, timing::Timings};
+use aoc_input::cli::{Answers, DayArgs};
+use day22::*;
+
+fn main() {
+    let args = DayArgs::from_env();
+    let _log = args.init_logging();
+    let mut timings = Timings::new(args.time);
+    let input = timings
+        .time("read", || args.load(2023, 22, env!("CARGO_MANIFEST_DIR")))
+        .or_exit("Could not load input");
+    let stack = timings
+        .time("parse", || {
+            args.parse_cached(2023, 22, "stack-1", &input, |input| Stack::try_from(input))
+        })
+        .or_exit("Could not parse input");
+    let mut answers = Answers::new(&args);
+    if args.runs(1) {
+        answers.report(1, timings.time("part 1", || stack.safe_count()));
+    }
+    if args.runs(2) {
+        answers.report(2, timings.time("part 2", || stack.total_falls()));
+    }
+    answers.finish();
+    timings.print();
+}
@@ -0,0 +1,261 @@
+use aoc_core::{Answer, AocError, Grid, Solver};
+use std::collections::HashMap;
+
+// Up, right, down, left, indexed by Tile::Slope.
+const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Tile {
+    Path,
+    Forest,
+    Slope(usize),
+}
+
+impl TryFrom<char> for Tile {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
+            '.' => Self::Path,
+            '#' => Self::Forest,
+            '^' => Self::Slope(0),
+            '>' => Self::Slope(1),
+            'v' => Self::Slope(2),
+            '<' => Self::Slope(3),
+            _ => return Err(AocError::invalid("tile", &value.to_string())),
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Trails {
+    pub map: Grid<Tile>,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl TryFrom<&str> for Trails {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let map = Grid::<char>::try_from(value)?.try_map(|c| Tile::try_from(*c))?;
+        if map.height() == 0 {
+            return Err(AocError::Missing("map"));
+        }
+        let opening = |y: usize, what| {
+            map.row(y)
+                .iter()
+                .position(|t| *t == Tile::Path)
+                .map(|x| (x, y))
+                .ok_or(AocError::Missing(what))
+        };
+        let start = opening(0, "start opening")?;
+        let end = opening(map.height() - 1, "end opening")?;
+        Ok(Self { map, start, end })
+    }
+}
+
+// Junctions are the start, the end and every tile where trails fork. Each edge
+// is the length of the corridor between two junctions.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Graph {
+    pub edges: Vec<Vec<(usize, usize)>>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Trails {
+    fn moves(
+        &self,
+        pos: (usize, usize),
+        slippery: bool,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let directions: &[(isize, isize)] = match self.map[pos] {
+            Tile::Slope(direction) if slippery => &DIRECTIONS[direction..=direction],
+            _ => &DIRECTIONS,
+        };
+        directions
+            .iter()
+            .filter_map(move |&offset| self.map.step(pos, offset))
+            .filter(|&next| self.map[next] != Tile::Forest)
+    }
+
+    pub fn junctions(&self) -> Vec<(usize, usize)> {
+        let mut junctions = vec![self.start, self.end];
+        junctions.extend(
+            self.map
+                .positions()
+                .filter(|&pos| self.map[pos] != Tile::Forest && self.moves(pos, false).count() > 2),
+        );
+        junctions
+    }
+
+    pub fn graph(&self, slippery: bool) -> Graph {
+        let junctions = self.junctions();
+        let ids: HashMap<(usize, usize), usize> = junctions
+            .iter()
+            .enumerate()
+            .map(|(id, pos)| (*pos, id))
+            .collect();
+        let mut edges = vec![Vec::new(); junctions.len()];
+        for (from, &junction) in junctions.iter().enumerate() {
+            for first in self.moves(junction, slippery) {
+                let (mut prev, mut cur, mut len) = (junction, first, 1);
+                let to = loop {
+                    if let Some(&to) = ids.get(&cur) {
+                        break Some(to);
+                    }
+                    let mut next = self.moves(cur, slippery).filter(|&pos| pos != prev);
+                    match (next.next(), next.next()) {
+                        (Some(pos), None) => (prev, cur, len) = (cur, pos, len + 1),
+                        _ => break None,
+                    }
+                };
+                if let Some(to) = to {
+                    edges[from].push((to, len));
+                }
+            }
+        }
+        Graph {
+            edges,
+            start: 0,
+            end: 1,
+        }
+    }
+}
+
+impl Graph {
+    // `visited` is a bitset over the junctions, cleared again on the way back.
+    fn search(&self, node: usize, visited: &mut [u64], len: usize, best: &mut Option<usize>) {
+        if node == self.end {
+            *best = (*best).max(Some(len));
+            return;
+        }
+        // The exit has a single neighbour, so leaving it any other way strands us.
+        let edges = match self.edges[node].iter().find(|(next, _)| *next == self.end) {
+            Some(edge) => std::slice::from_ref(edge),
+            None => &self.edges[node],
+        };
+        for &(next, edge) in edges {
+            let (word, bit) = (next / 64, 1 << (next % 64));
+            if visited[word] & bit == 0 {
+                visited[word] |= bit;
+                self.search(next, visited, len + edge, best);
+                visited[word] &= !bit;
+            }
+        }
+    }
+
+    pub fn longest_path(&self) -> Option<usize> {
+        let mut visited = vec![0; self.edges.len().div_ceil(64)];
+        visited[self.start / 64] |= 1 << (self.start % 64);
+        let mut best = None;
+        self.search(self.start, &mut visited, 0, &mut best);
+        best
+    }
+}
+
+pub fn longest_hike(trails: &Trails, slippery: bool) -> Result<usize, AocError> {
+    trails
+        .graph(slippery)
+        .longest_path()
+        .ok_or(AocError::Missing("path to the exit"))
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    longest_hike(&Trails::try_from(s)?, true)
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    longest_hike(&Trails::try_from(s)?, false)
+}
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    type Parsed<'a> = Trails;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        23
+    }
+
+    fn parse(input: &str) -> Result<Trails, AocError> {
+        Trails::try_from(input)
+    }
+
+    fn part1(trails: &Self::Parsed<'_>) -> impl Answer {
+        longest_hike(trails, true)
+    }
+
+    fn part2(trails: &Self::Parsed<'_>) -> impl Answer {
+        longest_hike(trails, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day23/example.txt");
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part1("#.#\n#x#\n#.#").unwrap_err().to_string(),
+            "invalid tile `x`"
+        );
+        assert_eq!(
+            part2("#.#\n#.#\n###").unwrap_err().to_string(),
+            "missing end opening"
+        );
+        assert_eq!(part1("").unwrap_err().to_string(), "missing map");
+        assert_eq!(
+            part2("#.#\n###\n#.#").unwrap_err().to_string(),
+            "missing path to the exit"
+        );
+        assert_eq!(
+            part1("#.#\n#.#\n###.").unwrap_err().to_string(),
+            "line 3: expected 3 columns, found 4"
+        );
+    }
+
+    #[test]
+    fn test_graph() {
+        let trails = Trails::try_from(TEST_INPUT).unwrap();
+        assert_eq!(trails.start, (1, 0));
+        assert_eq!(trails.end, (21, 22));
+        let junctions = trails.junctions();
+        assert_eq!(junctions.len(), 9);
+        let graph = trails.graph(false);
+        let [(first, len)] = graph.edges[graph.start][..] else {
+            panic!("Expected a single trail from the start");
+        };
+        assert_eq!((junctions[first], len), ((3, 5), 15));
+    }
+
+    #[test]
+    fn test_longest_path() {
+        let trails = Trails::try_from(TEST_INPUT).unwrap();
+        assert_eq!(trails.graph(true).longest_path(), Some(94));
+        assert_eq!(trails.graph(false).longest_path(), Some(154));
+        // More junctions than fit in a single word.
+        let chain = Graph {
+            edges: (0..100)
+                .map(|node: usize| {
+                    [
+                        node.checked_sub(1),
+                        Some(node + 1).filter(|&next| next < 100),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .map(|next| (next, 2))
+                    .collect()
+                })
+                .collect(),
+            start: 0,
+            end: 99,
+        };
+        assert_eq!(chain.longest_path(), Some(198));
+    }
+}
@@ -0,0 +1,237 @@
+use aoc_core::{
+    error::{parse_lines, parse_number, split_once},
+    Answer, AocError, Solver,
+};
+
+const TEST_AREA: (i64, i64) = (200_000_000_000_000, 400_000_000_000_000);
+const MAX_ROCK_SPEED: i64 = 1_000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Hailstone {
+    pub position: [i64; 3],
+    pub velocity: [i64; 3],
+}
+
+fn parse_vector(s: &str) -> Result<[i64; 3], AocError> {
+    let coords = s
+        .split(',')
+        .map(|c| parse_number("coordinate", c))
+        .collect::<Result<Vec<i64>, _>>()?;
+    coords
+        .try_into()
+        .map_err(|_| AocError::invalid("vector", s.trim()))
+}
+
+impl TryFrom<&str> for Hailstone {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (position, velocity) = split_once(value, "@", "hailstone")?;
+        Ok(Self {
+            position: parse_vector(position)?,
+            velocity: parse_vector(velocity)?,
+        })
+    }
+}
+
+impl Hailstone {
+    // Two stones collide at some point in time exactly when their relative
+    // position is parallel to their relative velocity.
+    pub fn collides_with(&self, other: &Hailstone) -> bool {
+        let d = [0, 1, 2].map(|i| (self.position[i] - other.position[i]) as i128);
+        let v = [0, 1, 2].map(|i| (self.velocity[i] - other.velocity[i]) as i128);
+        d[0] * v[1] == d[1] * v[0] && d[1] * v[2] == d[2] * v[1] && d[0] * v[2] == d[2] * v[0]
+    }
+}
+
+pub fn parse_hailstones(s: &str) -> Result<Vec<Hailstone>, AocError> {
+    parse_lines(s, Hailstone::try_from)
+}
+
+// Where the x/y paths of two hailstones cross, seen from a frame moving with
+// `frame` velocity. Returns the numerators of the times each stone reaches the
+// crossing and their shared, positive denominator.
+fn crossing(a: &Hailstone, b: &Hailstone, frame: [i64; 2]) -> Option<(i128, i128, i128)> {
+    let [avx, avy] = [0, 1].map(|i| (a.velocity[i] - frame[i]) as i128);
+    let [bvx, bvy] = [0, 1].map(|i| (b.velocity[i] - frame[i]) as i128);
+    let det = avx * bvy - avy * bvx;
+    if det == 0 {
+        return None;
+    }
+    let [dx, dy] = [0, 1].map(|i| (b.position[i] - a.position[i]) as i128);
+    let t = dx * bvy - dy * bvx;
+    let s = dx * avy - dy * avx;
+    Some((t * det.signum(), s * det.signum(), det.abs()))
+}
+
+pub fn crossings_within(hailstones: &[Hailstone], (min, max): (i64, i64)) -> usize {
+    let mut count = 0;
+    for (idx, a) in hailstones.iter().enumerate() {
+        for b in &hailstones[idx + 1..] {
+            let Some((t, s, den)) = crossing(a, b, [0, 0]) else {
+                continue;
+            };
+            let inside = [0, 1].into_iter().all(|i| {
+                let pos = a.position[i] as i128 * den + a.velocity[i] as i128 * t;
+                (min as i128 * den..=max as i128 * den).contains(&pos)
+            });
+            if t >= 0 && s >= 0 && inside {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+// All velocities whose largest x/y component is exactly `speed`.
+fn ring(speed: i64) -> impl Iterator<Item = [i64; 2]> {
+    (-speed..=speed).flat_map(move |vx| {
+        let step = if vx.abs() == speed { 1 } else { 2 * speed };
+        (-speed..=speed)
+            .step_by(step as usize)
+            .map(move |vy| [vx, vy])
+    })
+}
+
+fn throw_with(hailstones: &[Hailstone], [vx, vy]: [i64; 2]) -> Option<Hailstone> {
+    // Paths that are parallel in this frame, or reach the crossing together,
+    // say nothing about the rock, so take the first pair that doesn't.
+    let (a, b, (t, s, den)) = hailstones
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, a)| hailstones[idx + 1..].iter().map(move |b| (a, b)))
+        .find_map(|(a, b)| {
+            let (t, s, den) = crossing(a, b, [vx, vy])?;
+            (t != s).then_some((a, b, (t, s, den)))
+        })?;
+    if t < 0 || s < 0 || t % den != 0 || s % den != 0 {
+        return None;
+    }
+    let (ta, tb) = ((t / den) as i64, (s / den) as i64);
+    let (za, zb) = (
+        a.position[2] + a.velocity[2] * ta,
+        b.position[2] + b.velocity[2] * tb,
+    );
+    if (za - zb) % (ta - tb) != 0 {
+        return None;
+    }
+    let vz = (za - zb) / (ta - tb);
+    let velocity = [vx, vy, vz];
+    let rock = Hailstone {
+        position: [0, 1, 2].map(|i| a.position[i] + (a.velocity[i] - velocity[i]) * ta),
+        velocity,
+    };
+    hailstones
+        .iter()
+        .all(|hailstone| rock.collides_with(hailstone))
+        .then_some(rock)
+}
+
+// Search rock velocities outwards from zero. In the rock's frame every
+// hailstone passes through the same point, so two stones pin down the
+// position and the rest confirm it.
+pub fn throw(hailstones: &[Hailstone], max_speed: i64) -> Option<Hailstone> {
+    (0..=max_speed)
+        .flat_map(ring)
+        .find_map(|velocity| throw_with(hailstones, velocity))
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    Ok(crossings_within(&parse_hailstones(s)?, TEST_AREA))
+}
+
+pub fn rock_position_sum(hailstones: &[Hailstone]) -> Result<i64, AocError> {
+    let rock = throw(hailstones, MAX_ROCK_SPEED)
+        .ok_or(AocError::Missing("throw that hits every hailstone"))?;
+    Ok(rock.position.iter().sum())
+}
+
+pub fn part2(s: &str) -> Result<i64, AocError> {
+    rock_position_sum(&parse_hailstones(s)?)
+}
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    type Parsed<'a> = Vec<Hailstone>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        24
+    }
+
+    fn parse(input: &str) -> Result<Vec<Hailstone>, AocError> {
+        parse_hailstones(input)
+    }
+
+    fn part1(hailstones: &Self::Parsed<'_>) -> impl Answer {
+        crossings_within(hailstones, TEST_AREA)
+    }
+
+    fn part2(hailstones: &Self::Parsed<'_>) -> impl Answer {
+        rock_position_sum(hailstones)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day24/example.txt");
+
+    #[test]
+    fn test_crossings_within() {
+        let hailstones = parse_hailstones(TEST_INPUT).unwrap();
+        assert_eq!(
+            hailstones[0],
+            Hailstone {
+                position: [19, 13, 30],
+                velocity: [-2, 1, -2],
+            }
+        );
+        assert_eq!(crossings_within(&hailstones, (7, 27)), 2);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_hailstones("19, 13, 30 @ -2, 1, -2\n18, 19 @ -1, -1, -2")
+                .unwrap_err()
+                .to_string(),
+            "line 2: invalid vector `18, 19`"
+        );
+        assert_eq!(
+            part1("19, 13, 30 -2, 1, -2").unwrap_err().to_string(),
+            "line 1: invalid hailstone `19, 13, 30 -2, 1, -2`"
+        );
+    }
+
+    #[test]
+    fn test_throw() {
+        let hailstones = parse_hailstones(TEST_INPUT).unwrap();
+        assert_eq!(
+            throw(&hailstones, 10),
+            Some(Hailstone {
+                position: [24, 13, 10],
+                velocity: [-3, 1, 2],
+            })
+        );
+        // The first two stones move in parallel as seen from the rock.
+        let parallel = format!("21, 13, 19 @ -2, 1, -1\n{}", TEST_INPUT);
+        assert_eq!(
+            throw(&parse_hailstones(&parallel).unwrap(), 10).map(|rock| rock.position),
+            Some([24, 13, 10])
+        );
+        assert_eq!(
+            part2("19, 13, 30 @ -2, 1, -2").unwrap_err().to_string(),
+            "missing throw that hits every hailstone"
+        );
+    }
+
+    #[test]
+    fn test_ring() {
+        assert_eq!(ring(0).collect::<Vec<_>>(), vec![[0, 0]]);
+        assert_eq!(ring(1).count(), 8);
+        assert_eq!(ring(3).count(), 24);
+    }
+}
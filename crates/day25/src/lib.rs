@@ -0,0 +1,188 @@
+use aoc_core::{error::split_once, Answer, AocError, Solver};
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Wiring {
+    pub names: Vec<String>,
+    pub edges: Vec<Vec<usize>>,
+}
+
+impl TryFrom<&str> for Wiring {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut ids: HashMap<&str, usize> = HashMap::new();
+        let mut wiring = Wiring {
+            names: Vec::new(),
+            edges: Vec::new(),
+        };
+        for (idx, line) in value.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (name, others) =
+                split_once(line, ":", "component").map_err(|err| err.at_line(idx + 1))?;
+            let from = wiring.node(&mut ids, name.trim());
+            for other in others.split_whitespace() {
+                let to = wiring.node(&mut ids, other);
+                wiring.edges[from].push(to);
+                wiring.edges[to].push(from);
+            }
+        }
+        Ok(wiring)
+    }
+}
+
+impl Wiring {
+    fn node<'a>(&mut self, ids: &mut HashMap<&'a str, usize>, name: &'a str) -> usize {
+        *ids.entry(name).or_insert_with(|| {
+            self.names.push(name.to_string());
+            self.edges.push(Vec::new());
+            self.names.len() - 1
+        })
+    }
+
+    // Every wire has capacity 1 in either direction. Flow is stored for both
+    // directions, with the reverse entry negated.
+    fn augment(&self, flow: &mut HashMap<(usize, usize), i8>, source: usize, sink: usize) -> bool {
+        let mut parent = vec![None; self.names.len()];
+        let mut queue = VecDeque::from([source]);
+        parent[source] = Some(source);
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                break;
+            }
+            for &next in &self.edges[node] {
+                if parent[next].is_none() && flow.get(&(node, next)).copied().unwrap_or(0) < 1 {
+                    parent[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        if parent[sink].is_none() {
+            return false;
+        }
+        let mut node = sink;
+        while node != source {
+            let prev = parent[node].unwrap();
+            *flow.entry((prev, node)).or_default() += 1;
+            *flow.entry((node, prev)).or_default() -= 1;
+            node = prev;
+        }
+        true
+    }
+
+    // Pushes up to `max_cut + 1` units of flow from `source` to `sink`. If no
+    // more than `max_cut` get through, the nodes still reachable from `source`
+    // form its side of a minimum cut.
+    pub fn cut_side(&self, source: usize, sink: usize, max_cut: usize) -> Option<Vec<usize>> {
+        let mut flow = HashMap::new();
+        for _ in 0..=max_cut {
+            if !self.augment(&mut flow, source, sink) {
+                let mut seen = vec![false; self.names.len()];
+                let mut stack = vec![source];
+                seen[source] = true;
+                while let Some(node) = stack.pop() {
+                    for &next in &self.edges[node] {
+                        if !seen[next] && flow.get(&(node, next)).copied().unwrap_or(0) < 1 {
+                            seen[next] = true;
+                            stack.push(next);
+                        }
+                    }
+                }
+                return Some((0..seen.len()).filter(|&node| seen[node]).collect());
+            }
+        }
+        None
+    }
+
+    // Cutting a node with no more than `wires` wires off on its own splits
+    // nothing, so the flow only runs between nodes with more. That keeps at
+    // least two nodes on either side of any cut it finds.
+    pub fn partition(&self, wires: usize) -> Option<(usize, usize)> {
+        let busy = |node: &usize| self.edges[*node].len() > wires;
+        let source = (0..self.names.len()).find(busy)?;
+        (0..self.names.len())
+            .filter(|sink| *sink != source && busy(sink))
+            .find_map(|sink| {
+                let side = self.cut_side(source, sink, wires)?.len();
+                Some((side, self.names.len() - side))
+            })
+    }
+}
+
+const BUTTON: &str = "Push the big red button";
+
+pub fn group_product(wiring: &Wiring) -> Result<usize, AocError> {
+    let (a, b) = wiring
+        .partition(3)
+        .ok_or(AocError::Missing("three wires that split the machine"))?;
+    Ok(a * b)
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    group_product(&Wiring::try_from(s)?)
+}
+
+pub fn part2(_: &str) -> &'static str {
+    BUTTON
+}
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    type Parsed<'a> = Wiring;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        25
+    }
+
+    fn parse(input: &str) -> Result<Wiring, AocError> {
+        Wiring::try_from(input)
+    }
+
+    fn part1(wiring: &Self::Parsed<'_>) -> impl Answer {
+        group_product(wiring)
+    }
+
+    fn part2(_: &Self::Parsed<'_>) -> impl Answer {
+        BUTTON
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day25/example.txt");
+
+    #[test]
+    fn test_parse() {
+        let wiring = Wiring::try_from(TEST_INPUT).unwrap();
+        assert_eq!(wiring.names.len(), 15);
+        assert_eq!(wiring.edges.iter().map(Vec::len).sum::<usize>(), 2 * 33);
+        assert_eq!(
+            Wiring::try_from("jqt: rhn\n\nrsh frs")
+                .unwrap_err()
+                .to_string(),
+            "line 3: invalid component `rsh frs`"
+        );
+    }
+
+    #[test]
+    fn test_partition() {
+        let wiring = Wiring::try_from(TEST_INPUT).unwrap();
+        let (a, b) = wiring.partition(3).unwrap();
+        assert_eq!((a.min(b), a.max(b)), (6, 9));
+        assert_eq!(wiring.partition(2), None);
+        // A leaf wired to `jqt` could be cut off on its own.
+        let leaf = Wiring::try_from(format!("aaa: jqt\n{}", TEST_INPUT).as_str()).unwrap();
+        let (a, b) = leaf.partition(3).unwrap();
+        assert_eq!((a.min(b), a.max(b)), (7, 9));
+        assert_eq!(
+            part1("a: b c d").unwrap_err().to_string(),
+            "missing three wires that split the machine"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use aoc_core::{error::parse_number, Answer, AocError, Solver};
+
+#[derive(Default, Debug)]
+pub struct NumberCoords {
+    num: u64,
+    x_start: usize,
+    length: usize,
+    y: usize,
+}
+
+impl NumberCoords {
+    fn new(num: u64, x_start: usize, length: usize, y: usize) -> Self {
+        Self {
+            num,
+            x_start,
+            length,
+            y,
+        }
+    }
+
+    fn from_line_and_y(line: &str, y: usize) -> Result<Vec<Self>, AocError> {
+        let mut numbers: Vec<NumberCoords> = Vec::default();
+        let mut digits = String::default();
+        let mut cur_num_x_start: usize = 0;
+        for (x, ch) in line.chars().enumerate() {
+            if ch.is_ascii_digit() {
+                if digits.is_empty() {
+                    cur_num_x_start = x;
+                }
+                digits.push(ch);
+            } else if !digits.is_empty() {
+                numbers.push(NumberCoords::new(
+                    parse_number("number", &digits)?,
+                    cur_num_x_start,
+                    digits.len(),
+                    y,
+                ));
+                digits.clear();
+            }
+        }
+        if !digits.is_empty() {
+            numbers.push(NumberCoords::new(
+                parse_number("number", &digits)?,
+                cur_num_x_start,
+                digits.len(),
+                y,
+            ));
+        }
+        Ok(numbers)
+    }
+
+    fn is_adjacent_to(&self, location: &Location) -> bool {
+        let x_end = self.x_start + self.length;
+        location.x + 1 >= self.x_start
+            && location.x <= x_end
+            && location.y + 1 >= self.y
+            && location.y <= self.y + 1
+    }
+}
+
+struct Location {
+    x: usize,
+    y: usize,
+}
+
+// The numbers and the symbols around them, each symbol with its location.
+pub struct Schematic {
+    numbers: Vec<NumberCoords>,
+    symbols: Vec<(Location, char)>,
+}
+
+pub fn get_numbers(s: &str) -> Result<Vec<NumberCoords>, AocError> {
+    let mut numbers = Vec::new();
+    for (y, line) in s.lines().enumerate() {
+        numbers.extend(NumberCoords::from_line_and_y(line, y).map_err(|err| err.at_line(y + 1))?);
+    }
+    Ok(numbers)
+}
+
+pub fn parse_schematic(s: &str) -> Result<Schematic, AocError> {
+    let symbols = s
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars().enumerate().filter_map(move |(x, ch)| {
+                if ch.is_ascii_digit() || ch == '.' {
+                    None
+                } else {
+                    Some((Location { x, y }, ch))
+                }
+            })
+        })
+        .collect();
+    Ok(Schematic {
+        numbers: get_numbers(s)?,
+        symbols,
+    })
+}
+
+impl Schematic {
+    pub fn part_number_sum(&self) -> u64 {
+        self.numbers
+            .iter()
+            .filter(|coord| {
+                self.symbols
+                    .iter()
+                    .any(|(loc, _)| coord.is_adjacent_to(loc))
+            })
+            .map(|coord| coord.num)
+            .sum()
+    }
+
+    pub fn gear_ratio_sum(&self) -> u64 {
+        self.symbols
+            .iter()
+            .filter(|(_, ch)| *ch == '*')
+            .filter_map(|(loc, _)| {
+                let adj = self
+                    .numbers
+                    .iter()
+                    .filter(|coord| coord.is_adjacent_to(loc))
+                    .collect::<Vec<&NumberCoords>>();
+                if adj.len() == 2 {
+                    Some(adj[0].num * adj[1].num)
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+}
+
+pub fn part1(s: &str) -> Result<u64, AocError> {
+    Ok(parse_schematic(s)?.part_number_sum())
+}
+
+pub fn part2(s: &str) -> Result<u64, AocError> {
+    Ok(parse_schematic(s)?.gear_ratio_sum())
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Parsed<'a> = Schematic;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        3
+    }
+
+    fn parse(input: &str) -> Result<Schematic, AocError> {
+        parse_schematic(input)
+    }
+
+    fn part1(schematic: &Self::Parsed<'_>) -> impl Answer {
+        schematic.part_number_sum()
+    }
+
+    fn part2(schematic: &Self::Parsed<'_>) -> impl Answer {
+        schematic.gear_ratio_sum()
+    }
+}
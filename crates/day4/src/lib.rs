@@ -0,0 +1,103 @@
+use aoc_core::{
+    error::{parse_number, split_once},
+    parallel::*,
+    Answer, AocError, Solver,
+};
+use std::collections::HashSet;
+
+#[derive(Debug, Default, Clone)]
+pub struct Card {
+    score: u64,
+    matches: usize,
+}
+
+fn parse_numbers(s: &str) -> Result<HashSet<u32>, AocError> {
+    s.split_whitespace()
+        .map(|n| parse_number("number", n))
+        .collect()
+}
+
+impl TryFrom<&str> for Card {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (_, rest) = split_once(value, ": ", "card")?;
+        let (winners, numbers) = split_once(rest, " | ", "card numbers")?;
+        let winners = parse_numbers(winners)?;
+        let numbers = parse_numbers(numbers)?;
+        let matches = winners.intersection(&numbers).count();
+        let score = if matches == 0 { 0 } else { 1 << (matches - 1) };
+        Ok(Card { score, matches })
+    }
+}
+
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, AocError> {
+    par_parse_lines(s, Card::try_from)
+}
+
+pub fn total_score(cards: &[Card]) -> u64 {
+    cards.iter().map(|c| c.score).sum()
+}
+
+pub fn total_cards(cards: &[Card]) -> u64 {
+    let mut card_counts: Vec<usize> = cards.iter().map(|_| 1).collect();
+    for (cur_card_idx, card) in cards.iter().enumerate() {
+        let cur_count = card_counts[cur_card_idx];
+        for prize_count in card_counts
+            .iter_mut()
+            .skip(cur_card_idx + 1)
+            .take(card.matches)
+        {
+            *prize_count += cur_count;
+        }
+    }
+    card_counts.into_iter().sum::<usize>() as u64
+}
+
+pub fn part1(s: &str) -> Result<u64, AocError> {
+    Ok(total_score(&parse_cards(s)?))
+}
+
+pub fn part2(s: &str) -> Result<u64, AocError> {
+    Ok(total_cards(&parse_cards(s)?))
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Parsed<'a> = Vec<Card>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        4
+    }
+
+    fn parse(input: &str) -> Result<Vec<Card>, AocError> {
+        parse_cards(input)
+    }
+
+    fn part1(cards: &Self::Parsed<'_>) -> impl Answer {
+        total_score(cards)
+    }
+
+    fn part2(cards: &Self::Parsed<'_>) -> impl Answer {
+        total_cards(cards)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part1("Card 1: 41 48 | 83 x").unwrap_err().to_string(),
+            "line 1: invalid number `x`"
+        );
+        assert_eq!(
+            part2("Card 1: 41 48 83").unwrap_err().to_string(),
+            "line 1: invalid card numbers `41 48 83`"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use aoc_core::{
+    error::{parse_number, split_once},
+    interval::coalesce,
+    parallel::*,
+    Answer, AocError, Interval, Solver,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapEntry {
+    source: Interval<i64>,
+    offset: i64,
+}
+
+impl TryFrom<&str> for MapEntry {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let nums = value
+            .split_whitespace()
+            .map(|entry| parse_number::<i64>("number", entry))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [dest_start, source_start, range] = nums[..] else {
+            return Err(AocError::invalid("map entry", value));
+        };
+        if range <= 0 {
+            return Err(AocError::invalid("map entry", value));
+        }
+        Ok(Self {
+            source: Interval::new(source_start, source_start + range),
+            offset: dest_start - source_start,
+        })
+    }
+}
+
+impl MapEntry {
+    pub fn map_source(&self, num: i64) -> Option<i64> {
+        self.source.contains(num).then_some(num + self.offset)
+    }
+}
+
+pub struct Map {
+    entries: Vec<MapEntry>,
+}
+
+impl Map {
+    pub fn map_source(&self, num: i64) -> i64 {
+        for entry in self.entries.iter() {
+            if let Some(value) = entry.map_source(num) {
+                return value;
+            }
+        }
+        num
+    }
+
+    // Pieces of `interval` covered by an entry are moved by its offset, the
+    // rest maps to itself.
+    pub fn map_interval(&self, interval: Interval<i64>) -> Vec<Interval<i64>> {
+        let mut unmapped = vec![interval];
+        let mut mapped = Vec::new();
+        for entry in self.entries.iter() {
+            let mut rest = Vec::new();
+            for piece in unmapped {
+                let covered = piece.intersection(&entry.source);
+                if !covered.is_empty() {
+                    mapped.push(covered.shift(entry.offset));
+                }
+                rest.extend(piece.subtract(&entry.source));
+            }
+            unmapped = rest;
+        }
+        mapped.extend(unmapped);
+        mapped
+    }
+}
+
+pub struct Data {
+    start_numbers: Vec<i64>,
+    maps: Vec<Map>,
+}
+
+impl Data {
+    fn map_source(&self, mut num: i64) -> i64 {
+        for map in self.maps.iter() {
+            num = map.map_source(num);
+        }
+        num
+    }
+
+    pub fn calc_lowest(&self) -> i64 {
+        let mut lowest = self.map_source(self.start_numbers[0]);
+        for num in self.start_numbers.iter().skip(1) {
+            let end = self.map_source(*num);
+            if end < lowest {
+                lowest = end;
+            }
+        }
+        lowest
+    }
+
+    // Maps every seed in the ranges one at a time. Only usable on small
+    // inputs, but it doesn't share any code with the interval version.
+    pub fn calc_lowest_brute(&self) -> i64 {
+        let ranges: Vec<_> = self
+            .start_numbers
+            .chunks_exact(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect();
+        par_iter(ranges)
+            .flatten()
+            .map(|num| self.map_source(num))
+            .min()
+            .unwrap_or(0)
+    }
+
+    pub fn calc_lowest_ranges(&self) -> i64 {
+        let mut intervals = coalesce(
+            self.start_numbers
+                .chunks_exact(2)
+                .map(|pair| Interval::new(pair[0], pair[0] + pair[1])),
+        );
+        for map in self.maps.iter() {
+            intervals = coalesce(
+                intervals
+                    .into_iter()
+                    .flat_map(|interval| map.map_interval(interval)),
+            );
+        }
+        intervals[0].start
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Data, AocError> {
+    let mut maps: Vec<Map> = Vec::default();
+    let mut entries = Vec::default();
+    let seeds = s.lines().next().ok_or(AocError::Missing("seeds"))?;
+    let start_numbers = split_once(seeds, ": ", "seeds")
+        .and_then(|(_, numbers)| {
+            numbers
+                .split_whitespace()
+                .map(|n| parse_number::<i64>("seed", n))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|err| err.at_line(1))?;
+    if start_numbers.is_empty() {
+        return Err(AocError::Missing("seeds").at_line(1));
+    }
+    for (idx, line) in s.lines().enumerate().skip(2) {
+        if line.is_empty() {
+            maps.push(Map { entries });
+            entries = Vec::default();
+        } else if line.contains("map") {
+        } else {
+            entries.push(MapEntry::try_from(line).map_err(|err| err.at_line(idx + 1))?);
+        }
+    }
+    if !entries.is_empty() {
+        maps.push(Map { entries });
+    }
+    Ok(Data {
+        start_numbers,
+        maps,
+    })
+}
+
+pub fn part1(s: &str) -> Result<u64, AocError> {
+    let data = parse_input(s)?;
+    Ok(data.calc_lowest() as u64)
+}
+
+pub fn part2(s: &str) -> Result<u64, AocError> {
+    let data = parse_input(s)?;
+    Ok(data.calc_lowest_ranges() as u64)
+}
+
+pub fn part2_brute(s: &str) -> Result<u64, AocError> {
+    let data = parse_input(s)?;
+    Ok(data.calc_lowest_brute() as u64)
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Parsed<'a> = Data;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        5
+    }
+
+    fn parse(input: &str) -> Result<Data, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(data: &Self::Parsed<'_>) -> impl Answer {
+        data.calc_lowest() as u64
+    }
+
+    fn part2(data: &Self::Parsed<'_>) -> impl Answer {
+        data.calc_lowest_ranges() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const TEST_INPUT: &str = include_str!("../../../tests/data/day5/example.txt");
+
+    #[test]
+    fn test_parse_line() {
+        let foo = MapEntry::try_from("50 98 2").unwrap();
+        assert_eq!(foo.source, Interval::new(98, 100));
+        assert_eq!(foo.offset, -48);
+        assert_eq!(foo.map_source(99), Some(51));
+        let map = Map {
+            entries: vec![foo, MapEntry::try_from("52 50 48").unwrap()],
+        };
+        assert_eq!(
+            coalesce(map.map_interval(Interval::new(45, 100))),
+            vec![
+                Interval::new(45, 50),
+                Interval::new(50, 52),
+                Interval::new(52, 100)
+            ]
+        );
+        assert_eq!(
+            map.map_interval(Interval::new(97, 99)),
+            vec![Interval::new(50, 51), Interval::new(99, 100)]
+        );
+    }
+
+    #[test]
+    fn test_part2_brute() {
+        assert_eq!(part2_brute(TEST_INPUT), Ok(46));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            MapEntry::try_from("50 98").unwrap_err().to_string(),
+            "invalid map entry `50 98`"
+        );
+        let input = TEST_INPUT.replace("39 0 15", "39 0 x");
+        assert_eq!(
+            parse_input(&input).err().unwrap().to_string(),
+            "line 10: invalid number `x`"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use aoc_core::{error::parse_number, Answer, AocError, Solver};
+
+fn distance_traveled(charge_time: u64, travel_time: u64) -> u64 {
+    charge_time * travel_time
+}
+
+fn min_charge_time(total_time: u64, record_distance: u64) -> Option<u64> {
+    (1..total_time).find(|&charge_time| {
+        distance_traveled(charge_time, total_time - charge_time) > record_distance
+    })
+}
+
+fn max_charge_time(total_time: u64, record_distance: u64) -> Option<u64> {
+    (1..total_time).rev().find(|&charge_time| {
+        distance_traveled(charge_time, total_time - charge_time) > record_distance
+    })
+}
+
+fn ways_to_win(total_time: u64, record_distance: u64) -> Result<u64, AocError> {
+    match (
+        min_charge_time(total_time, record_distance),
+        max_charge_time(total_time, record_distance),
+    ) {
+        (Some(min), Some(max)) => Ok(max - min + 1),
+        _ => Err(AocError::invalid(
+            "race",
+            &format!("{} {}", total_time, record_distance),
+        )),
+    }
+}
+
+// The values after the `Time:` or `Distance:` label.
+fn values<'a>(line: Option<&'a str>, what: &'static str) -> Result<Vec<&'a str>, AocError> {
+    Ok(line
+        .ok_or(AocError::Missing(what))?
+        .split_whitespace()
+        .skip(1)
+        .collect())
+}
+
+// Each race as a time and the record distance, and the one long race part 2
+// reads once the spaces between the numbers are dropped.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Races {
+    pub races: Vec<(u64, u64)>,
+    pub long_race: (u64, u64),
+}
+
+pub fn parse_races(s: &str) -> Result<Races, AocError> {
+    let mut lines = s.lines();
+    let times = values(lines.next(), "times")?;
+    let distances = values(lines.next(), "distances")?;
+    if times.len() != distances.len() {
+        return Err(AocError::invalid("distances", &distances.join(" ")));
+    }
+    let races = times
+        .iter()
+        .zip(&distances)
+        .map(|(time, distance)| {
+            Ok((
+                parse_number("time", time)?,
+                parse_number("distance", distance)?,
+            ))
+        })
+        .collect::<Result<_, AocError>>()?;
+    Ok(Races {
+        races,
+        long_race: (
+            parse_number("time", &times.concat())?,
+            parse_number("distance", &distances.concat())?,
+        ),
+    })
+}
+
+pub fn margin(races: &Races) -> Result<u64, AocError> {
+    let mut margin: u64 = 1;
+    for &(total_time, record_distance) in &races.races {
+        margin *= ways_to_win(total_time, record_distance)?;
+    }
+    Ok(margin)
+}
+
+pub fn long_race_ways(races: &Races) -> Result<u64, AocError> {
+    let (total_time, record_distance) = races.long_race;
+    ways_to_win(total_time, record_distance)
+}
+
+pub fn part1(s: &str) -> Result<u64, AocError> {
+    margin(&parse_races(s)?)
+}
+
+pub fn part2(s: &str) -> Result<u64, AocError> {
+    long_race_ways(&parse_races(s)?)
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Parsed<'a> = Races;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        6
+    }
+
+    fn parse(input: &str) -> Result<Races, AocError> {
+        parse_races(input)
+    }
+
+    fn part1(races: &Self::Parsed<'_>) -> impl Answer {
+        margin(races)
+    }
+
+    fn part2(races: &Self::Parsed<'_>) -> impl Answer {
+        long_race_ways(races)
+    }
+}
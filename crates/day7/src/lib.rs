@@ -0,0 +1,328 @@
+use aoc_core::{
+    error::{parse_lines, parse_number},
+    Answer, AocError, Solver,
+};
+use std::collections::HashMap;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Clone, Copy)]
+pub enum Card {
+    Ace = 14,
+    King = 13,
+    Queen = 12,
+    Jack = 11,
+    Ten = 10,
+    Nine = 9,
+    Eight = 8,
+    Seven = 7,
+    Six = 6,
+    Five = 5,
+    Four = 4,
+    Three = 3,
+    Two = 2,
+}
+
+impl TryFrom<char> for Card {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
+            'A' => Self::Ace,
+            'K' => Self::King,
+            'Q' => Self::Queen,
+            'J' => Self::Jack,
+            'T' => Self::Ten,
+            '9' => Self::Nine,
+            '8' => Self::Eight,
+            '7' => Self::Seven,
+            '6' => Self::Six,
+            '5' => Self::Five,
+            '4' => Self::Four,
+            '3' => Self::Three,
+            '2' => Self::Two,
+            _ => return Err(AocError::invalid("card", &value.to_string())),
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Clone, Copy)]
+pub enum WildCard {
+    Ace = 14,
+    King = 13,
+    Queen = 12,
+    Ten = 10,
+    Nine = 9,
+    Eight = 8,
+    Seven = 7,
+    Six = 6,
+    Five = 5,
+    Four = 4,
+    Three = 3,
+    Two = 2,
+    Jack = 1,
+}
+
+impl TryFrom<char> for WildCard {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
+            'A' => Self::Ace,
+            'K' => Self::King,
+            'Q' => Self::Queen,
+            'J' => Self::Jack,
+            'T' => Self::Ten,
+            '9' => Self::Nine,
+            '8' => Self::Eight,
+            '7' => Self::Seven,
+            '6' => Self::Six,
+            '5' => Self::Five,
+            '4' => Self::Four,
+            '3' => Self::Three,
+            '2' => Self::Two,
+            _ => return Err(AocError::invalid("card", &value.to_string())),
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum HandType {
+    FiveOfKind = 7,
+    FourOfKind = 6,
+    FullHouse = 5,
+    ThreeOfKind = 4,
+    TwoPair = 3,
+    OnePair = 2,
+    HighCard = 1,
+}
+
+impl From<&[Card; 5]> for HandType {
+    fn from(value: &[Card; 5]) -> Self {
+        let mut counter: HashMap<&Card, u8> = HashMap::default();
+        for card in value {
+            counter.insert(card, counter.get(card).copied().unwrap_or_default() + 1);
+        }
+        match counter.values().max().unwrap() {
+            5 => return Self::FiveOfKind,
+            4 => return Self::FourOfKind,
+            1 => return Self::HighCard,
+            _ => {}
+        };
+        if counter.values().any(|x| *x == 3) {
+            if counter.values().any(|x| *x == 2) {
+                return Self::FullHouse;
+            }
+            return Self::ThreeOfKind;
+        }
+        if counter.values().filter(|x| **x == 2).count() == 2 {
+            return Self::TwoPair;
+        }
+        Self::OnePair
+    }
+}
+
+impl From<&[WildCard; 5]> for HandType {
+    fn from(value: &[WildCard; 5]) -> Self {
+        let mut counter: HashMap<&WildCard, u8> = HashMap::default();
+        for card in value {
+            counter.insert(card, counter.get(card).copied().unwrap_or_default() + 1);
+        }
+        let jacks = counter.remove(&WildCard::Jack).unwrap_or_default();
+        match (counter.values().copied().max().unwrap_or_default(), jacks) {
+            (5, 0) => return Self::FiveOfKind,
+            (4, 0) => return Self::FourOfKind,
+            (4, 1) => return Self::FiveOfKind,
+            (1, 0) => return Self::HighCard,
+            (1, 1) => return Self::OnePair,
+            (1, 2) => return Self::ThreeOfKind,
+            (1, 3) => return Self::FourOfKind,
+            (1, 4) => return Self::FiveOfKind,
+            (0, 5) => return Self::FiveOfKind,
+            _ => {}
+        };
+        if counter.values().any(|x| *x == 3) {
+            if counter.values().any(|x| *x == 2) {
+                return Self::FullHouse;
+            }
+            match jacks {
+                0 => return Self::ThreeOfKind,
+                1 => return Self::FourOfKind,
+                2 => return Self::FiveOfKind,
+                _ => {}
+            }
+        }
+        if counter.values().filter(|x| **x == 2).count() == 2 {
+            if jacks == 1 {
+                return Self::FullHouse;
+            } else {
+                return Self::TwoPair;
+            }
+        }
+        match jacks {
+            0 => Self::OnePair,
+            1 => Self::ThreeOfKind,
+            2 => Self::FourOfKind,
+            3 => Self::FiveOfKind,
+            _ => panic!("Shouldn't be able to get here"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Hand {
+    hand_type: HandType,
+    cards: [Card; 5],
+    bid: u64,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct WildHand {
+    hand_type: HandType,
+    cards: [WildCard; 5],
+    bid: u64,
+}
+
+fn parse_hand<C: TryFrom<char, Error = AocError>>(value: &str) -> Result<([C; 5], u64), AocError> {
+    let (hand, bid) = value
+        .split_once(' ')
+        .ok_or_else(|| AocError::invalid("hand", value))?;
+    let cards = hand
+        .chars()
+        .map(C::try_from)
+        .collect::<Result<Vec<C>, _>>()?
+        .try_into()
+        .map_err(|_| AocError::invalid("hand", hand))?;
+    Ok((cards, parse_number("bid", bid)?))
+}
+
+impl TryFrom<&str> for Hand {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (cards, bid) = parse_hand(value)?;
+        let hand_type = HandType::from(&cards);
+        Ok(Self {
+            hand_type,
+            cards,
+            bid,
+        })
+    }
+}
+
+impl From<Card> for WildCard {
+    fn from(card: Card) -> Self {
+        match card {
+            Card::Ace => Self::Ace,
+            Card::King => Self::King,
+            Card::Queen => Self::Queen,
+            Card::Jack => Self::Jack,
+            Card::Ten => Self::Ten,
+            Card::Nine => Self::Nine,
+            Card::Eight => Self::Eight,
+            Card::Seven => Self::Seven,
+            Card::Six => Self::Six,
+            Card::Five => Self::Five,
+            Card::Four => Self::Four,
+            Card::Three => Self::Three,
+            Card::Two => Self::Two,
+        }
+    }
+}
+
+impl From<&Hand> for WildHand {
+    fn from(hand: &Hand) -> Self {
+        let cards = hand.cards.map(WildCard::from);
+        Self {
+            hand_type: HandType::from(&cards),
+            cards,
+            bid: hand.bid,
+        }
+    }
+}
+
+impl TryFrom<&str> for WildHand {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (cards, bid) = parse_hand(value)?;
+        let hand_type = HandType::from(&cards);
+        Ok(Self {
+            hand_type,
+            cards,
+            bid,
+        })
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Hand>, AocError> {
+    parse_lines(s, Hand::try_from)
+}
+
+pub fn parse_input2(s: &str) -> Result<Vec<WildHand>, AocError> {
+    parse_lines(s, WildHand::try_from)
+}
+
+pub fn total_winnings(hands: &[Hand]) -> u64 {
+    let mut data: Vec<&Hand> = hands.iter().collect();
+    data.sort();
+    data.into_iter()
+        .enumerate()
+        .map(|(i, data)| (i as u64 + 1) * data.bid)
+        .sum()
+}
+
+pub fn wild_winnings(hands: &[Hand]) -> u64 {
+    let mut data: Vec<WildHand> = hands.iter().map(WildHand::from).collect();
+    data.sort();
+    data.into_iter()
+        .enumerate()
+        .map(|(i, data)| (i as u64 + 1) * data.bid)
+        .sum()
+}
+
+pub fn part1(s: &str) -> Result<u64, AocError> {
+    Ok(total_winnings(&parse_input(s)?))
+}
+
+pub fn part2(s: &str) -> Result<u64, AocError> {
+    Ok(wild_winnings(&parse_input(s)?))
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Parsed<'a> = Vec<Hand>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        7
+    }
+
+    fn parse(input: &str) -> Result<Vec<Hand>, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(hands: &Self::Parsed<'_>) -> impl Answer {
+        total_winnings(hands)
+    }
+
+    fn part2(hands: &Self::Parsed<'_>) -> impl Answer {
+        wild_winnings(hands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part1("32T3K 765\nT55X5 684").unwrap_err().to_string(),
+            "line 2: invalid card `X`"
+        );
+        assert_eq!(
+            part2("32T3 765").unwrap_err().to_string(),
+            "line 1: invalid hand `32T3`"
+        );
+    }
+}
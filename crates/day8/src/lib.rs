@@ -0,0 +1,161 @@
+use aoc_core::{error::split_once, math::lcm, Answer, AocError, Solver};
+use std::collections::HashMap;
+
+pub struct Pair<'a> {
+    left: &'a str,
+    right: &'a str,
+}
+
+pub struct Input<'a> {
+    instructions: Vec<bool>,
+    map: HashMap<&'a str, Pair<'a>>,
+}
+
+fn parse_node(line: &str) -> Result<(&str, Pair<'_>), AocError> {
+    let (from, to) = split_once(line, " = (", "node")?;
+    let (left, right) = split_once(to, ", ", "node")?;
+    let right = right
+        .strip_suffix(')')
+        .ok_or_else(|| AocError::invalid("node", line))?;
+    Ok((from, Pair { left, right }))
+}
+
+impl<'a> TryFrom<&'a str> for Input<'a> {
+    type Error = AocError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let mut iter = value.lines().enumerate();
+        let (_, instructions) = iter.next().ok_or(AocError::Missing("instructions"))?;
+        let instructions = instructions
+            .chars()
+            .map(|c| match c {
+                'L' => Ok(true),
+                'R' => Ok(false),
+                _ => Err(AocError::invalid("instruction", &c.to_string()).at_line(1)),
+            })
+            .collect::<Result<_, _>>()?;
+        let mut map = HashMap::new();
+        for (idx, line) in iter.filter(|(_, line)| !line.trim().is_empty()) {
+            let (from, pair) = parse_node(line).map_err(|err| err.at_line(idx + 1))?;
+            map.insert(from, pair);
+        }
+        for pair in map.values() {
+            for target in [pair.left, pair.right] {
+                if !map.contains_key(target) {
+                    return Err(AocError::invalid("node reference", target));
+                }
+            }
+        }
+        Ok(Self { instructions, map })
+    }
+}
+
+impl<'a> Input<'a> {
+    pub fn follow_directions(&self) -> usize {
+        let mut steps: usize = 0;
+        let mut current = "AAA";
+        let mut step_iter = self.instructions.clone().into_iter().cycle();
+        while current != "ZZZ" {
+            let cur_char = step_iter.next().unwrap();
+            let cur_pair = self.map.get(current).unwrap();
+            current = if cur_char {
+                cur_pair.left
+            } else {
+                cur_pair.right
+            };
+            steps += 1;
+        }
+        steps
+    }
+
+    pub fn get_cycle_length(&self, start: &str) -> usize {
+        let mut steps = 0;
+        let mut current = start;
+        let mut step_iter = self.instructions.clone().into_iter().cycle();
+        while !current.ends_with('Z') {
+            let cur_char = step_iter.next().unwrap();
+            let cur_pair = self.map.get(current).unwrap();
+            current = if cur_char {
+                cur_pair.left
+            } else {
+                cur_pair.right
+            };
+            steps += 1;
+        }
+        steps
+    }
+}
+
+pub fn steps_to_end(input: &Input) -> Result<usize, AocError> {
+    if !input.map.contains_key("AAA") {
+        return Err(AocError::Missing("node AAA"));
+    }
+    Ok(input.follow_directions())
+}
+
+pub fn ghost_steps(input: &Input) -> Result<usize, AocError> {
+    let lengths: Vec<usize> = input
+        .map
+        .keys()
+        .filter(|k| k.ends_with('A'))
+        .map(|k| input.get_cycle_length(k))
+        .collect();
+    if lengths.is_empty() {
+        return Err(AocError::Missing("start nodes"));
+    }
+    Ok(lengths.into_iter().fold(1, lcm))
+}
+
+pub fn part1(s: &str) -> Result<usize, AocError> {
+    steps_to_end(&Input::try_from(s)?)
+}
+
+pub fn part2(s: &str) -> Result<usize, AocError> {
+    ghost_steps(&Input::try_from(s)?)
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Parsed<'a> = Input<'a>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        8
+    }
+
+    fn parse(input: &str) -> Result<Input<'_>, AocError> {
+        Input::try_from(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> impl Answer {
+        steps_to_end(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> impl Answer {
+        ghost_steps(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            part1("LXR\n\nAAA = (AAA, AAA)").unwrap_err().to_string(),
+            "line 1: invalid instruction `X`"
+        );
+        assert_eq!(
+            part1("LR\n\nAAA = (BBB, AAA)\nBBB = BBB")
+                .unwrap_err()
+                .to_string(),
+            "line 4: invalid node `BBB = BBB`"
+        );
+        assert_eq!(
+            part2("LR\n\nAAA = (ZZZ, AAA)").unwrap_err().to_string(),
+            "invalid node reference `ZZZ`"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use aoc_core::{
+    error::{parse_lines, parse_number},
+    Answer, AocError, Solver,
+};
+
+pub fn parse_line(line: &str) -> Result<Vec<i64>, AocError> {
+    line.split_whitespace()
+        .map(|num| parse_number("value", num))
+        .collect()
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Vec<i64>>, AocError> {
+    parse_lines(s, parse_line)
+}
+
+fn extrapolate_stack(line: Vec<i64>) -> Vec<Vec<i64>> {
+    let mut stack = vec![line];
+    while !stack.last().unwrap().iter().all(|num| *num == 0) {
+        let line: Vec<i64> = stack
+            .last()
+            .unwrap()
+            .iter()
+            .zip(stack.last().unwrap().iter().skip(1))
+            .map(|(left, right)| right - left)
+            .collect();
+        stack.push(line);
+    }
+    stack
+}
+
+fn get_next_in_line(line: &[i64]) -> i64 {
+    let stack = extrapolate_stack(line.to_vec());
+
+    // A single value leaves an empty row of differences at the bottom.
+    let mut num: i64 = 0;
+    for line in stack.iter().rev() {
+        num += line.last().unwrap_or(&0);
+    }
+
+    num
+}
+
+fn get_prev_in_line(line: &[i64]) -> i64 {
+    let stack = extrapolate_stack(line.to_vec());
+
+    let mut num: i64 = 0;
+    for line in stack.iter().rev() {
+        num = line.first().unwrap_or(&0) - num;
+    }
+
+    num
+}
+
+pub fn next_sum(lines: &[Vec<i64>]) -> i64 {
+    lines.iter().map(|line| get_next_in_line(line)).sum()
+}
+
+pub fn prev_sum(lines: &[Vec<i64>]) -> i64 {
+    lines.iter().map(|line| get_prev_in_line(line)).sum()
+}
+
+pub fn part1(s: &str) -> Result<i64, AocError> {
+    Ok(next_sum(&parse_input(s)?))
+}
+
+pub fn part2(s: &str) -> Result<i64, AocError> {
+    Ok(prev_sum(&parse_input(s)?))
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Parsed<'a> = Vec<Vec<i64>>;
+    type Error = AocError;
+
+    fn day() -> u8 {
+        9
+    }
+
+    fn parse(input: &str) -> Result<Vec<Vec<i64>>, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(lines: &Self::Parsed<'_>) -> impl Answer {
+        next_sum(lines)
+    }
+
+    fn part2(lines: &Self::Parsed<'_>) -> impl Answer {
+        prev_sum(lines)
+    }
+}
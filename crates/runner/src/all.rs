@@ -0,0 +1,451 @@
+use crate::{
+    cache::{self, AnswerCache},
+    default_input,
+    history::{History, Run},
+    parse_days, resolve_year, run_part, split_year,
+    stats::{self, format_bytes, AllocStats},
+    RunError, DAYS,
+};
+use aoc_core::timing::format_duration;
+use aoc_input::Format;
+use serde_json::{json, Value};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+use threadpool::ThreadPool;
+
+pub type PartResult = Result<(String, Duration), String>;
+
+// A panicking part is reported like any other failure so one broken day
+// doesn't take the rest down with it.
+pub fn solve_part(year: u16, day: u8, part: u8, input: &str) -> PartResult {
+    match panic::catch_unwind(AssertUnwindSafe(|| run_part(year, day, part, input))) {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(RunError::Failed { message, .. })) => Err(message),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(_) => Err("panicked".to_string()),
+    }
+}
+
+pub fn part_json(part: u8, result: &PartResult) -> Value {
+    match result {
+        Ok((answer, elapsed)) => {
+            json!({"part": part, "answer": answer, "nanos": elapsed.as_nanos() as u64})
+        }
+        Err(message) => json!({"part": part, "error": message}),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: u8,
+    pub parts: [PartResult; 2],
+    pub cached: [bool; 2],
+    pub stats: Option<AllocStats>,
+}
+
+impl DayReport {
+    // Only answers are cached, so a part that failed last time is retried.
+    pub fn solve(year: u16, day: u8, input: &str, cache: &AnswerCache, force: bool) -> Self {
+        let hash = cache::input_hash(input);
+        let mut cached = [false; 2];
+        let parts = [1, 2].map(|part| {
+            let key = cache::key(year, day, part, &hash);
+            if let Some(answer) = cache.get(&key).filter(|_| !force) {
+                cached[part as usize - 1] = true;
+                return Ok((answer, Duration::ZERO));
+            }
+            let result = solve_part(year, day, part, input);
+            if let Ok((answer, _)) = &result {
+                cache.insert(key, answer.clone());
+            }
+            result
+        });
+        Self {
+            day,
+            parts,
+            cached,
+            stats: None,
+        }
+    }
+
+    pub fn failed(day: u8, message: String) -> Self {
+        Self {
+            day,
+            parts: [Err(message.clone()), Err(message)],
+            cached: [false; 2],
+            stats: None,
+        }
+    }
+
+    pub fn errors(&self) -> Vec<String> {
+        match &self.parts {
+            [Err(first), Err(second)] if first == second => {
+                vec![format!("day {}: {}", self.day, first)]
+            }
+            parts => (1..)
+                .zip(parts)
+                .filter_map(|(part, result)| {
+                    let message = result.as_ref().err()?;
+                    Some(format!("day {} part {}: {}", self.day, part, message))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let parts: Vec<Value> = (1..)
+            .zip(&self.parts)
+            .zip(self.cached)
+            .map(|((part, result), cached)| match cached {
+                true => json!({"part": part, "answer": result.as_ref().unwrap().0, "cached": true}),
+                false => part_json(part, result),
+            })
+            .collect();
+        let mut json = json!({"day": self.day, "parts": parts});
+        if let Some(stats) = self.stats {
+            json["stats"] = json!({
+                "allocations": stats.allocations,
+                "allocated_bytes": stats.allocated,
+                "peak_heap_bytes": stats.peak,
+                "peak_rss_bytes": stats.peak_rss,
+            });
+        }
+        json
+    }
+
+    // The parts solved just now, as opposed to read from the answer cache.
+    pub fn runs(&self, year: u16, input: &str) -> Vec<Run> {
+        let input_hash = cache::input_hash(input);
+        (1..)
+            .zip(&self.parts)
+            .zip(self.cached)
+            .filter_map(|((part, result), cached)| {
+                let (answer, elapsed) = result.as_ref().ok().filter(|_| !cached)?;
+                Some(Run {
+                    year,
+                    day: self.day,
+                    part,
+                    input_hash: input_hash.clone(),
+                    elapsed: *elapsed,
+                    answer: answer.clone(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parts
+            .iter()
+            .filter_map(|part| part.as_ref().ok())
+            .map(|(_, elapsed)| *elapsed)
+            .sum()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AllArgs {
+    pub year: Option<u16>,
+    pub jobs: usize,
+    pub skip: Vec<u8>,
+    pub force: bool,
+    pub stats: bool,
+}
+
+impl TryFrom<&[String]> for AllArgs {
+    type Error = RunError;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let (year, args) = split_year(args)?;
+        let mut jobs = match aoc_input::config()?.jobs {
+            Some(jobs) => jobs.get(),
+            None => thread::available_parallelism().map_or(1, |n| n.get()),
+        };
+        let mut skip = Vec::new();
+        let mut force = false;
+        let mut stats = false;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--force" => {
+                    force = true;
+                    continue;
+                }
+                "--stats" => {
+                    stats = true;
+                    continue;
+                }
+                _ => {}
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| RunError::Usage(format!("missing value for {}", arg)))?;
+            match arg.as_str() {
+                "--jobs" => {
+                    jobs =
+                        value.parse().ok().filter(|&jobs| jobs > 0).ok_or_else(|| {
+                            RunError::Usage(format!("invalid job count `{}`", value))
+                        })?
+                }
+                "--skip" => skip = parse_days(value)?,
+                _ => return Err(RunError::Usage(format!("unknown flag {}", arg))),
+            }
+        }
+        if stats && !stats::ENABLED {
+            return Err(RunError::Usage(
+                "--stats needs the runner built with `--features stats`".to_string(),
+            ));
+        }
+        Ok(AllArgs {
+            year,
+            jobs,
+            skip,
+            force,
+            stats,
+        })
+    }
+}
+
+// Slowest days first, with days that produced no answers at the bottom.
+pub fn table(reports: &mut [DayReport]) -> String {
+    reports.sort_by_key(|report| {
+        let solved = report.parts.iter().any(Result::is_ok);
+        (!solved, std::cmp::Reverse(report.total()), report.day)
+    });
+    let cell = |part: &PartResult, cached: bool| match part {
+        Ok((answer, _)) if cached => (answer.clone(), "cached".to_string()),
+        Ok((answer, elapsed)) => (answer.clone(), format_duration(*elapsed)),
+        Err(_) => ("error".to_string(), "-".to_string()),
+    };
+    let mut rows = vec![[
+        "Day".to_string(),
+        "Part 1".to_string(),
+        "Time".to_string(),
+        "Part 2".to_string(),
+        "Time".to_string(),
+        "Total".to_string(),
+    ]];
+    for report in reports.iter() {
+        let (answer1, time1) = cell(&report.parts[0], report.cached[0]);
+        let (answer2, time2) = cell(&report.parts[1], report.cached[1]);
+        rows.push([
+            report.day.to_string(),
+            answer1,
+            time1,
+            answer2,
+            time2,
+            format_duration(report.total()),
+        ]);
+    }
+    let mut widths = [0; 6];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(widths)
+                .enumerate()
+                .map(|(col, (cell, width))| match col {
+                    1 | 3 => format!("{:<width$}", cell),
+                    _ => format!("{:>width$}", cell),
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            line.trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+// Allocation counts, heap and RSS peaks for each day, heaviest heap first.
+pub fn stats_table(reports: &[DayReport]) -> String {
+    let mut rows: Vec<(u8, AllocStats)> = reports
+        .iter()
+        .filter_map(|report| Some((report.day, report.stats?)))
+        .collect();
+    rows.sort_by_key(|(day, stats)| (std::cmp::Reverse(stats.peak), *day));
+    let mut text = format!(
+        "{:>3}  {:>12}  {:>10}  {:>10}  {:>10}\n",
+        "Day", "Allocations", "Allocated", "Peak heap", "Peak RSS"
+    );
+    for (day, stats) in rows {
+        text += &format!(
+            "{:>3}  {:>12}  {:>10}  {:>10}  {:>10}\n",
+            day,
+            stats.allocations,
+            format_bytes(stats.allocated),
+            format_bytes(stats.peak),
+            stats.peak_rss.map_or("-".to_string(), format_bytes)
+        );
+    }
+    text
+}
+
+pub fn run_all(args: &AllArgs) -> Result<(), RunError> {
+    let year = resolve_year(args.year)?;
+    let start = Instant::now();
+    let cache = Arc::new(AnswerCache::open(aoc_input::config()?.answers_path()));
+    let history = Arc::new(History::open(aoc_input::config()?.history_path()));
+    // The allocation counters are shared, so measured days take turns, and
+    // cached answers would leave nothing to measure.
+    let jobs = if args.stats { 1 } else { args.jobs };
+    let pool = ThreadPool::new(jobs);
+    let (tx, rx) = mpsc::channel();
+    for day in (1..=DAYS).filter(|day| !args.skip.contains(day)) {
+        let tx = tx.clone();
+        let cache = cache.clone();
+        let history = history.clone();
+        let force = args.force || args.stats;
+        let measured = args.stats;
+        pool.execute(move || {
+            let report = match aoc_input::load(year, day, default_input(year, day)) {
+                Ok(input) => {
+                    let report = match measured {
+                        true => {
+                            let (mut report, stats) = stats::measure(|| {
+                                DayReport::solve(year, day, &input, &cache, force)
+                            });
+                            report.stats = Some(stats);
+                            report
+                        }
+                        false => DayReport::solve(year, day, &input, &cache, force),
+                    };
+                    for run in report.runs(year, &input) {
+                        history.record(run);
+                    }
+                    report
+                }
+                Err(err) => DayReport::failed(day, err.to_string()),
+            };
+            tx.send(report).unwrap();
+        });
+    }
+    drop(tx);
+    let mut reports: Vec<DayReport> = rx.iter().collect();
+    if let Err(err) = cache.save() {
+        eprintln!("Could not save the answer cache: {}", err);
+    }
+    if let Err(err) = history.save() {
+        eprintln!("Could not save the timing history: {}", err);
+    }
+    let failed = reports
+        .iter()
+        .flat_map(|report| &report.parts)
+        .filter(|part| part.is_err())
+        .count();
+    let cpu: Duration = reports.iter().map(DayReport::total).sum();
+    match aoc_input::config()?.format {
+        Format::Text => {
+            print!("{}", table(&mut reports));
+            if args.stats {
+                print!("\n{}\n", stats_table(&reports));
+            }
+            for error in reports.iter().flat_map(DayReport::errors) {
+                println!("{}", error);
+            }
+            println!(
+                "{} days of {} in {} ({} solving across {} threads)",
+                reports.len(),
+                year,
+                format_duration(start.elapsed()),
+                format_duration(cpu),
+                jobs
+            );
+        }
+        Format::Json => {
+            reports.sort_by_key(|report| report.day);
+            let days: Vec<Value> = reports.iter().map(DayReport::to_json).collect();
+            println!("{}", json!({"year": year, "days": days}));
+        }
+    }
+    if failed > 0 {
+        return Err(RunError::PartsFailed(failed));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::all::*;
+
+    #[test]
+    fn test_solve() {
+        let cache = AnswerCache::default();
+        let report = DayReport::solve(2023, 1, "two1nine\neightwothree", &cache, false);
+        // Part 1 finds no digits on the second line.
+        assert_eq!(report.parts[0], Err("line 2: missing digit".to_string()));
+        assert_eq!(report.parts[1].as_ref().unwrap().0, "112");
+        let report = DayReport::solve(2023, 19, "in{x>10:A}", &cache, false);
+        assert!(report.parts.iter().all(Result::is_err));
+        assert_eq!(report.total(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_solve_cached() {
+        let cache = AnswerCache::default();
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        let hash = cache::input_hash(input);
+        cache.insert(cache::key(2023, 1, 2, &hash), "1234".to_string());
+        let report = DayReport::solve(2023, 1, input, &cache, false);
+        assert_eq!(report.cached, [false, true]);
+        let runs = report.runs(2023, input);
+        assert_eq!(runs.len(), 1);
+        assert_eq!((runs[0].part, runs[0].answer.as_str()), (1, "142"));
+        assert_eq!(report.parts[1], Ok(("1234".to_string(), Duration::ZERO)));
+        assert_eq!(
+            cache.get(&cache::key(2023, 1, 1, &hash)),
+            Some("142".to_string())
+        );
+        let report = DayReport::solve(2023, 1, input, &cache, true);
+        assert_eq!(report.cached, [false, false]);
+        assert_eq!(report.parts[1].as_ref().unwrap().0, "142");
+        assert_eq!(
+            report.to_json()["parts"][1],
+            json!({"part": 2, "answer": "142", "nanos": report.parts[1].as_ref().unwrap().1.as_nanos() as u64})
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let ms = Duration::from_millis;
+        let mut reports = vec![
+            DayReport::failed(3, "missing session".to_string()),
+            DayReport {
+                day: 1,
+                parts: [
+                    Ok(("142".to_string(), ms(1))),
+                    Ok(("281".to_string(), Duration::ZERO)),
+                ],
+                cached: [false, true],
+                stats: None,
+            },
+            DayReport {
+                day: 2,
+                parts: [Ok(("8".to_string(), ms(5))), Err("bad".to_string())],
+                cached: [false; 2],
+                stats: None,
+            },
+        ];
+        assert_eq!(
+            table(&mut reports),
+            "\
+Day  Part 1    Time  Part 2    Time   Total
+  2  8       5.00ms  error        -  5.00ms
+  1  142     1.00ms  281     cached  1.00ms
+  3  error        -  error        -     0ns
+"
+        );
+        assert_eq!(reports[0].errors(), vec!["day 2 part 2: bad"]);
+        assert_eq!(reports[2].errors(), vec!["day 3: missing session"]);
+        assert_eq!(
+            reports[0].to_json().to_string(),
+            r#"{"day":2,"parts":[{"answer":"8","nanos":5000000,"part":1},{"error":"bad","part":2}]}"#
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    all::{part_json, solve_part, PartResult},
+    RunError,
+};
+use aoc_core::timing::format_duration;
+use aoc_input::Format;
+use aoc_solvers::examples::parse_answers;
+use serde_json::{json, Value};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+// Every file in `dir` except dotfiles and `.answers` sidecars, by name.
+pub fn inputs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        let sidecar = path.extension().is_some_and(|ext| ext == "answers");
+        if path.is_file() && !name.starts_with('.') && !sidecar {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+// An input with a `.answers` file next to it, in the same `part N: answer`
+// format as the examples, is checked against it.
+fn read_input(path: &Path) -> io::Result<(String, Vec<(u8, String)>)> {
+    let input = fs::read_to_string(path)?;
+    let expected = match fs::read_to_string(path.with_extension("answers")) {
+        Ok(answers) => parse_answers(&answers)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(err),
+    };
+    Ok((input, expected))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InputReport {
+    pub name: String,
+    pub parts: Vec<(u8, PartResult)>,
+    pub expected: Vec<(u8, String)>,
+}
+
+impl InputReport {
+    pub fn solve(year: u16, day: u8, path: &Path, parts: &[u8]) -> Self {
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into(),
+        );
+        match read_input(path) {
+            Ok((input, expected)) => Self {
+                name,
+                parts: parts
+                    .iter()
+                    .map(|&part| (part, solve_part(year, day, part, &input)))
+                    .collect(),
+                expected,
+            },
+            Err(err) => Self {
+                name,
+                parts: parts
+                    .iter()
+                    .map(|&part| (part, Err(err.to_string())))
+                    .collect(),
+                expected: Vec::new(),
+            },
+        }
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        self.expected
+            .iter()
+            .find(|(expected, _)| *expected == part)
+            .map(|(_, answer)| answer.as_str())
+    }
+
+    pub fn errors(&self) -> Vec<String> {
+        self.parts
+            .iter()
+            .filter_map(|(part, result)| match (result, self.expected(*part)) {
+                (Err(message), _) => Some(format!("{} part {}: {}", self.name, part, message)),
+                (Ok((answer, _)), Some(expected)) if answer != expected => Some(format!(
+                    "{} part {}: got {}, expected {}",
+                    self.name, part, answer, expected
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn to_json(&self) -> Value {
+        let parts: Vec<Value> = self
+            .parts
+            .iter()
+            .map(|(part, result)| {
+                let mut json = part_json(*part, result);
+                if let Some(expected) = self.expected(*part) {
+                    json["expected"] = json!(expected);
+                }
+                json
+            })
+            .collect();
+        json!({"input": self.name, "parts": parts})
+    }
+}
+
+pub fn table(reports: &[InputReport], parts: &[u8]) -> String {
+    let mut rows = vec![vec!["Input".to_string()]];
+    for part in parts {
+        rows[0].extend([format!("Part {}", part), "Time".to_string()]);
+    }
+    for report in reports {
+        let mut row = vec![report.name.clone()];
+        for (part, result) in &report.parts {
+            row.extend(match (result, report.expected(*part)) {
+                (Ok((answer, elapsed)), Some(expected)) if answer != expected => [
+                    format!("{} (expected {})", answer, expected),
+                    format_duration(*elapsed),
+                ],
+                (Ok((answer, elapsed)), _) => [answer.clone(), format_duration(*elapsed)],
+                (Err(_), _) => ["error".to_string(), "-".to_string()],
+            });
+        }
+        rows.push(row);
+    }
+    let mut widths = vec![0; rows[0].len()];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(col, (cell, &width))| match col % 2 {
+                    0 if col > 0 => format!("{:>width$}", cell),
+                    _ => format!("{:<width$}", cell),
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            line.trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+pub fn run_batch(year: u16, day: u8, dir: &Path, parts: &[u8]) -> Result<(), RunError> {
+    let paths = inputs(dir)?;
+    if paths.is_empty() {
+        return Err(RunError::Usage(format!("no inputs in {}", dir.display())));
+    }
+    let reports: Vec<InputReport> = paths
+        .iter()
+        .map(|path| InputReport::solve(year, day, path, parts))
+        .collect();
+    let errors: Vec<String> = reports.iter().flat_map(InputReport::errors).collect();
+    match aoc_input::config()?.format {
+        Format::Text => {
+            println!(
+                "{} day {}, {} inputs in {}",
+                year,
+                day,
+                reports.len(),
+                dir.display()
+            );
+            print!("{}", table(&reports, parts));
+            for error in &errors {
+                println!("{}", error);
+            }
+        }
+        Format::Json => {
+            let inputs: Vec<Value> = reports.iter().map(InputReport::to_json).collect();
+            println!("{}", json!({"year": year, "day": day, "inputs": inputs}));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(RunError::PartsFailed(errors.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::batch::*;
+    use aoc_solvers::examples::data_dir;
+    use std::time::Duration;
+
+    #[test]
+    fn test_solve() {
+        let dir = data_dir(2023).join("day1");
+        let paths = inputs(&dir).unwrap();
+        assert_eq!(
+            paths,
+            vec![dir.join("example.txt"), dir.join("example2.txt")]
+        );
+        let reports: Vec<InputReport> = paths
+            .iter()
+            .map(|path| InputReport::solve(2023, 1, path, &[1, 2]))
+            .collect();
+        assert_eq!(reports[0].name, "example.txt");
+        assert_eq!(reports[0].expected(1), Some("142"));
+        assert_eq!(reports[0].errors(), Vec::<String>::new());
+        assert_eq!(
+            reports[1].errors(),
+            vec!["example2.txt part 1: line 2: missing digit"]
+        );
+        let missing = InputReport::solve(2023, 1, &dir.join("missing.txt"), &[2]);
+        assert_eq!(missing.errors().len(), 1);
+    }
+
+    #[test]
+    fn test_table() {
+        let ms = Duration::from_millis;
+        let reports = vec![
+            InputReport {
+                name: "alice.txt".to_string(),
+                parts: vec![
+                    (1, Ok(("142".to_string(), ms(1)))),
+                    (2, Err("bad".to_string())),
+                ],
+                expected: vec![],
+            },
+            InputReport {
+                name: "bob.txt".to_string(),
+                parts: vec![
+                    (1, Ok(("7".to_string(), ms(12)))),
+                    (2, Ok(("9".to_string(), ms(3)))),
+                ],
+                expected: vec![(2, "8".to_string())],
+            },
+        ];
+        assert_eq!(
+            table(&reports, &[1, 2]),
+            "\
+Input      Part 1     Time  Part 2            Time
+alice.txt  142      1.00ms  error                -
+bob.txt    7       12.00ms  9 (expected 8)  3.00ms
+"
+        );
+        assert_eq!(
+            reports[1].errors(),
+            vec!["bob.txt part 2: got 9, expected 8"]
+        );
+        assert_eq!(
+            reports[1].to_json()["parts"][1],
+            json!({"part": 2, "answer": "9", "nanos": 3000000, "expected": "8"})
+        );
+    }
+}
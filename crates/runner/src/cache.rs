@@ -0,0 +1,78 @@
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fs, io, path::PathBuf, sync::Mutex};
+
+// Answers from earlier runs, keyed by the input they were computed from so an
+// edited or re-downloaded input is solved again.
+#[derive(Debug, Default)]
+pub struct AnswerCache {
+    path: Option<PathBuf>,
+    answers: Mutex<BTreeMap<String, String>>,
+}
+
+pub fn input_hash(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input))
+}
+
+pub fn key(year: u16, day: u8, part: u8, hash: &str) -> String {
+    format!("{}/{}/{}/{}", year, day, part, hash)
+}
+
+impl AnswerCache {
+    // A missing or unreadable file starts an empty cache rather than failing
+    // the run.
+    pub fn open(path: Option<PathBuf>) -> Self {
+        let answers = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            answers: Mutex::new(answers),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.answers.lock().unwrap().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: String, answer: String) {
+        self.answers.lock().unwrap().insert(key, answer);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let answers = self.answers.lock().unwrap();
+        fs::write(path, serde_json::to_string_pretty(&*answers)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::*;
+    use std::env;
+
+    #[test]
+    fn test_cache() {
+        let hash = input_hash("abc");
+        assert_eq!(
+            hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(key(2023, 1, 2, "ab"), "2023/1/2/ab");
+        let path = env::temp_dir().join(format!("aoc-answers-{}.json", std::process::id()));
+        let cache = AnswerCache::open(Some(path.clone()));
+        assert_eq!(cache.get(&key(2023, 1, 1, &hash)), None);
+        cache.insert(key(2023, 1, 1, &hash), "142".to_string());
+        cache.save().unwrap();
+        let cache = AnswerCache::open(Some(path.clone()));
+        assert_eq!(cache.get(&key(2023, 1, 1, &hash)), Some("142".to_string()));
+        assert_eq!(cache.get(&key(2023, 1, 2, &hash)), None);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{all::solve_part, parse_days, split_year, RunError};
+use aoc_core::timing::format_duration;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Expected {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: PathBuf,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Changed(String),
+    Failed(String),
+    MissingInput,
+}
+
+pub fn parse_manifest(s: &str) -> Result<Vec<Expected>, RunError> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(idx, line)| {
+            let invalid = |message: &str| RunError::Manifest {
+                line: idx + 1,
+                message: message.to_string(),
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [year, day, part, input, answer] = fields[..] else {
+                return Err(invalid("expected `year day part input answer`"));
+            };
+            let year = year.parse().map_err(|_| invalid("invalid year"))?;
+            let day = day.parse().map_err(|_| invalid("invalid day"))?;
+            let part = match part {
+                "1" => 1,
+                "2" => 2,
+                _ => return Err(invalid("invalid part")),
+            };
+            Ok(Expected {
+                year,
+                day,
+                part,
+                input: input.into(),
+                answer: answer.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn check(expected: &Expected, root: &Path) -> Outcome {
+    let Ok(input) = fs::read_to_string(root.join(&expected.input)) else {
+        return Outcome::MissingInput;
+    };
+    match solve_part(expected.year, expected.day, expected.part, &input) {
+        Ok((answer, _)) if answer == expected.answer => Outcome::Pass,
+        Ok((answer, _)) => Outcome::Changed(answer),
+        Err(message) => Outcome::Failed(message),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CheckArgs {
+    pub year: Option<u16>,
+    pub manifest: PathBuf,
+    pub skip: Vec<u8>,
+}
+
+impl TryFrom<&[String]> for CheckArgs {
+    type Error = RunError;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let (year, args) = split_year(args)?;
+        let mut manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../answers.txt");
+        let mut skip = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| RunError::Usage(format!("missing value for {}", arg)))?;
+            match arg.as_str() {
+                "--manifest" => manifest = value.into(),
+                "--skip" => skip = parse_days(value)?,
+                _ => return Err(RunError::Usage(format!("unknown flag {}", arg))),
+            }
+        }
+        Ok(CheckArgs {
+            year,
+            manifest,
+            skip,
+        })
+    }
+}
+
+pub fn check_all(args: &CheckArgs) -> Result<(), RunError> {
+    let manifest = aoc_input::read(&args.manifest.to_string_lossy())?;
+    let root = args.manifest.parent().unwrap_or(Path::new("."));
+    let (mut passed, mut skipped, mut failed) = (0, 0, 0);
+    for expected in parse_manifest(&manifest)? {
+        if args.year.is_some_and(|year| year != expected.year) {
+            continue;
+        }
+        if args.skip.contains(&expected.day) {
+            skipped += 1;
+            continue;
+        }
+        let label = format!(
+            "{} day {} part {} ({})",
+            expected.year,
+            expected.day,
+            expected.part,
+            expected.input.display()
+        );
+        let start = std::time::Instant::now();
+        match check(&expected, root) {
+            Outcome::Pass => {
+                passed += 1;
+                println!("ok       {} in {}", label, format_duration(start.elapsed()));
+            }
+            Outcome::Changed(answer) => {
+                failed += 1;
+                println!(
+                    "CHANGED  {}: expected {}, got {}",
+                    label, expected.answer, answer
+                );
+            }
+            Outcome::Failed(message) => {
+                failed += 1;
+                println!("FAILED   {}: {}", label, message);
+            }
+            Outcome::MissingInput => {
+                skipped += 1;
+                println!("skipped  {}: input not found", label);
+            }
+        }
+    }
+    println!("{} passed, {} failed, {} skipped", passed, failed, skipped);
+    if failed > 0 {
+        return Err(RunError::AnswersChanged(failed));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = "# year day part input answer\n\n2023 1 2 crates/day1/input.txt 281\n";
+        assert_eq!(
+            parse_manifest(manifest).unwrap(),
+            vec![Expected {
+                year: 2023,
+                day: 1,
+                part: 2,
+                input: "crates/day1/input.txt".into(),
+                answer: "281".to_string(),
+            }]
+        );
+        assert!(matches!(
+            parse_manifest("2023 1 3 input.txt 5"),
+            Err(RunError::Manifest { line: 1, .. })
+        ));
+        assert!(matches!(
+            parse_manifest("# header\n2023 1 1 input.txt"),
+            Err(RunError::Manifest { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_check() {
+        let root = std::env::temp_dir();
+        let input = format!("aoc-check-{}.txt", std::process::id());
+        fs::write(root.join(&input), "two1nine\neightwothree").unwrap();
+        let expected = |part, answer: &str| Expected {
+            year: 2023,
+            day: 1,
+            part,
+            input: input.clone().into(),
+            answer: answer.to_string(),
+        };
+        assert_eq!(check(&expected(2, "112"), &root), Outcome::Pass);
+        assert_eq!(
+            check(&expected(2, "113"), &root),
+            Outcome::Changed("112".to_string())
+        );
+        // Part 1 has no digits to read on the second line.
+        assert_eq!(
+            check(&expected(1, "0"), &root),
+            Outcome::Failed("line 2: missing digit".to_string())
+        );
+        fs::remove_file(root.join(&input)).unwrap();
+        assert_eq!(check(&expected(2, "112"), &root), Outcome::MissingInput);
+    }
+}
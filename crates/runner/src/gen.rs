@@ -0,0 +1,101 @@
+use crate::{parse_day, resolve_year, split_year, RunError};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GenArgs {
+    pub year: Option<u16>,
+    pub day: u8,
+    pub size: Option<usize>,
+    pub seed: u64,
+}
+
+impl TryFrom<&[String]> for GenArgs {
+    type Error = RunError;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let (year, args) = match args.get(1) {
+            Some(day) if !day.starts_with('-') => split_year(args)?,
+            _ => (None, args),
+        };
+        let mut args = args.iter();
+        let day = parse_day(args.next())?;
+        let mut size = None;
+        let mut seed = 0;
+        while let Some(arg) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| RunError::Usage(format!("missing value for {}", arg)))?;
+            let invalid = || RunError::Usage(format!("invalid value `{}` for {}", value, arg));
+            match arg.as_str() {
+                "--size" => size = Some(value.parse().map_err(|_| invalid())?),
+                "--seed" => seed = value.parse().map_err(|_| invalid())?,
+                _ => return Err(RunError::Usage(format!("unknown flag {}", arg))),
+            }
+        }
+        Ok(GenArgs {
+            year,
+            day,
+            size,
+            seed,
+        })
+    }
+}
+
+// The generators are written against 2023's puzzles.
+pub fn run_gen(args: &GenArgs) -> Result<(), RunError> {
+    let year = resolve_year(args.year)?;
+    let generator = aoc_gen::find(args.day).filter(|_| year == 2023);
+    let Some(generator) = generator else {
+        let days: Vec<String> = aoc_gen::GENERATORS
+            .iter()
+            .map(|generator| generator.day.to_string())
+            .collect();
+        return Err(RunError::Usage(format!(
+            "no generator for {} day {} (have 2023 days {})",
+            year,
+            args.day,
+            days.join(", ")
+        )));
+    };
+    let size = args.size.unwrap_or(generator.default_size);
+    eprintln!(
+        "{} day {}: {} {}, seed {}",
+        year, args.day, size, generator.unit, args.seed
+    );
+    print!("{}", generator.generate(size, args.seed));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_gen_args() {
+        assert_eq!(
+            GenArgs::try_from(args("2023 16 --size 500 --seed 9").as_slice()).unwrap(),
+            GenArgs {
+                year: Some(2023),
+                day: 16,
+                size: Some(500),
+                seed: 9,
+            }
+        );
+        assert_eq!(
+            GenArgs::try_from(args("19").as_slice()).unwrap(),
+            GenArgs {
+                year: None,
+                day: 19,
+                size: None,
+                seed: 0,
+            }
+        );
+        assert!(matches!(
+            GenArgs::try_from(args("19 --size big").as_slice()),
+            Err(RunError::Usage(_))
+        ));
+    }
+}
@@ -0,0 +1,430 @@
+use crate::{parse_day, resolve_year, split_year, RunError, DAYS};
+use aoc_core::timing::format_duration;
+use rusqlite::{params, Connection};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    recorded_at INTEGER NOT NULL,
+    year INTEGER NOT NULL,
+    day INTEGER NOT NULL,
+    part INTEGER NOT NULL,
+    input_hash TEXT NOT NULL,
+    nanos INTEGER NOT NULL,
+    answer TEXT NOT NULL,
+    rev TEXT
+)";
+
+// Runs shown by `aoc stats --day`, most recent last.
+const RECENT: usize = 20;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Run {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: String,
+    pub elapsed: Duration,
+    pub answer: String,
+}
+
+// The commit the runner was built from, marked dirty when the tree has
+// uncommitted changes, so timings can be lined up with the code behind them.
+pub fn git_rev() -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+    let rev = String::from_utf8(output.stdout).ok()?;
+    output.status.success().then(|| rev.trim().to_string())
+}
+
+pub fn connect(path: &Path) -> rusqlite::Result<Connection> {
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let conn = Connection::open(path)?;
+    conn.busy_timeout(Duration::from_secs(5))?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+// Solved parts waiting to be appended to the database, written in one go at
+// the end of a command so worker threads never wait on SQLite.
+#[derive(Debug, Default)]
+pub struct History {
+    path: Option<PathBuf>,
+    runs: Mutex<Vec<Run>>,
+}
+
+impl History {
+    pub fn open(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            runs: Mutex::default(),
+        }
+    }
+
+    pub fn record(&self, run: Run) {
+        self.runs.lock().unwrap().push(run);
+    }
+
+    pub fn save(&self) -> rusqlite::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let runs = std::mem::take(&mut *self.runs.lock().unwrap());
+        if runs.is_empty() {
+            return Ok(());
+        }
+        let mut conn = connect(path)?;
+        insert(&mut conn, &runs, git_rev().as_deref())
+    }
+}
+
+pub fn insert(conn: &mut Connection, runs: &[Run], rev: Option<&str>) -> rusqlite::Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() as i64);
+    let tx = conn.transaction()?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO runs (recorded_at, year, day, part, input_hash, nanos, answer, rev)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for run in runs {
+            insert.execute(params![
+                now,
+                run.year,
+                run.day,
+                run.part,
+                run.input_hash,
+                run.elapsed.as_nanos() as i64,
+                run.answer,
+                rev
+            ])?;
+        }
+    }
+    tx.commit()
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Trend {
+    pub day: u8,
+    pub part: u8,
+    pub runs: usize,
+    pub first: Duration,
+    pub best: Duration,
+    pub latest: Duration,
+    pub rev: Option<String>,
+}
+
+impl Trend {
+    // How much faster (negative) or slower the latest run is than the first.
+    pub fn change(&self) -> Option<f64> {
+        let first = self.first.as_secs_f64();
+        (self.runs > 1 && first > 0.0).then(|| (self.latest.as_secs_f64() / first - 1.0) * 100.0)
+    }
+}
+
+// Each part's runs on the input it was last run on, since times on another
+// input say nothing about the code.
+pub fn trends(conn: &Connection, year: u16) -> rusqlite::Result<Vec<Trend>> {
+    let mut select = conn.prepare(
+        "SELECT r.day, r.part, r.nanos, r.rev FROM runs r
+         WHERE r.year = ?1 AND r.input_hash = (
+             SELECT input_hash FROM runs
+             WHERE year = r.year AND day = r.day AND part = r.part
+             ORDER BY id DESC LIMIT 1
+         )
+         ORDER BY r.day, r.part, r.id",
+    )?;
+    let rows = select.query_map([year], |row| {
+        Ok((
+            row.get::<_, u8>(0)?,
+            row.get::<_, u8>(1)?,
+            Duration::from_nanos(row.get::<_, i64>(2)? as u64),
+            row.get::<_, Option<String>>(3)?,
+        ))
+    })?;
+    let mut trends: Vec<Trend> = Vec::new();
+    for row in rows {
+        let (day, part, elapsed, rev) = row?;
+        match trends.last_mut() {
+            Some(trend) if (trend.day, trend.part) == (day, part) => {
+                trend.runs += 1;
+                trend.best = trend.best.min(elapsed);
+                trend.latest = elapsed;
+                trend.rev = rev;
+            }
+            _ => trends.push(Trend {
+                day,
+                part,
+                runs: 1,
+                first: elapsed,
+                best: elapsed,
+                latest: elapsed,
+                rev,
+            }),
+        }
+    }
+    Ok(trends)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Entry {
+    pub recorded_at: String,
+    pub part: u8,
+    pub input_hash: String,
+    pub elapsed: Duration,
+    pub answer: String,
+    pub rev: Option<String>,
+}
+
+// The latest runs of one day, oldest first.
+pub fn recent(conn: &Connection, year: u16, day: u8, limit: usize) -> rusqlite::Result<Vec<Entry>> {
+    let mut select = conn.prepare(
+        "SELECT datetime(recorded_at, 'unixepoch'), part, input_hash, nanos, answer, rev
+         FROM runs WHERE year = ?1 AND day = ?2 ORDER BY id DESC LIMIT ?3",
+    )?;
+    let mut entries = select
+        .query_map(params![year, day, limit as i64], |row| {
+            Ok(Entry {
+                recorded_at: row.get(0)?,
+                part: row.get(1)?,
+                input_hash: row.get(2)?,
+                elapsed: Duration::from_nanos(row.get::<_, i64>(3)? as u64),
+                answer: row.get(4)?,
+                rev: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    entries.reverse();
+    Ok(entries)
+}
+
+pub fn trends_table(trends: &[Trend]) -> String {
+    let mut text = format!(
+        "{:>3}  {:>4}  {:>4}  {:>9}  {:>9}  {:>9}  {:>6}  {}\n",
+        "Day", "Part", "Runs", "First", "Best", "Latest", "Change", "Rev"
+    );
+    for trend in trends {
+        let change = trend
+            .change()
+            .map_or("-".to_string(), |change| format!("{:+.0}%", change));
+        let line = format!(
+            "{:>3}  {:>4}  {:>4}  {:>9}  {:>9}  {:>9}  {:>6}  {}",
+            trend.day,
+            trend.part,
+            trend.runs,
+            format_duration(trend.first),
+            format_duration(trend.best),
+            format_duration(trend.latest),
+            change,
+            trend.rev.as_deref().unwrap_or("-")
+        );
+        text += line.trim_end();
+        text += "\n";
+    }
+    text
+}
+
+pub fn recent_table(entries: &[Entry]) -> String {
+    let mut text = format!(
+        "{:<19}  {:>4}  {:<8}  {:>9}  {:<15}  {}\n",
+        "Recorded (UTC)", "Part", "Input", "Time", "Answer", "Rev"
+    );
+    for entry in entries {
+        let line = format!(
+            "{:<19}  {:>4}  {:<8}  {:>9}  {:<15}  {}",
+            entry.recorded_at,
+            entry.part,
+            &entry.input_hash[..entry.input_hash.len().min(8)],
+            format_duration(entry.elapsed),
+            entry.answer,
+            entry.rev.as_deref().unwrap_or("-")
+        );
+        text += line.trim_end();
+        text += "\n";
+    }
+    text
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StatsArgs {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+}
+
+impl TryFrom<&[String]> for StatsArgs {
+    type Error = RunError;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let (year, args) = split_year(args)?;
+        let mut stats = StatsArgs { year, day: None };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => {
+                    let day = parse_day(args.next())?;
+                    if !(1..=DAYS).contains(&day) {
+                        return Err(RunError::UnknownDay(day));
+                    }
+                    stats.day = Some(day);
+                }
+                _ => return Err(RunError::Usage(format!("unknown flag {}", arg))),
+            }
+        }
+        Ok(stats)
+    }
+}
+
+pub fn run_stats(args: &StatsArgs) -> Result<(), RunError> {
+    let year = resolve_year(args.year)?;
+    let path = aoc_input::config()?
+        .history_path()
+        .ok_or_else(|| RunError::Usage("no cache directory for the history".to_string()))?;
+    let conn = connect(&path)?;
+    match args.day {
+        Some(day) => {
+            let entries = recent(&conn, year, day, RECENT)?;
+            if entries.is_empty() {
+                println!("No runs of {} day {} recorded yet", year, day);
+            } else {
+                print!("{}", recent_table(&entries));
+            }
+        }
+        None => {
+            let trends = trends(&conn, year)?;
+            if trends.is_empty() {
+                println!("No runs of {} recorded yet", year);
+            } else {
+                print!("{}", trends_table(&trends));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    fn run(day: u8, part: u8, hash: &str, ms: u64) -> Run {
+        Run {
+            year: 2023,
+            day,
+            part,
+            input_hash: hash.to_string(),
+            elapsed: Duration::from_millis(ms),
+            answer: "142".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_trends() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        insert(
+            &mut conn,
+            &[run(1, 1, "a", 40), run(1, 2, "a", 9)],
+            Some("r1"),
+        )
+        .unwrap();
+        insert(
+            &mut conn,
+            &[run(1, 1, "a", 10), run(2, 1, "b", 5)],
+            Some("r2"),
+        )
+        .unwrap();
+        insert(&mut conn, &[run(1, 1, "a", 20)], None).unwrap();
+        // A new input starts the part's trend over.
+        insert(&mut conn, &[run(1, 2, "c", 30)], Some("r3")).unwrap();
+        let trends = trends(&conn, 2023).unwrap();
+        assert_eq!(
+            trends,
+            [
+                Trend {
+                    day: 1,
+                    part: 1,
+                    runs: 3,
+                    first: Duration::from_millis(40),
+                    best: Duration::from_millis(10),
+                    latest: Duration::from_millis(20),
+                    rev: None,
+                },
+                Trend {
+                    day: 1,
+                    part: 2,
+                    runs: 1,
+                    first: Duration::from_millis(30),
+                    best: Duration::from_millis(30),
+                    latest: Duration::from_millis(30),
+                    rev: Some("r3".to_string()),
+                },
+                Trend {
+                    day: 2,
+                    part: 1,
+                    runs: 1,
+                    first: Duration::from_millis(5),
+                    best: Duration::from_millis(5),
+                    latest: Duration::from_millis(5),
+                    rev: Some("r2".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            trends_table(&trends[..2]),
+            "\
+Day  Part  Runs      First       Best     Latest  Change  Rev
+  1     1     3    40.00ms    10.00ms    20.00ms    -50%  -
+  1     2     1    30.00ms    30.00ms    30.00ms       -  r3
+"
+        );
+        let recent = recent(&conn, 2023, 1, 2).unwrap();
+        assert_eq!(
+            recent.iter().map(|entry| entry.elapsed).collect::<Vec<_>>(),
+            [Duration::from_millis(20), Duration::from_millis(30)]
+        );
+        assert_eq!(recent[1].input_hash, "c");
+    }
+
+    #[test]
+    fn test_save() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.sqlite", std::process::id()));
+        let history = History::open(Some(path.clone()));
+        history.record(run(3, 1, "a", 1));
+        history.save().unwrap();
+        history.save().unwrap();
+        assert_eq!(trends(&connect(&path).unwrap(), 2023).unwrap()[0].runs, 1);
+        fs::remove_file(path).unwrap();
+        History::default().save().unwrap();
+    }
+
+    #[test]
+    fn test_stats_args() {
+        assert_eq!(
+            StatsArgs::try_from(args("2023 --day 12").as_slice()).unwrap(),
+            StatsArgs {
+                year: Some(2023),
+                day: Some(12),
+            }
+        );
+        assert!(matches!(
+            StatsArgs::try_from(args("--day 30").as_slice()),
+            Err(RunError::UnknownDay(30))
+        ));
+    }
+}
@@ -0,0 +1,536 @@
+mod all;
+mod batch;
+mod cache;
+mod check;
+mod gen;
+mod history;
+mod stats;
+mod tui;
+mod verify;
+mod watch;
+
+use aoc_core::timing::format_duration;
+use aoc_input::{Format, InputError};
+use aoc_solvers::{find, DAYS, YEARS};
+use serde_json::json;
+#[cfg(feature = "stats")]
+#[global_allocator]
+static ALLOC: stats::CountingAlloc = stats::CountingAlloc;
+
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug)]
+pub enum RunError {
+    Usage(String),
+    UnknownYear(u16),
+    UnknownDay(u8),
+    Io(io::Error),
+    Input(InputError),
+    History(rusqlite::Error),
+    Failed {
+        day: u8,
+        part: u8,
+        message: String,
+    },
+    Manifest {
+        line: usize,
+        message: String,
+    },
+    AnswersChanged(usize),
+    PartsFailed(usize),
+    Disagreement {
+        day: u8,
+        part: u8,
+        input: String,
+        answers: Vec<(&'static str, String)>,
+    },
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Usage(msg) => write!(
+                f,
+                "{}\nusage: aoc run [YEAR] <day> [--part 1|2] [--input PATH|URL | --inputs DIR] [--stats]\n       aoc run [YEAR] <day> --verify [--part 1|2] [--input PATH|URL | --random N [--size N] [--seed S]]\n       aoc watch [YEAR] <day> [--part 1|2] [--input PATH]\n       aoc fetch [YEAR] <day>\n       aoc desc [YEAR] <day>\n       aoc open [YEAR] <day>\n       aoc check-all [YEAR] [--manifest PATH] [--skip DAY,...]\n       aoc all [YEAR] [--jobs N] [--skip DAY,...] [--force] [--stats]\n       aoc tui [YEAR] [--jobs N] [--skip DAY,...]\n       aoc stats [YEAR] [--day N]\n       aoc gen [YEAR] <day> [--size N] [--seed S]",
+                msg
+            ),
+            Self::UnknownYear(year) => write!(
+                f,
+                "there are no solutions for {} (have {:?})",
+                year, YEARS
+            ),
+            Self::UnknownDay(day) => write!(f, "day {} is not implemented (1..={})", day, DAYS),
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Input(err) => write!(f, "{}", err),
+            Self::History(err) => write!(f, "timing history: {}", err),
+            Self::Failed { day, part, message } => {
+                write!(f, "day {} part {} failed: {}", day, part, message)
+            }
+            Self::Manifest { line, message } => write!(f, "manifest line {}: {}", line, message),
+            Self::AnswersChanged(count) => write!(f, "answers changed: {}", count),
+            Self::PartsFailed(count) => write!(f, "parts failed: {}", count),
+            Self::Disagreement {
+                day,
+                part,
+                input,
+                answers,
+            } => {
+                let answers: Vec<String> = answers
+                    .iter()
+                    .map(|(name, answer)| format!("{} {}", name, answer))
+                    .collect();
+                write!(
+                    f,
+                    "day {} part {} disagrees on {}: {}",
+                    day,
+                    part,
+                    input,
+                    answers.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl From<io::Error> for RunError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<InputError> for RunError {
+    fn from(err: InputError) -> Self {
+        Self::Input(err)
+    }
+}
+
+impl From<rusqlite::Error> for RunError {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::History(err)
+    }
+}
+
+fn parse_day(arg: Option<&String>) -> Result<u8, RunError> {
+    let day = arg.ok_or_else(|| RunError::Usage("missing day".to_string()))?;
+    day.parse()
+        .map_err(|_| RunError::Usage(format!("invalid day `{}`", day)))
+}
+
+// A leading argument that isn't a flag names the year; commands fall back to
+// the configured default year without one.
+fn split_year(args: &[String]) -> Result<(Option<u16>, &[String]), RunError> {
+    match args.first() {
+        Some(year) if !year.starts_with('-') => {
+            let year = year
+                .parse()
+                .map_err(|_| RunError::Usage(format!("invalid year `{}`", year)))?;
+            Ok((Some(year), &args[1..]))
+        }
+        _ => Ok((None, args)),
+    }
+}
+
+fn resolve_year(year: Option<u16>) -> Result<u16, RunError> {
+    let year = match year {
+        Some(year) => year,
+        None => aoc_input::config()?.year,
+    };
+    match YEARS.contains(&year) {
+        true => Ok(year),
+        false => Err(RunError::UnknownYear(year)),
+    }
+}
+
+// Puzzle commands work for any day of any year, solved here or not.
+fn puzzle_args(args: &[String]) -> Result<(u16, u8), RunError> {
+    let (year, args) = match args.get(1) {
+        Some(_) => split_year(args)?,
+        None => (None, args),
+    };
+    let day = parse_day(args.first())?;
+    if !(1..=25).contains(&day) {
+        return Err(RunError::Usage(format!("there is no day {}", day)));
+    }
+    if let Some(arg) = args.get(1) {
+        return Err(RunError::Usage(format!("unexpected argument `{}`", arg)));
+    }
+    let year = match year {
+        Some(year) => year,
+        None => aoc_input::config()?.year,
+    };
+    Ok((year, day))
+}
+
+fn open_browser(url: &str) -> bool {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener)
+        .arg(url)
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn parse_days(list: &str) -> Result<Vec<u8>, RunError> {
+    list.split(',')
+        .map(|day| {
+            day.parse()
+                .map_err(|_| RunError::Usage(format!("invalid day `{}`", day)))
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct RunArgs {
+    year: Option<u16>,
+    day: u8,
+    part: Option<u8>,
+    input: Option<String>,
+    inputs: Option<String>,
+    stats: bool,
+}
+
+impl TryFrom<&[String]> for RunArgs {
+    type Error = RunError;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let (year, args) = match args.get(1) {
+            Some(day) if !day.starts_with('-') => split_year(args)?,
+            _ => (None, args),
+        };
+        let mut args = args.iter();
+        let day = parse_day(args.next())?;
+        if !(1..=DAYS).contains(&day) {
+            return Err(RunError::UnknownDay(day));
+        }
+        let mut part = None;
+        let mut input = None;
+        let mut inputs = None;
+        let mut stats = false;
+        while let Some(arg) = args.next() {
+            if arg == "--stats" {
+                stats = true;
+                continue;
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| RunError::Usage(format!("missing value for {}", arg)))?;
+            match arg.as_str() {
+                "--part" => match value.as_str() {
+                    "1" => part = Some(1),
+                    "2" => part = Some(2),
+                    _ => return Err(RunError::Usage(format!("invalid part `{}`", value))),
+                },
+                "--input" => input = Some(value.clone()),
+                "--inputs" => inputs = Some(value.clone()),
+                _ => return Err(RunError::Usage(format!("unknown flag {}", arg))),
+            }
+        }
+        if input.is_some() && inputs.is_some() {
+            return Err(RunError::Usage(
+                "--inputs runs every file in a directory, drop --input".to_string(),
+            ));
+        }
+        if stats && inputs.is_some() {
+            return Err(RunError::Usage(
+                "--stats measures a single input, drop --inputs".to_string(),
+            ));
+        }
+        if stats && !stats::ENABLED {
+            return Err(RunError::Usage(
+                "--stats needs the runner built with `--features stats`".to_string(),
+            ));
+        }
+        Ok(RunArgs {
+            year,
+            day,
+            part,
+            input,
+            inputs,
+            stats,
+        })
+    }
+}
+
+// 2023 predates multi-year support and keeps its days at the top level, other
+// years get a directory of their own.
+fn year_dir(year: u16) -> PathBuf {
+    match year {
+        2023 => PathBuf::new(),
+        _ => year.to_string().into(),
+    }
+}
+
+fn day_dir(year: u16, day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(year_dir(year))
+        .join(format!("day{}", day))
+}
+
+fn default_input(year: u16, day: u8) -> PathBuf {
+    day_dir(year, day).join("input.txt")
+}
+
+fn example_input(year: u16, day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/data")
+        .join(year_dir(year))
+        .join(format!("day{}", day))
+        .join("example.txt")
+}
+
+fn run_part(year: u16, day: u8, part: u8, input: &str) -> Result<(String, Duration), RunError> {
+    let solver = find(year, day).ok_or(RunError::UnknownDay(day))?.parts[part as usize - 1];
+    let start = Instant::now();
+    let answer = solver(input).map_err(|message| RunError::Failed { day, part, message })?;
+    Ok((answer, start.elapsed()))
+}
+
+fn run_day(args: RunArgs) -> Result<(), RunError> {
+    let config = aoc_input::config()?;
+    let year = resolve_year(args.year)?;
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    if let Some(dir) = &args.inputs {
+        return batch::run_batch(year, args.day, Path::new(dir), &parts);
+    }
+    let input = match args.input {
+        Some(path) => aoc_input::read(&path)?,
+        None => config.load(year, args.day, default_input(year, args.day))?,
+    };
+    let text = config.format == Format::Text;
+    if text {
+        println!("{} day {}", year, args.day);
+    }
+    let parse = find(year, args.day)
+        .ok_or(RunError::UnknownDay(args.day))?
+        .parse;
+    let start = Instant::now();
+    let parsed = parse(&input).is_ok().then(|| start.elapsed());
+    if let (true, Some(elapsed)) = (text, parsed) {
+        println!("Parse: {}", format_duration(elapsed));
+    }
+    let history = history::History::open(config.history_path());
+    let input_hash = cache::input_hash(&input);
+    let mut results = Vec::new();
+    for part in parts {
+        let (result, alloc) = stats::measure(|| run_part(year, args.day, part, &input));
+        let (answer, elapsed) = result?;
+        history.record(history::Run {
+            year,
+            day: args.day,
+            part,
+            input_hash: input_hash.clone(),
+            elapsed,
+            answer: answer.clone(),
+        });
+        if text {
+            println!("Part {}: {} ({})", part, answer, format_duration(elapsed));
+            if args.stats {
+                println!("        {}", alloc.summary());
+            }
+        }
+        let mut json = all::part_json(part, &Ok((answer, elapsed)));
+        if args.stats {
+            json["allocations"] = json!(alloc.allocations);
+            json["peak_heap_bytes"] = json!(alloc.peak);
+            json["peak_rss_bytes"] = json!(alloc.peak_rss);
+        }
+        results.push(json);
+    }
+    if !text {
+        let parse_nanos = parsed.map(|elapsed| elapsed.as_nanos() as u64);
+        println!(
+            "{}",
+            json!({"year": year, "day": args.day, "parse_nanos": parse_nanos, "parts": results})
+        );
+    }
+    if let Err(err) = history.save() {
+        eprintln!("Could not save the timing history: {}", err);
+    }
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), RunError> {
+    match args.first().map(String::as_str) {
+        Some("run") if args.iter().any(|arg| arg == "--verify") => {
+            verify::run_verify(&verify::VerifyArgs::try_from(&args[1..])?)
+        }
+        Some("run") => run_day(RunArgs::try_from(&args[1..])?),
+        Some("watch") => watch::run_watch(&watch::WatchArgs::try_from(&args[1..])?),
+        Some("fetch") => {
+            let (year, day) = puzzle_args(&args[1..])?;
+            let config = aoc_input::config()?;
+            let input = config.fetch(year, day)?;
+            match config.cache_path(year, day) {
+                Some(path) => println!("Saved {} bytes to {}", input.len(), path.display()),
+                None => print!("{}", input),
+            }
+            Ok(())
+        }
+        Some("desc") => {
+            let (year, day) = puzzle_args(&args[1..])?;
+            let html = aoc_input::config()?.puzzle(year, day)?;
+            print!("{}", aoc_input::puzzle::to_markdown(&html));
+            Ok(())
+        }
+        Some("open") => {
+            let (year, day) = puzzle_args(&args[1..])?;
+            // Keep a copy for `aoc desc`, but the browser doesn't need it.
+            if let Err(err) = aoc_input::config()?.puzzle(year, day) {
+                eprintln!("Could not cache the puzzle: {}", err);
+            }
+            let url = aoc_input::puzzle_url(year, day);
+            if !open_browser(&url) {
+                println!("{}", url);
+            }
+            Ok(())
+        }
+        Some("all") => all::run_all(&all::AllArgs::try_from(&args[1..])?),
+        Some("stats") => history::run_stats(&history::StatsArgs::try_from(&args[1..])?),
+        Some("tui") => tui::run_tui(&all::AllArgs::try_from(&args[1..])?),
+        Some("gen") => gen::run_gen(&gen::GenArgs::try_from(&args[1..])?),
+        Some("check-all") => check::check_all(&check::CheckArgs::try_from(&args[1..])?),
+        Some(command) => Err(RunError::Usage(format!("unknown command `{}`", command))),
+        None => Err(RunError::Usage("missing command".to_string())),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_run_args() {
+        assert_eq!(
+            RunArgs::try_from(args("19 --part 2 --input foo.txt").as_slice()).unwrap(),
+            RunArgs {
+                year: None,
+                day: 19,
+                part: Some(2),
+                input: Some("foo.txt".to_string()),
+                inputs: None,
+                stats: false,
+            }
+        );
+        assert_eq!(
+            RunArgs::try_from(args("7").as_slice()).unwrap(),
+            RunArgs {
+                year: None,
+                day: 7,
+                part: None,
+                input: None,
+                inputs: None,
+                stats: false,
+            }
+        );
+        assert_eq!(
+            RunArgs::try_from(args("2022 7 --part 1").as_slice()).unwrap(),
+            RunArgs {
+                year: Some(2022),
+                day: 7,
+                part: Some(1),
+                input: None,
+                inputs: None,
+                stats: false,
+            }
+        );
+        assert_eq!(
+            RunArgs::try_from(args("7 --stats --part 1").as_slice())
+                .is_ok_and(|args| args.stats && args.part == Some(1)),
+            stats::ENABLED
+        );
+        assert!(matches!(
+            RunArgs::try_from(args("twenty 7").as_slice()),
+            Err(RunError::Usage(_))
+        ));
+        assert!(matches!(
+            RunArgs::try_from(args("26").as_slice()),
+            Err(RunError::UnknownDay(26))
+        ));
+        assert!(matches!(
+            RunArgs::try_from(args("3 --part 3").as_slice()),
+            Err(RunError::Usage(_))
+        ));
+        assert!(matches!(
+            RunArgs::try_from(args("3 --input").as_slice()),
+            Err(RunError::Usage(_))
+        ));
+        assert_eq!(
+            RunArgs::try_from(args("12 --inputs friends --part 1").as_slice())
+                .unwrap()
+                .inputs,
+            Some("friends".to_string())
+        );
+        assert!(matches!(
+            RunArgs::try_from(args("12 --inputs friends --input foo.txt").as_slice()),
+            Err(RunError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_puzzle_args() {
+        assert_eq!(puzzle_args(&args("2022 5")).unwrap(), (2022, 5));
+        assert!(matches!(
+            puzzle_args(&args("2022 26")),
+            Err(RunError::Usage(_))
+        ));
+        assert!(matches!(
+            puzzle_args(&args("2022 5 6")),
+            Err(RunError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_year_dir() {
+        assert!(default_input(2023, 5).ends_with("runner/../day5/input.txt"));
+        assert!(default_input(2022, 5).ends_with("runner/../2022/day5/input.txt"));
+        assert!(example_input(2023, 5).ends_with("tests/data/day5/example.txt"));
+    }
+
+    #[test]
+    fn test_run_part() {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        assert_eq!(run_part(2023, 1, 1, input).unwrap().0, "142");
+        assert!(matches!(
+            run_part(2022, 1, 1, input),
+            Err(RunError::UnknownDay(1))
+        ));
+        let err = run_part(2023, 19, 1, "in{x>10:A}").unwrap_err();
+        assert!(matches!(
+            err,
+            RunError::Failed {
+                day: 19,
+                part: 1,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs,
+    sync::atomic::{AtomicU64, Ordering::Relaxed},
+};
+
+pub const ENABLED: bool = cfg!(feature = "stats");
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+// The system allocator with running totals. Counters are process-wide, so
+// measurements are only per day when days run one at a time.
+#[cfg_attr(not(feature = "stats"), allow(dead_code))]
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size() as u64, Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            CURRENT.fetch_sub(layout.size() as u64, Relaxed);
+            record_alloc(new_size as u64);
+        }
+        new
+    }
+}
+
+#[cfg_attr(not(feature = "stats"), allow(dead_code))]
+fn record_alloc(size: u64) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    ALLOCATED.fetch_add(size, Relaxed);
+    let current = CURRENT.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(current, Relaxed);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub allocated: u64,
+    // Highest heap use above what was live when measuring started.
+    pub peak: u64,
+    pub peak_rss: Option<u64>,
+}
+
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    reset_peak_rss();
+    let base = CURRENT.load(Relaxed);
+    PEAK.store(base, Relaxed);
+    let (allocations, allocated) = (ALLOCATIONS.load(Relaxed), ALLOCATED.load(Relaxed));
+    let result = f();
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        allocated: ALLOCATED.load(Relaxed) - allocated,
+        peak: PEAK.load(Relaxed).saturating_sub(base),
+        peak_rss: peak_rss(),
+    };
+    (result, stats)
+}
+
+// Linux only. Clearing the high-water mark makes the next reading cover just
+// the code in between, where the kernel allows it.
+fn reset_peak_rss() {
+    let _ = fs::write("/proc/self/clear_refs", "5");
+}
+
+fn peak_rss() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{}B", bytes),
+        1024..1_048_576 => format!("{:.2}KiB", bytes as f64 / 1024.0),
+        1_048_576..1_073_741_824 => format!("{:.2}MiB", bytes as f64 / 1_048_576.0),
+        _ => format!("{:.2}GiB", bytes as f64 / 1_073_741_824.0),
+    }
+}
+
+impl AllocStats {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} allocations, {} allocated, {} peak heap, {} peak RSS",
+            self.allocations,
+            format_bytes(self.allocated),
+            format_bytes(self.peak),
+            self.peak_rss.map_or("-".to_string(), format_bytes)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::*;
+
+    #[test]
+    fn test_measure() {
+        let (len, stats) = measure(|| vec![0u8; 4096].len());
+        assert_eq!(len, 4096);
+        if ENABLED {
+            assert!(stats.allocations >= 1);
+            assert!(stats.allocated >= 4096);
+        }
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.50KiB");
+        assert_eq!(format_bytes(3 << 20), "3.00MiB");
+    }
+}
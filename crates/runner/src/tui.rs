@@ -0,0 +1,390 @@
+use crate::{
+    all::{AllArgs, PartResult},
+    default_input, example_input, resolve_year, RunError, DAYS,
+};
+use aoc_core::timing::format_duration;
+use aoc_solvers::{solve_with_events, SolveEvent};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    widgets::{Block, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+use std::{
+    fs, io, panic,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+use threadpool::ThreadPool;
+
+const TICK: Duration = Duration::from_millis(100);
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Source {
+    Real,
+    Example,
+}
+
+impl Source {
+    fn load(self, year: u16, day: u8) -> Result<String, String> {
+        match self {
+            Self::Real => {
+                aoc_input::load(year, day, default_input(year, day)).map_err(|err| err.to_string())
+            }
+            Self::Example => fs::read_to_string(example_input(year, day))
+                .map_err(|_| "no example input".to_string()),
+        }
+    }
+
+    fn toggle(self) -> Self {
+        match self {
+            Self::Real => Self::Example,
+            Self::Example => Self::Real,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub enum PartState {
+    #[default]
+    Idle,
+    Queued,
+    // How far along the part says it is, if it reports progress at all.
+    Running(Instant, Option<u8>),
+    Done(PartResult),
+}
+
+#[derive(Debug)]
+pub struct Update {
+    pub source: Source,
+    pub day: u8,
+    pub part: u8,
+    pub state: PartState,
+}
+
+pub struct Dashboard {
+    year: u16,
+    real: Vec<[PartState; 2]>,
+    example: Vec<[PartState; 2]>,
+    source: Source,
+    selected: usize,
+    skip: Vec<u8>,
+    pool: ThreadPool,
+    tx: Sender<Update>,
+    rx: Receiver<Update>,
+}
+
+impl Dashboard {
+    pub fn new(year: u16, args: &AllArgs) -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            year,
+            real: vec![Default::default(); DAYS as usize],
+            example: vec![Default::default(); DAYS as usize],
+            source: Source::Real,
+            selected: 0,
+            skip: args.skip.clone(),
+            pool: ThreadPool::new(args.jobs),
+            tx,
+            rx,
+        }
+    }
+
+    fn days(&self, source: Source) -> &[[PartState; 2]] {
+        match source {
+            Source::Real => &self.real,
+            Source::Example => &self.example,
+        }
+    }
+
+    fn days_mut(&mut self, source: Source) -> &mut [[PartState; 2]] {
+        match source {
+            Source::Real => &mut self.real,
+            Source::Example => &mut self.example,
+        }
+    }
+
+    pub fn apply(&mut self, update: Update) {
+        self.days_mut(update.source)[update.day as usize - 1][update.part as usize - 1] =
+            update.state;
+    }
+
+    pub fn run_day(&mut self, day: u8) {
+        let (year, source) = (self.year, self.source);
+        self.days_mut(source)[day as usize - 1] = [PartState::Queued, PartState::Queued];
+        let tx = self.tx.clone();
+        self.pool.execute(move || {
+            let send = |part, state| {
+                // The dashboard may have quit while this day was still running.
+                let _ = tx.send(Update {
+                    source,
+                    day,
+                    part,
+                    state,
+                });
+            };
+            match source.load(year, day) {
+                Ok(input) => {
+                    let (events, received) = mpsc::channel();
+                    thread::scope(|scope| {
+                        scope.spawn(|| solve_with_events(year, day, &input, events));
+                        let mut start = Instant::now();
+                        for event in received {
+                            match event {
+                                SolveEvent::ParseDone(Ok(_)) => {
+                                    start = Instant::now();
+                                    send(1, PartState::Running(start, None));
+                                }
+                                SolveEvent::ParseDone(Err(message)) => {
+                                    for part in 1..=2 {
+                                        send(part, PartState::Done(Err(message.clone())));
+                                    }
+                                }
+                                SolveEvent::Progress { part, percent } => {
+                                    send(part, PartState::Running(start, Some(percent)));
+                                }
+                                SolveEvent::PartDone { part, result } => {
+                                    send(part, PartState::Done(result));
+                                    if part == 1 {
+                                        start = Instant::now();
+                                        send(2, PartState::Running(start, None));
+                                    }
+                                }
+                            }
+                        }
+                    });
+                }
+                Err(message) => {
+                    for part in 1..=2 {
+                        send(part, PartState::Done(Err(message.clone())));
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn run_all(&mut self) {
+        let days: Vec<u8> = (1..=DAYS).filter(|day| !self.skip.contains(day)).collect();
+        for day in days {
+            self.run_day(day);
+        }
+    }
+
+    pub fn rows(&self, now: Instant) -> Vec<[String; 6]> {
+        let cell = |state: &PartState| match state {
+            PartState::Idle => (String::new(), String::new()),
+            PartState::Queued => ("queued".to_string(), String::new()),
+            PartState::Running(start, percent) => {
+                let elapsed = now.duration_since(*start);
+                let tick = (elapsed.as_millis() / TICK.as_millis()) as usize;
+                let status = match percent {
+                    Some(percent) => format!("running {}%", percent),
+                    None => format!("running {}", SPINNER[tick % SPINNER.len()]),
+                };
+                (status, format_duration(elapsed))
+            }
+            PartState::Done(Ok((answer, elapsed))) => (answer.clone(), format_duration(*elapsed)),
+            PartState::Done(Err(_)) => ("error".to_string(), "-".to_string()),
+        };
+        (1..=DAYS)
+            .zip(self.days(self.source))
+            .map(|(day, parts)| {
+                let status = match parts {
+                    [PartState::Done(Ok(_)), PartState::Done(Ok(_))] => "ok",
+                    [PartState::Done(_), PartState::Done(_)] => "failed",
+                    [PartState::Idle, PartState::Idle] => "",
+                    _ => "busy",
+                };
+                let (answer1, time1) = cell(&parts[0]);
+                let (answer2, time2) = cell(&parts[1]);
+                [
+                    day.to_string(),
+                    status.to_string(),
+                    answer1,
+                    time1,
+                    answer2,
+                    time2,
+                ]
+            })
+            .collect()
+    }
+
+    fn details(&self) -> String {
+        let day = self.selected + 1;
+        let errors: Vec<String> = (1..)
+            .zip(&self.days(self.source)[self.selected])
+            .filter_map(|(part, state)| match state {
+                PartState::Done(Err(message)) => Some(format!("part {}: {}", part, message)),
+                _ => None,
+            })
+            .collect();
+        match errors.is_empty() {
+            true => format!("day {}", day),
+            false => format!("day {}: {}", day, errors.join("; ")),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .areas(frame.area());
+        let input = match self.source {
+            Source::Real => "real",
+            Source::Example => "example",
+        };
+        frame.render_widget(
+            Paragraph::new(format!("year: {}, input: {}", self.year, input)).block(
+                Block::bordered()
+                    .title("aoc dashboard: [r]un day, run [a]ll, [e]xample/real input, [q]uit"),
+            ),
+            header,
+        );
+        let rows = self.rows(Instant::now()).into_iter().map(|row| {
+            let color = match row[1].as_str() {
+                "ok" => Color::Green,
+                "failed" => Color::Red,
+                "busy" => Color::Yellow,
+                _ => Color::Reset,
+            };
+            Row::new(row).style(Style::default().fg(color))
+        });
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Length(6),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+        ];
+        let table = Table::new(rows, widths)
+            .header(Row::new([
+                "Day", "Status", "Part 1", "Time", "Part 2", "Time",
+            ]))
+            .row_highlight_style(Style::default().bg(Color::DarkGray))
+            .block(Block::bordered().title("days"));
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, body, &mut state);
+        frame.render_widget(
+            Paragraph::new(self.details()).block(Block::bordered()),
+            footer,
+        );
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            while let Ok(update) = self.rx.try_recv() {
+                self.apply(update);
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+            if !event::poll(TICK)? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        self.selected = self.selected.saturating_sub(1)
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        self.selected = (self.selected + 1).min(DAYS as usize - 1)
+                    }
+                    KeyCode::Enter | KeyCode::Char('r') => self.run_day(self.selected as u8 + 1),
+                    KeyCode::Char('a') => self.run_all(),
+                    KeyCode::Char('e') | KeyCode::Tab => self.source = self.source.toggle(),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+pub fn run_tui(args: &AllArgs) -> Result<(), RunError> {
+    let mut dashboard = Dashboard::new(resolve_year(args.year)?, args);
+    let mut terminal = ratatui::init();
+    // Solver panics are caught and shown in the table, so only let panics on
+    // the main thread restore the terminal and print.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some("main") {
+            hook(info);
+        }
+    }));
+    let result = dashboard.event_loop(&mut terminal);
+    ratatui::restore();
+    Ok(result?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tui::*;
+
+    #[test]
+    fn test_rows() {
+        let mut dashboard = Dashboard::new(
+            2023,
+            &AllArgs {
+                year: None,
+                jobs: 1,
+                skip: Vec::new(),
+                force: false,
+                stats: false,
+            },
+        );
+        let now = Instant::now();
+        let update = |day, part, state| Update {
+            source: Source::Real,
+            day,
+            part,
+            state,
+        };
+        dashboard.apply(update(1, 1, PartState::Done(Ok(("142".to_string(), TICK)))));
+        dashboard.apply(update(1, 2, PartState::Running(now, None)));
+        dashboard.apply(update(2, 1, PartState::Done(Err("bad".to_string()))));
+        dashboard.apply(update(2, 2, PartState::Done(Err("bad".to_string()))));
+        let rows = dashboard.rows(now);
+        assert_eq!(rows.len(), DAYS as usize);
+        assert_eq!(rows[0][..4], ["1", "busy", "142", "100.00ms"]);
+        assert_eq!(rows[0][4..], ["running |", "0ns"]);
+        assert_eq!(rows[1][1..3], ["failed", "error"]);
+        assert_eq!(rows[2][1], "");
+        dashboard.apply(update(1, 2, PartState::Running(now, Some(42))));
+        assert_eq!(dashboard.rows(now)[0][4], "running 42%");
+        dashboard.selected = 1;
+        assert_eq!(dashboard.details(), "day 2: part 1: bad; part 2: bad");
+        dashboard.source = dashboard.source.toggle();
+        assert_eq!(dashboard.rows(now)[0][1], "");
+    }
+
+    #[test]
+    fn test_run_day() {
+        let mut dashboard = Dashboard::new(
+            2023,
+            &AllArgs {
+                year: None,
+                jobs: 1,
+                skip: Vec::new(),
+                force: false,
+                stats: false,
+            },
+        );
+        dashboard.source = Source::Example;
+        dashboard.run_day(2);
+        assert_eq!(dashboard.example[1], [PartState::Queued, PartState::Queued]);
+        dashboard.pool.join();
+        while let Ok(update) = dashboard.rx.try_recv() {
+            dashboard.apply(update);
+        }
+        let rows = dashboard.rows(Instant::now());
+        assert_eq!(rows[1][1..3], ["ok", "8"]);
+        assert_eq!(rows[1][4], "2286");
+    }
+}
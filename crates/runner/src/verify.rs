@@ -0,0 +1,242 @@
+use crate::{default_input, parse_day, resolve_year, split_year, RunError};
+use aoc_core::timing::format_duration;
+use aoc_solvers::{variants, Variant, DAYS};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VerifyArgs {
+    pub year: Option<u16>,
+    pub day: u8,
+    pub part: Option<u8>,
+    pub input: Option<String>,
+    pub random: usize,
+    pub size: Option<usize>,
+    pub seed: u64,
+}
+
+impl TryFrom<&[String]> for VerifyArgs {
+    type Error = RunError;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let (year, args) = match args.get(1) {
+            Some(day) if !day.starts_with('-') => split_year(args)?,
+            _ => (None, args),
+        };
+        let mut args = args.iter();
+        let day = parse_day(args.next())?;
+        if !(1..=DAYS).contains(&day) {
+            return Err(RunError::UnknownDay(day));
+        }
+        let mut verify = VerifyArgs {
+            year,
+            day,
+            part: None,
+            input: None,
+            random: 0,
+            size: None,
+            seed: 0,
+        };
+        while let Some(arg) = args.next() {
+            if arg == "--verify" {
+                continue;
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| RunError::Usage(format!("missing value for {}", arg)))?;
+            let invalid = || RunError::Usage(format!("invalid value `{}` for {}", value, arg));
+            match arg.as_str() {
+                "--part" => match value.as_str() {
+                    "1" => verify.part = Some(1),
+                    "2" => verify.part = Some(2),
+                    _ => return Err(RunError::Usage(format!("invalid part `{}`", value))),
+                },
+                "--input" => verify.input = Some(value.clone()),
+                "--random" => verify.random = value.parse().map_err(|_| invalid())?,
+                "--size" => verify.size = Some(value.parse().map_err(|_| invalid())?),
+                "--seed" => verify.seed = value.parse().map_err(|_| invalid())?,
+                _ => return Err(RunError::Usage(format!("unknown flag {}", arg))),
+            }
+        }
+        if verify.random > 0 && verify.input.is_some() {
+            return Err(RunError::Usage(
+                "--random replaces the puzzle input, drop --input".to_string(),
+            ));
+        }
+        Ok(verify)
+    }
+}
+
+pub type VariantTimes = Vec<(&'static str, Duration)>;
+
+// Runs every variant on `input` and returns the answer they share, with how
+// long each took.
+pub fn compare(
+    day: u8,
+    part: u8,
+    label: &str,
+    input: &str,
+    variants: &[Variant],
+) -> Result<(String, VariantTimes), RunError> {
+    let mut answers = Vec::new();
+    let mut timings = Vec::new();
+    for variant in variants {
+        let start = Instant::now();
+        let answer = (variant.solve)(input);
+        timings.push((variant.name, start.elapsed()));
+        answers.push((variant.name, answer));
+    }
+    if answers.windows(2).any(|pair| pair[0].1 != pair[1].1) {
+        return Err(RunError::Disagreement {
+            day,
+            part,
+            input: label.to_string(),
+            answers: answers
+                .into_iter()
+                .map(|(name, answer)| match answer {
+                    Ok(answer) => (name, answer),
+                    Err(message) => (name, format!("error ({})", message)),
+                })
+                .collect(),
+        });
+    }
+    match answers.into_iter().next() {
+        Some((_, Ok(answer))) => Ok((answer, timings)),
+        Some((_, Err(message))) => Err(RunError::Failed { day, part, message }),
+        None => Err(RunError::Usage(format!(
+            "nothing to verify for part {}",
+            part
+        ))),
+    }
+}
+
+fn parts(args: &VerifyArgs) -> Vec<u8> {
+    match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+// Slow variants sit out the puzzle input, generated inputs are kept small
+// enough for them.
+pub fn run_verify(args: &VerifyArgs) -> Result<(), RunError> {
+    let year = resolve_year(args.year)?;
+    let all = variants(year, args.day);
+    if all.is_empty() {
+        let days: Vec<String> = (1..=DAYS)
+            .filter(|&day| !variants(year, day).is_empty())
+            .map(|day| day.to_string())
+            .collect();
+        return Err(RunError::Usage(format!(
+            "{} day {} has only one implementation (days {} have more)",
+            year,
+            args.day,
+            days.join(", ")
+        )));
+    }
+    let for_part = |part: u8, slow: bool| -> Vec<Variant> {
+        all.iter()
+            .filter(|variant| variant.part == part && (slow || !variant.slow))
+            .copied()
+            .collect()
+    };
+    if args.random == 0 {
+        let config = aoc_input::config()?;
+        let input = match &args.input {
+            Some(path) => aoc_input::read(path)?,
+            None => config.load(year, args.day, default_input(year, args.day))?,
+        };
+        println!("{} day {}", year, args.day);
+        for part in parts(args) {
+            let variants = for_part(part, false);
+            if variants.len() < 2 {
+                if !for_part(part, true).is_empty() {
+                    println!("Part {}: too slow for the puzzle input, try --random", part);
+                }
+                continue;
+            }
+            let (answer, timings) = compare(args.day, part, "the puzzle input", &input, &variants)?;
+            let timings: Vec<String> = timings
+                .iter()
+                .map(|(name, elapsed)| format!("{} {}", name, format_duration(*elapsed)))
+                .collect();
+            println!("Part {}: {}", part, answer);
+            println!("        {}", timings.join(", "));
+        }
+        return Ok(());
+    }
+    let generator = aoc_gen::find(args.day)
+        .filter(|_| year == 2023)
+        .ok_or_else(|| RunError::Usage(format!("no generator for {} day {}", year, args.day)))?;
+    let size = args.size.unwrap_or(generator.default_size);
+    println!(
+        "{} day {}, {} generated inputs of {} {}",
+        year, args.day, args.random, size, generator.unit
+    );
+    let seeds = args.seed..args.seed + args.random as u64;
+    for part in parts(args) {
+        let variants = for_part(part, true);
+        if variants.len() < 2 {
+            continue;
+        }
+        for seed in seeds.clone() {
+            let input = generator.generate(size, seed);
+            let label = format!("seed {} (size {})", seed, size);
+            compare(args.day, part, &label, &input, &variants)?;
+        }
+        let names: Vec<&str> = variants.iter().map(|variant| variant.name).collect();
+        println!("Part {}: {} agree", part, names.join(", "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::verify::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_verify_args() {
+        assert_eq!(
+            VerifyArgs::try_from(args("2023 18 --verify --random 20 --seed 4").as_slice()).unwrap(),
+            VerifyArgs {
+                year: Some(2023),
+                day: 18,
+                part: None,
+                input: None,
+                random: 20,
+                size: None,
+                seed: 4,
+            }
+        );
+        assert!(matches!(
+            VerifyArgs::try_from(args("18 --verify --random 2 --input foo.txt").as_slice()),
+            Err(RunError::Usage(_))
+        ));
+        assert!(matches!(
+            VerifyArgs::try_from(args("18 --verify --random many").as_slice()),
+            Err(RunError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_compare() {
+        let variants = variants(2023, 5);
+        let input = aoc_gen::find(5).unwrap().generate(4, 1);
+        let (answer, timings) = compare(5, 2, "seed 1", &input, &variants).unwrap();
+        assert_eq!(Ok(answer), aoc_solvers::solve(2023, 5, 2, &input));
+        assert_eq!(timings.len(), 2);
+        let broken = Variant {
+            name: "broken",
+            solve: |_| Ok("0".to_string()),
+            ..variants[0]
+        };
+        let err = compare(5, 2, "seed 1", &input, &[variants[0], broken]).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("day 5 part 2 disagrees on seed 1: intervals"));
+        assert!(err.to_string().ends_with(", broken 0"));
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{day_dir, default_input, parse_day, resolve_year, split_year, RunError, DAYS};
+use aoc_core::timing::format_duration;
+use std::{
+    env,
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+const POLL: Duration = Duration::from_millis(250);
+// Editors save in several writes, so a change only counts once it holds
+// still this long.
+const SETTLE: Duration = Duration::from_millis(100);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct WatchArgs {
+    pub year: Option<u16>,
+    pub day: u8,
+    pub part: Option<u8>,
+    pub input: Option<String>,
+}
+
+impl TryFrom<&[String]> for WatchArgs {
+    type Error = RunError;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let (year, args) = match args.get(1) {
+            Some(day) if !day.starts_with('-') => split_year(args)?,
+            _ => (None, args),
+        };
+        let mut args = args.iter();
+        let day = parse_day(args.next())?;
+        if !(1..=DAYS).contains(&day) {
+            return Err(RunError::UnknownDay(day));
+        }
+        let mut watch = WatchArgs {
+            year,
+            day,
+            part: None,
+            input: None,
+        };
+        while let Some(arg) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| RunError::Usage(format!("missing value for {}", arg)))?;
+            match arg.as_str() {
+                "--part" => match value.as_str() {
+                    "1" => watch.part = Some(1),
+                    "2" => watch.part = Some(2),
+                    _ => return Err(RunError::Usage(format!("invalid part `{}`", value))),
+                },
+                "--input" => watch.input = Some(value.clone()),
+                _ => return Err(RunError::Usage(format!("unknown flag {}", arg))),
+            }
+        }
+        Ok(watch)
+    }
+}
+
+pub type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+fn collect(path: &Path, files: &mut Snapshot) -> io::Result<()> {
+    let meta = match fs::metadata(path) {
+        Ok(meta) => meta,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    if meta.is_dir() {
+        for entry in fs::read_dir(path)? {
+            collect(&entry?.path(), files)?;
+        }
+    } else {
+        files.push((path.to_path_buf(), meta.modified().ok()));
+    }
+    Ok(())
+}
+
+// Every file under `paths` with its modification time. Files that appear or
+// vanish change the snapshot as much as edits do.
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut files = Vec::new();
+    for path in paths {
+        if let Err(err) = collect(path, &mut files) {
+            eprintln!("Could not read {}: {}", path.display(), err);
+        }
+    }
+    files.sort();
+    files
+}
+
+// The file the day would read, fetched now if there isn't one yet so there
+// is something to watch. A path given on the command line is made absolute,
+// since cargo runs the day from the workspace root.
+fn input_path(year: u16, day: u8, input: Option<&str>) -> Result<PathBuf, RunError> {
+    if let Some(path) = input {
+        return Ok(fs::canonicalize(path)?);
+    }
+    let local = default_input(year, day);
+    if local.exists() {
+        return Ok(local);
+    }
+    let config = aoc_input::config()?;
+    config.load(year, day, &local)?;
+    config
+        .cache_path(year, day)
+        .filter(|path| path.exists())
+        .ok_or_else(|| RunError::Usage("no input file to watch, pass --input".to_string()))
+}
+
+pub fn command(day: u8, part: Option<u8>, input: &Path) -> Command {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."))
+        .args(["run", "--release", "--quiet", "--package"])
+        .arg(format!("day{}", day))
+        .args(["--", "--time", "--input"])
+        .arg(input);
+    if let Some(part) = part {
+        command.args(["--part", &part.to_string()]);
+    }
+    command
+}
+
+fn redraw(year: u16, day: u8, watched: &[PathBuf], mut command: Command) {
+    print!("\x1b[2J\x1b[H");
+    println!("{} day {}, watching:", year, day);
+    for path in watched {
+        println!("  {}", path.display());
+    }
+    println!();
+    let start = Instant::now();
+    match command.status() {
+        Ok(status) if status.success() => {
+            println!("\nDone in {}", format_duration(start.elapsed()))
+        }
+        Ok(status) => println!(
+            "\nFailed ({}) after {}",
+            status,
+            format_duration(start.elapsed())
+        ),
+        Err(err) => println!("\nCould not run cargo: {}", err),
+    }
+}
+
+// Rebuilds and reruns the day's binary whenever its crate or its input
+// changes, until interrupted.
+pub fn run_watch(args: &WatchArgs) -> Result<(), RunError> {
+    let year = resolve_year(args.year)?;
+    let input = input_path(year, args.day, args.input.as_deref())?;
+    let dir = day_dir(year, args.day);
+    let watched = vec![dir.join("Cargo.toml"), dir.join("src"), input.clone()];
+    let mut last = None;
+    loop {
+        let mut current = snapshot(&watched);
+        if last.is_some() && last.as_ref() != Some(&current) {
+            thread::sleep(SETTLE);
+            current = snapshot(&watched);
+        }
+        if last.as_ref() != Some(&current) {
+            redraw(
+                year,
+                args.day,
+                &watched,
+                command(args.day, args.part, &input),
+            );
+            last = Some(current);
+        }
+        thread::sleep(POLL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::watch::*;
+    use std::process;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_watch_args() {
+        assert_eq!(
+            WatchArgs::try_from(args("2023 12 --part 2").as_slice()).unwrap(),
+            WatchArgs {
+                year: Some(2023),
+                day: 12,
+                part: Some(2),
+                input: None,
+            }
+        );
+        assert!(matches!(
+            WatchArgs::try_from(args("12 --stats").as_slice()),
+            Err(RunError::Usage(_))
+        ));
+        let command = command(12, Some(1), Path::new("big.txt"));
+        let command: Vec<_> = command.get_args().collect();
+        assert_eq!(
+            command,
+            [
+                "run",
+                "--release",
+                "--quiet",
+                "--package",
+                "day12",
+                "--",
+                "--time",
+                "--input",
+                "big.txt",
+                "--part",
+                "1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        let watched = [dir.join("src"), dir.join("input.txt")];
+        let before = snapshot(&watched);
+        assert_eq!(before.len(), 1);
+        assert_eq!(snapshot(&watched), before);
+        fs::File::options()
+            .write(true)
+            .open(dir.join("src/lib.rs"))
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        assert_ne!(snapshot(&watched), before);
+        fs::write(dir.join("input.txt"), "1").unwrap();
+        assert_eq!(snapshot(&watched).len(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_input_path() {
+        let path = input_path(2023, 1, Some("Cargo.toml")).unwrap();
+        assert!(path.is_absolute());
+        assert_eq!(path, env::current_dir().unwrap().join("Cargo.toml"));
+        assert!(matches!(
+            input_path(2023, 1, Some("no-such-input.txt")),
+            Err(RunError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,27 @@
+use std::process::Command;
+
+fn check_all(args: &[&str]) {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("check-all")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_answers_unchanged() {
+    // Days 5 and 14 take minutes in a debug build.
+    check_all(&["--skip", "5,14"]);
+}
+
+#[test]
+#[ignore = "brute forces day 5, run with `cargo test --release -- --ignored`"]
+fn test_all_answers_unchanged() {
+    check_all(&[]);
+}